@@ -0,0 +1,846 @@
+// Effects, levels, resampling, noise reduction, and the metronome sounds
+
+// -------- Imports --------
+use crate::*; // Constants, errors, and the other modules through the crate root
+
+// -------- Structs --------
+// Handles for the dial controlled effects on a track
+pub struct Equaliser {
+    pub polarity: Arc<RwLock<(bool, bool)>>, // Whether the left and right channels are flipped
+    pub sub_bass: EqFilterHandle,
+    pub bass: EqFilterHandle,
+    pub low_mids: EqFilterHandle,
+    pub high_mids: EqFilterHandle,
+    pub treble: EqFilterHandle,
+    pub pan: PanningControlHandle,
+    pub high_pass: FilterHandle,
+    pub low_pass: FilterHandle,
+    pub trim: VolumeControlHandle, // Turns the whole track down to make up for boosted bands
+    pub kinds: [FilterKind; 5],    // Shapes the filters currently have
+    pub values: [i32; 6],          // Dial values the filters were last set to
+    pub makeup: bool,              // Whether the trim is being used
+}
+
+impl Equaliser {
+    pub fn add_to(builder: &mut TrackBuilder) -> Equaliser {
+        // Adds the filters to a track and keeps their handles for real time updating
+        Equaliser {
+            // Added first so that panning mixes the already flipped channels
+            polarity: builder.add_effect(PolarityBuilder {
+                flipped: Arc::new(RwLock::new((false, false))),
+            }),
+            sub_bass: builder.add_effect(EqFilterBuilder::new(
+                EqFilterKind::LowShelf,
+                40.0,
+                0.0,
+                1.0,
+            )),
+            bass: builder.add_effect(EqFilterBuilder::new(EqFilterKind::Bell, 155.0, 0.0, 0.82)),
+            low_mids: builder.add_effect(EqFilterBuilder::new(
+                EqFilterKind::Bell,
+                625.0,
+                0.0,
+                0.83,
+            )),
+            high_mids: builder.add_effect(EqFilterBuilder::new(
+                EqFilterKind::Bell,
+                1500.0,
+                0.0,
+                1.5,
+            )),
+            treble: builder.add_effect(EqFilterBuilder::new(
+                EqFilterKind::HighShelf,
+                12000.0,
+                0.0,
+                0.75,
+            )),
+            pan: builder.add_effect(PanningControlBuilder::default()),
+            // Both start fully open and bypassed so they don't colour the sound until they're moved
+            high_pass: builder.add_effect(
+                FilterBuilder::new()
+                    .mode(FilterMode::HighPass)
+                    .cutoff(HIGH_PASS_OPEN as f64)
+                    .mix(Mix::DRY),
+            ),
+            low_pass: builder.add_effect(
+                FilterBuilder::new()
+                    .mode(FilterMode::LowPass)
+                    .cutoff(LOW_PASS_OPEN as f64)
+                    .mix(Mix::DRY),
+            ),
+            trim: builder.add_effect(VolumeControlBuilder::new(0.0)),
+            kinds: default_filter_kinds(),
+            values: [0, 0, 0, 0, 0, 0],
+            makeup: false,
+        }
+    }
+
+    pub fn set_trim(&mut self, tween: Tween) {
+        // Applies or removes the makeup gain for the current dial values
+        self.trim.set_volume(
+            if self.makeup {
+                makeup_gain(self.values)
+            } else {
+                0.0
+            },
+            tween,
+        );
+    }
+
+    pub fn set_cutoffs(&mut self, high_pass: f32, low_pass: f32, tween: Tween) {
+        // Moves the high and low pass cutoffs, bypassing either one when it's fully open
+        self.high_pass.set_cutoff(high_pass as f64, tween);
+        self.high_pass.set_mix(
+            if high_pass <= HIGH_PASS_OPEN {
+                Mix::DRY
+            } else {
+                Mix::WET
+            },
+            tween,
+        );
+        self.low_pass.set_cutoff(low_pass as f64, tween);
+        self.low_pass.set_mix(
+            if low_pass >= LOW_PASS_OPEN {
+                Mix::DRY
+            } else {
+                Mix::WET
+            },
+            tween,
+        );
+    }
+
+    pub fn follow(&mut self, recording: &Recording, tween: Tween) {
+        // Sets everything that isn't captured in snapshots to the recording's values
+        self.set_kinds(recording.filter_kinds);
+        self.set_cutoffs(recording.high_pass, recording.low_pass, tween);
+        if recording.auto_makeup != self.makeup {
+            self.makeup = recording.auto_makeup;
+            self.set_trim(tween);
+        }
+        Tracker::write(
+            self.polarity.clone(),
+            (recording.invert_left, recording.invert_right),
+        );
+    }
+
+    pub fn flatten(&mut self, tween: Tween) {
+        // Sets every effect to neutral without touching any saved values
+        self.set([0, 0, 0, 0, 0, 0], tween);
+        self.follow(&Recording::new(&String::new()), tween);
+    }
+
+    pub fn set_kinds(&mut self, kinds: [FilterKind; 5]) {
+        // Changes the shape of any bands that are different
+        if kinds == self.kinds {
+            return;
+        }
+        let filters = [
+            &mut self.sub_bass,
+            &mut self.bass,
+            &mut self.low_mids,
+            &mut self.high_mids,
+            &mut self.treble,
+        ];
+        for (band, filter) in filters.into_iter().enumerate() {
+            if kinds[band] != self.kinds[band] {
+                filter.set_kind(kinds[band].to_kira());
+            }
+        }
+        self.kinds = kinds;
+    }
+
+    pub fn set(&mut self, values: [i32; 6], tween: Tween) {
+        // Sets the filters to the dial values
+        self.sub_bass.set_gain(dial_to_gain(values[0]), tween);
+        self.bass.set_gain(dial_to_gain(values[1]), tween);
+        self.low_mids.set_gain(dial_to_gain(values[2]), tween);
+        self.high_mids.set_gain(dial_to_gain(values[3]), tween);
+        self.treble.set_gain(dial_to_gain(values[4]), tween);
+        self.pan.set_panning(dial_to_panning(values[5]), tween);
+        self.values = values;
+        self.set_trim(tween);
+    }
+
+    pub fn set_captured(&mut self, values: [i32; 6], tween: Tween, pan_glide: Duration) {
+        // Sets the filters to a captured frame, gliding the pan so stereo moves aren't abrupt
+        self.set(values, tween);
+        if pan_glide > tween.duration {
+            self.pan.set_panning(
+                dial_to_panning(values[5]),
+                Tween {
+                    duration: pan_glide,
+                    easing: Easing::InOutPowi(2), // Eases both ends and never goes past the target
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
+
+pub fn parameter_tween(glide_ms: u32) -> Tween {
+    // Tween used for dial changes during playback
+    Tween {
+        duration: Duration::from_millis(glide_ms as u64),
+        ..Default::default()
+    }
+}
+
+// Flips the polarity of either channel on a track
+pub struct PolarityBuilder {
+    pub flipped: Arc<RwLock<(bool, bool)>>, // Left, right
+}
+
+pub struct Polarity {
+    pub flipped: Arc<RwLock<(bool, bool)>>,
+    pub current: (bool, bool), // Last value read so the audio thread never has to wait for the lock
+}
+
+impl EffectBuilder for PolarityBuilder {
+    type Handle = Arc<RwLock<(bool, bool)>>;
+
+    fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+        let effect = Polarity {
+            flipped: self.flipped.clone(),
+            current: (false, false),
+        };
+        (Box::new(effect), self.flipped)
+    }
+}
+
+impl Effect for Polarity {
+    fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+        if let Ok(flipped) = self.flipped.try_read() {
+            self.current = *flipped;
+        }
+        for frame in input.iter_mut() {
+            *frame = invert(*frame, self.current);
+        }
+    }
+}
+
+pub fn invert(frame: Frame, (left, right): (bool, bool)) -> Frame {
+    // Multiplies the flipped channels by -1
+    Frame::new(
+        if left { -frame.left } else { frame.left },
+        if right { -frame.right } else { frame.right },
+    )
+}
+
+pub fn meter_fill(amplitude: f32) -> f32 {
+    // How much of the output meter a level fills, on a decibel scale so quiet audio still shows
+    if amplitude <= 0.0 {
+        return 0.0;
+    }
+    ((20.0 * amplitude.log10() - METER_FLOOR) / -METER_FLOOR).clamp(0.0, 1.0)
+}
+
+// Measures the audio passing through the end of a track
+pub struct MeterBuilder {
+    pub levels: Arc<RwLock<Levels>>,
+}
+
+pub struct Meter {
+    pub levels: Arc<RwLock<Levels>>,
+    pub pending: Levels, // Levels that couldn't be written yet because the UI was reading them
+}
+
+impl EffectBuilder for MeterBuilder {
+    type Handle = Arc<RwLock<Levels>>;
+
+    fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+        let effect = Meter {
+            levels: self.levels.clone(),
+            pending: Levels::new(),
+        };
+        (Box::new(effect), self.levels)
+    }
+}
+
+impl Effect for Meter {
+    fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+        self.pending.measure(input);
+        // Never waits for the lock so the audio thread can't stall
+        if let Ok(mut levels) = self.levels.try_write() {
+            for channel in 0..2 {
+                levels.peak[channel] = levels.peak[channel].max(self.pending.peak[channel]);
+                levels.held[channel] = levels.held[channel].max(self.pending.peak[channel]);
+            }
+            levels.rms = self.pending.rms;
+            self.pending.peak = [0.0, 0.0];
+        }
+    }
+}
+
+// Plays recorded input as it comes in
+pub struct MonitorSound {
+    pub buffer: Arc<Mutex<VecDeque<Frame>>>, // Frames pushed by the recorder
+    pub rate: u32,                           // Sample rate the frames were captured at
+    pub position: f64, // How far the output is between the two oldest frames in the buffer
+}
+
+impl MonitorSound {
+    pub fn new(buffer: Arc<Mutex<VecDeque<Frame>>>, rate: u32) -> MonitorSound {
+        MonitorSound {
+            buffer,
+            rate,
+            position: 0.0,
+        }
+    }
+
+    pub fn pull(&mut self, buffer: &mut VecDeque<Frame>, step: f64) -> Frame {
+        // Blends the two oldest frames for where the output is between them, then moves on by a step of input frames
+        let (current, next) = match (buffer.get(0), buffer.get(1)) {
+            (Some(&current), Some(&next)) => (current, next),
+            _ => return Frame::ZERO, // Waits for the recorder so there are two frames to blend
+        };
+        let frame = current + (next - current) * self.position as f32;
+        self.position += step;
+        while self.position >= 1.0 && buffer.len() > 1 {
+            buffer.pop_front();
+            self.position -= 1.0;
+        }
+        frame
+    }
+}
+
+impl Sound for MonitorSound {
+    fn process(&mut self, out: &mut [Frame], dt: f64, _info: &Info) {
+        // Fills the output with whatever the recorder has sent, or silence if it hasn't caught up
+        // The input is stepped through at its own rate so it plays at the right speed on outputs with a different rate
+        let step = self.rate as f64 * dt;
+        let buffer = self.buffer.clone();
+        // Never waits for the lock so the audio thread can't stall, the recorder only holds it while pushing
+        let Ok(mut buffer) = buffer.try_lock() else {
+            out.fill(Frame::ZERO);
+            return;
+        };
+        for frame in out.iter_mut() {
+            *frame = self.pull(&mut buffer, step);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        false // Runs until the monitor's audio manager is dropped
+    }
+}
+
+impl SoundData for MonitorSound {
+    type Error = ();
+    type Handle = ();
+
+    fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+        Ok((Box::new(self), ()))
+    }
+}
+
+// -------- Functions --------
+pub fn map_channels(data: &[Vec<f32>], channels: [u32; 2]) -> (&[f32], &[f32], bool) {
+    // Picks the input channels that are recorded as left and right, and whether a chosen one wasn't delivered
+    // Mono input with the default channels isn't counted as missing as it has always been copied to both sides
+    // A missing left channel falls back to the first one and a missing right channel to the second, then to the left
+    let first = match data.first() {
+        Some(value) => &value[..],
+        None => &[],
+    };
+    let left = data.get(channels[0] as usize).map(|value| &value[..]);
+    let right = data.get(channels[1] as usize).map(|value| &value[..]);
+    let missing = (left.is_none() || right.is_none()) && channels != default_input_channels();
+
+    let left = left.unwrap_or(first);
+    let right = match right {
+        Some(value) => value,
+        None => data.get(1).map(|value| &value[..]).unwrap_or(left),
+    };
+
+    (left, right, missing)
+}
+
+pub fn dial_to_gain(value: i32) -> f32 {
+    // Converts a band dial value into decibels
+    if value == SILENT_DIAL {
+        SILENT_GAIN
+    } else {
+        value as f32 * GAIN_PER_STEP
+    }
+}
+
+pub fn gain_to_dial(gain: f32) -> i32 {
+    // Converts decibels into the nearest band dial value, the opposite of dial_to_gain
+    // Only the muted gain or lower mutes a band, anything above it stays audible even if it's past the lowest step
+    if gain <= SILENT_GAIN {
+        SILENT_DIAL
+    } else {
+        ((gain / GAIN_PER_STEP).round() as i32).clamp(SILENT_DIAL + 1, MAX_DIAL)
+    }
+}
+
+pub fn band_gains(values: [i32; 6]) -> [f32; 5] {
+    // Gets the decibels each band is set to, the unit dial values are shared in outside the app
+    // Pan is left out as it isn't a gain
+    let mut gains = [0.0; 5];
+    for band in 0..5 {
+        gains[band] = dial_to_gain(values[band]);
+    }
+    gains
+}
+
+pub fn makeup_gain(values: [i32; 6]) -> f32 {
+    // Estimates how much louder the boosted bands make the track and returns the decibels to turn it down by
+    // Each band covers about a fifth of what's heard so its boost is spread across all five, cuts are left alone
+    // as they don't risk clipping, and pan is skipped as it doesn't change the level
+    let mut boost = 0.0;
+    for value in &values[0..5] {
+        boost += dial_to_gain(*value).max(0.0);
+    }
+    -boost / 5.0
+}
+
+pub fn dial_to_panning(value: i32) -> f32 {
+    // Converts the pan dial value into kira panning
+    value as f32 * PANNING_PER_STEP
+}
+
+pub fn audible(sample: f32) -> bool {
+    // Whether a captured sample is loud enough to count as sound, so hiss and a small DC offset don't
+    // Checked on the capture buffer, which is always floats from -1 to 1 no matter what format the file is written in
+    sample.abs() >= SOUND_FLOOR
+}
+
+pub fn sound_frames(left: &[f32], right: &[f32]) -> u64 {
+    // Counts the frames where either channel is above the sound floor
+    // A stray click only lasts a few frames and a small DC offset stays under the floor, so neither keeps a recording on its own
+    left.iter()
+        .zip(right.iter())
+        .filter(|(left, right)| left.abs() >= SOUND_FLOOR || right.abs() >= SOUND_FLOOR)
+        .count() as u64
+}
+
+pub fn first_sound(channels: &[Vec<f32>]) -> Option<usize> {
+    // Finds the first frame where any channel is audible
+    // Uses the same check the recorder trims leading silence with, so trimming afterwards cuts where recording would have started
+    channels
+        .iter()
+        .filter_map(|channel| channel.iter().position(|sample| audible(*sample)))
+        .min()
+}
+
+pub fn min_sound_frames(min_sound_ms: u32) -> u64 {
+    // Frames of sound a recording needs before it's kept, always at least one
+    (min_sound_ms as u64 * RECORDING_SAMPLE_RATE as u64 / 1000).max(1)
+}
+
+// Windowed sinc filter worked out ahead of time for each point an output sample can fall at between two input samples
+// The filter cuts off at the lower of the two rates' limits so going down doesn't fold high frequencies back into the audio
+pub struct Resampler {
+    up: u64,               // Output samples in each repeating group of the two rates
+    down: u64,             // Input samples in each repeating group of the two rates
+    reach: isize,          // Input samples on each side of an output sample that the filter covers
+    phases: Vec<Vec<f32>>, // Filter taps for each point between two input samples
+}
+
+impl Resampler {
+    pub fn new(from: u32, to: u32) -> Resampler {
+        let (mut a, mut b) = (from.max(1) as u64, to.max(1) as u64);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        let (up, down) = (to.max(1) as u64 / a, from.max(1) as u64 / a);
+        let cutoff = (up as f64 / down as f64).min(1.0);
+        let reach = RESAMPLE_TAPS as f64 / cutoff;
+        let taps = reach.ceil() as isize;
+
+        let count = (up as usize).min(RESAMPLE_PHASES);
+        let phases = (0..count)
+            .map(|phase| {
+                let offset = phase as f64 / count as f64; // How far past an input sample the output falls
+                (-taps..=taps)
+                    .map(|tap| {
+                        let distance = tap as f64 - offset;
+                        if distance.abs() >= reach {
+                            return 0.0;
+                        }
+                        let phase = PI * distance * cutoff;
+                        let sinc = if phase.abs() < 1e-9 {
+                            1.0
+                        } else {
+                            phase.sin() / phase
+                        };
+                        // Blackman window so the filter fades out instead of stopping sharply
+                        let window = 0.42
+                            + 0.5 * (PI * distance / reach).cos()
+                            + 0.08 * (2.0 * PI * distance / reach).cos();
+                        (sinc * cutoff * window) as f32
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Resampler {
+            up,
+            down,
+            reach: taps,
+            phases,
+        }
+    }
+
+    pub fn run(&self, samples: &[f32]) -> Vec<f32> {
+        // Changes the sample rate of one channel, only multiplying and adding for each output sample
+        let length = (samples.len() as f64 * self.up as f64 / self.down as f64).round() as u64;
+        let count = self.phases.len() as u64;
+        (0..length)
+            .map(|index| {
+                let position = index * self.down;
+                let mut centre = (position / self.up) as isize;
+                // Uses the nearest point worked out when the rates have more than were kept
+                let mut phase = ((position % self.up) * count + self.up / 2) / self.up;
+                if phase == count {
+                    phase = 0;
+                    centre += 1;
+                }
+
+                let taps = &self.phases[phase as usize];
+                let start = centre - self.reach;
+                let first = (-start).max(0) as usize;
+                let last = (samples.len() as isize - start).clamp(0, taps.len() as isize) as usize;
+                if first >= last {
+                    return 0.0;
+                }
+                samples[(start + first as isize) as usize..(start + last as isize) as usize]
+                    .iter()
+                    .zip(&taps[first..last])
+                    .map(|(sample, tap)| sample * tap)
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+pub fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    // Changes the sample rate of one channel
+    if from == to || from == 0 || to == 0 || samples.is_empty() {
+        return samples.to_vec();
+    }
+    Resampler::new(from, to).run(samples)
+}
+
+pub fn resampled_sound(data: StaticSoundData, sample_rate: u32) -> StaticSoundData {
+    // Brings loaded audio to another sample rate in memory, leaving the file alone
+    if data.sample_rate == sample_rate || data.sample_rate == 0 {
+        return data;
+    }
+    let left: Vec<f32> = data.frames.iter().map(|frame| frame.left).collect();
+    let right: Vec<f32> = data.frames.iter().map(|frame| frame.right).collect();
+    // The channels don't depend on each other so they're worked out side by side
+    let resampler = Resampler::new(data.sample_rate, sample_rate);
+    let (left, right) = thread::scope(|scope| {
+        let left = scope.spawn(|| resampler.run(&left));
+        let right = resampler.run(&right);
+        (left.join().unwrap(), right)
+    });
+    let frames: Vec<Frame> = left
+        .into_iter()
+        .zip(right)
+        .map(|(left, right)| Frame { left, right })
+        .collect();
+    StaticSoundData {
+        sample_rate,
+        frames: frames.into(),
+        ..data
+    }
+}
+
+// Recordings already brought to the playback rate, so playing one again doesn't redo the work
+pub struct ResampleCache {
+    entries: VecDeque<(String, Option<SystemTime>, StaticSoundData)>, // File, when it was last changed, and its audio
+}
+
+impl ResampleCache {
+    pub fn new() -> ResampleCache {
+        ResampleCache {
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn load(&mut self, file: &str, sample_rate: u32) -> Result<StaticSoundData, FromFileError> {
+        // Loads a recording at a sample rate, reusing what was worked out last time if the file hasn't changed since
+        let modified = fs::metadata(file)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|(cached, _, _)| cached == file)
+        {
+            if let Some(entry) = self.entries.remove(index) {
+                if modified.is_some() && entry.1 == modified {
+                    let data = entry.2.clone();
+                    self.entries.push_front(entry);
+                    return Ok(data);
+                }
+            }
+        }
+
+        let data = resampled_sound(StaticSoundData::from_file(file)?, sample_rate);
+        self.entries
+            .push_front((file.to_string(), modified, data.clone()));
+        self.entries.truncate(RESAMPLE_CACHE_SIZE);
+        Ok(data)
+    }
+}
+
+pub fn fft(real: &mut [f32], imaginary: &mut [f32], inverse: bool) {
+    // Turns a slice of audio into its frequencies in place, or back again when inverse
+    // The length has to be a power of two
+    let length = real.len();
+
+    // Puts the samples in bit reversed order so each pass can work on neighbouring pairs
+    let mut swap = 0;
+    for index in 1..length {
+        let mut bit = length >> 1;
+        while swap & bit != 0 {
+            swap ^= bit;
+            bit >>= 1;
+        }
+        swap |= bit;
+        if index < swap {
+            real.swap(index, swap);
+            imaginary.swap(index, swap);
+        }
+    }
+
+    let direction = if inverse { 1.0 } else { -1.0 };
+    let mut size = 2;
+    while size <= length {
+        let step = direction * TAU / size as f32;
+        for start in (0..length).step_by(size) {
+            for offset in 0..size / 2 {
+                let (sin, cos) = (step * offset as f32).sin_cos();
+                let even = start + offset;
+                let odd = even + size / 2;
+                let odd_real = real[odd] * cos - imaginary[odd] * sin;
+                let odd_imaginary = real[odd] * sin + imaginary[odd] * cos;
+                real[odd] = real[even] - odd_real;
+                imaginary[odd] = imaginary[even] - odd_imaginary;
+                real[even] += odd_real;
+                imaginary[even] += odd_imaginary;
+            }
+        }
+        size *= 2;
+    }
+
+    if inverse {
+        for index in 0..length {
+            real[index] /= length as f32;
+            imaginary[index] /= length as f32;
+        }
+    }
+}
+
+pub fn noise_window() -> Vec<f32> {
+    // Fades each slice in and out so slices that overlap by half add back up to the original
+    (0..NOISE_WINDOW)
+        .map(|index| 0.5 - 0.5 * (TAU * index as f32 / NOISE_WINDOW as f32).cos())
+        .collect()
+}
+
+// The average loudness of every frequency in a recording of background noise
+#[derive(Savefile, Clone, PartialEq)]
+pub struct NoiseProfile {
+    pub source: String, // Recording or loop region the profile was taken from
+    pub magnitudes: Vec<Vec<f32>>, // Loudness of each frequency for each channel
+}
+
+impl NoiseProfile {
+    pub fn from_channels(source: &str, channels: &[Vec<f32>]) -> Result<NoiseProfile, Error> {
+        // Averages the frequencies of every slice of each channel
+        let window = noise_window();
+        let mut magnitudes = vec![];
+        for channel in channels {
+            if channel.len() < NOISE_WINDOW {
+                return Err(Error::NoiseProfileError);
+            }
+
+            let mut total = vec![0.0; NOISE_WINDOW / 2 + 1];
+            let mut slices = 0;
+            for start in (0..=channel.len() - NOISE_WINDOW).step_by(NOISE_WINDOW / 2) {
+                let mut real: Vec<f32> = (0..NOISE_WINDOW)
+                    .map(|index| channel[start + index] * window[index])
+                    .collect();
+                let mut imaginary = vec![0.0; NOISE_WINDOW];
+                fft(&mut real, &mut imaginary, false);
+                for bin in 0..total.len() {
+                    total[bin] += real[bin].hypot(imaginary[bin]);
+                }
+                slices += 1;
+            }
+            magnitudes.push(total.iter().map(|value| value / slices as f32).collect());
+        }
+
+        Ok(NoiseProfile {
+            source: source.to_string(),
+            magnitudes,
+        })
+    }
+
+    pub fn capture(recording: &Recording) -> Result<NoiseProfile, Error> {
+        // Takes a profile from the recording's loop region, or the whole recording when one isn't set
+        // Whatever it's taken from should only have background noise in it
+        let (spec, channels) = read_channels(&recording.name)?;
+        let frames = channels.first().map_or(0, |channel| channel.len());
+        let length = Duration::from_secs_f64(frames as f64 / spec.sample_rate as f64);
+        match recording.loop_region(length) {
+            Some((start, end)) => {
+                let first = (start.as_secs_f64() * spec.sample_rate as f64) as usize;
+                let last = ((end.as_secs_f64() * spec.sample_rate as f64) as usize).min(frames);
+                let region: Vec<Vec<f32>> = channels
+                    .iter()
+                    .map(|channel| channel[first.min(last)..last].to_vec())
+                    .collect();
+                NoiseProfile::from_channels(&format!("{}'s loop region", recording.name), &region)
+            }
+            None => NoiseProfile::from_channels(&recording.name, &channels),
+        }
+    }
+
+    pub fn save(&self) -> Option<Error> {
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        match save_file(
+            format!("{}/{}.noise", path, NOISE_PROFILE_FILE),
+            SAVE_VERSION,
+            self,
+        ) {
+            Ok(_) => None,
+            Err(_) => Some(Error::SaveError),
+        }
+    }
+
+    pub fn load() -> Result<NoiseProfile, Error> {
+        let path = File::get_directory()?;
+        match load_file(
+            format!("{}/{}.noise", path, NOISE_PROFILE_FILE),
+            SAVE_VERSION,
+        ) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(Error::MissingProfileError),
+        }
+    }
+
+    pub fn summary() -> SharedString {
+        // Names the recording the saved profile came from for the UI
+        match NoiseProfile::load() {
+            Ok(profile) => SharedString::from(profile.source),
+            Err(_) => SharedString::from("None"),
+        }
+    }
+
+    pub fn reduce(&self, channel: &[f32], index: usize) -> Vec<f32> {
+        // Turns down each frequency by how loud the noise was at it, then puts the slices back together
+        // Channels without their own profile use the last one
+        let noise = match self.magnitudes.get(index).or(self.magnitudes.last()) {
+            Some(value) => value,
+            None => return channel.to_vec(),
+        };
+        let window = noise_window();
+        let hop = NOISE_WINDOW / 2;
+
+        // Pads both ends so the first and last samples are covered by two slices like the rest
+        let mut padded = vec![0.0; hop];
+        padded.extend_from_slice(channel);
+        padded.resize(padded.len() + NOISE_WINDOW, 0.0);
+        let mut output = vec![0.0; padded.len()];
+
+        for start in (0..=padded.len() - NOISE_WINDOW).step_by(hop) {
+            let mut real: Vec<f32> = (0..NOISE_WINDOW)
+                .map(|index| padded[start + index] * window[index])
+                .collect();
+            let mut imaginary = vec![0.0; NOISE_WINDOW];
+            fft(&mut real, &mut imaginary, false);
+
+            for bin in 0..NOISE_WINDOW {
+                // Bins past the middle mirror the ones before it
+                let profiled = noise[bin.min(NOISE_WINDOW - bin)];
+                let magnitude = real[bin].hypot(imaginary[bin]);
+                let gain = if magnitude > 0.0 {
+                    (1.0 - NOISE_OVER_SUBTRACTION * profiled / magnitude).max(NOISE_FLOOR_GAIN)
+                } else {
+                    NOISE_FLOOR_GAIN
+                };
+                real[bin] *= gain;
+                imaginary[bin] *= gain;
+            }
+
+            fft(&mut real, &mut imaginary, true);
+            for index in 0..NOISE_WINDOW {
+                output[start + index] += real[index];
+            }
+        }
+
+        output[hop..hop + channel.len()].to_vec()
+    }
+
+    pub fn denoise(&self, name: &str, recordings: &Vec<Recording>) -> Result<String, Error> {
+        // Writes a cleaned copy of a recording next to it and returns the copy's name
+        let (spec, channels) = read_channels(name)?;
+        let cleaned: Vec<Vec<f32>> = channels
+            .iter()
+            .enumerate()
+            .map(|(index, channel)| self.reduce(channel, index))
+            .collect();
+
+        let copy = unused_name(&Disk, format!("{} denoised", name), recordings);
+
+        if let Some(error) = write_channels(&copy, spec, &cleaned) {
+            return Err(error);
+        }
+
+        match SnapShot::create(&copy) {
+            Some(error) => Err(error),
+            None => Ok(copy),
+        }
+    }
+}
+
+pub fn audio_length(data: &StaticSoundData) -> Option<Duration> {
+    // Gets how long audio lasts at its own sample rate, so files that weren't recorded by the app play for the right time
+    // Kira resamples every file to the output device's rate before the effects run, so the filter frequencies hold at any file rate
+    if data.sample_rate == 0 {
+        return None; // A broken header would make the length infinite and kira would panic working it out
+    }
+    Some(data.duration())
+}
+
+pub fn click_sound(frequency: f32) -> StaticSoundData {
+    // Creates a short fading sine wave to use as a metronome click
+    click_from(|time, envelope| (time * frequency * TAU).sin() * envelope * 0.5)
+}
+
+pub fn wood_block_sound(frequency: f32) -> StaticSoundData {
+    // Creates a sine wave that dies away quickly so it knocks instead of beeping
+    click_from(|time, _| (time * frequency * TAU).sin() * (-time * 150.0).exp() * 0.6)
+}
+
+pub fn tick_sound(level: f32) -> StaticSoundData {
+    // Creates a fading burst of noise
+    click_from(|_, envelope| random_range(-1.0..=1.0) * envelope * envelope * level)
+}
+
+fn click_from(mut sample: impl FnMut(f32, f32) -> f32) -> StaticSoundData {
+    // Builds a metronome click from a sample for each point in time and how far it has faded
+    let length = (RECORDING_SAMPLE_RATE as f32 * CLICK_LENGTH) as usize;
+    let mut frames = vec![];
+    for index in 0..length {
+        let time = index as f32 / RECORDING_SAMPLE_RATE as f32;
+        let envelope = 1.0 - index as f32 / length as f32; // Fades out so the click doesn't pop
+        frames.push(Frame::from_mono(sample(time, envelope)));
+    }
+
+    StaticSoundData {
+        sample_rate: RECORDING_SAMPLE_RATE,
+        frames: frames.into(),
+        settings: StaticSoundSettings::default(),
+        slice: None,
+    }
+}
@@ -0,0 +1,1368 @@
+// Saving, loading, naming, and checking the files in the data folder, along with reading and rewriting recordings
+
+// -------- Imports --------
+use crate::*; // Constants, errors, and the other modules through the crate root
+
+// -------- Enums --------
+// Holds values used when sorting
+#[derive(PartialEq)]
+pub enum TextNum {
+    Text(String),
+    Number(i32),
+}
+
+impl TextNum {
+    pub fn split_text_and_numbers(input: String) -> Vec<TextNum> {
+        // Takes a string and returns a vector of itself
+        let mut text = String::new();
+        let mut number = String::new();
+        let mut list = vec![];
+
+        let mut adding_text = false; // Keeps track of whether the last character was text
+        let mut adding_number = false; // Keeps track of whether the last character was a number
+
+        let mut index = 0;
+        for char in input.chars() {
+            // Loops over every character in the input string
+            match char.to_string().parse::<i32>() {
+                // Attempts to parse the char into an i32
+                Ok(_) => {
+                    // If parse is successful
+                    adding_number = true;
+                    number.push(char); // Adds char to the numbers list
+                    if adding_text {
+                        adding_text = false;
+                        if text.len() > 0 {
+                            list.push(TextNum::Text(text.clone())); // Pushes the text string onto the final list
+                            text.clear(); // Clears the text string for another use
+                        }
+                    }
+                    if index == input.len() - 1 {
+                        // Checks to see if on the last char
+                        list.push(TextNum::Number(number.parse().unwrap())); // Pushes the number string onto the final list after parsing it into an i32
+                    }
+                }
+                Err(_) => {
+                    // If parse fails
+                    // Do the same thing as if it was successful but with the opposite strings
+                    adding_text = true;
+                    text.push(char);
+                    if adding_number {
+                        adding_number = false;
+                        if number.len() > 0 {
+                            list.push(TextNum::Number(number.parse().unwrap()));
+                            number.clear();
+                        }
+                    }
+                    if index == input.len() - 1 {
+                        list.push(TextNum::Text(text.clone()));
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        list // Return the final list
+    }
+}
+
+pub fn sequential_name(
+    taken_names: &[String],
+    collision: NameCollision,
+    fallbacks: usize,
+    now: SystemTime,
+) -> Option<String> {
+    // Names a new recording 'Recording N' after the ones already there, falling back to the chosen strategy if that's taken
+    let potential = format!("Recording {}", taken_names.len() + 1);
+    if taken_names.contains(&potential) {
+        collision.resolve(&potential, taken_names, fallbacks, now)
+    } else {
+        Some(potential)
+    }
+}
+
+pub fn timestamp_name(seconds: u64) -> String {
+    // Formats seconds since 1970 as a date and time in UTC that's safe to use in a file name
+    let days = (seconds / 86400) as i64;
+    let time = seconds % 86400;
+
+    // Turns days since 1970 into a calendar date, with years starting in March so leap days come last
+    let shifted = days + 719468;
+    let era = shifted.div_euclid(146097);
+    let day_of_era = shifted.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}-{:02}-{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+pub fn index_after_removal(current: usize, removed: usize, remaining: usize) -> usize {
+    // Where the current item is once another is taken out of the list, the next one moves into a removed current item's place
+    if remaining == 0 {
+        0
+    } else if current > removed {
+        current - 1
+    } else {
+        current.min(remaining - 1)
+    }
+}
+
+pub fn unique_name(name: String, taken_names: &[String]) -> String {
+    // Adds a counter to a name that's already been used
+    if !taken_names.contains(&name) {
+        return name;
+    }
+    let mut counter = 2;
+    while taken_names.contains(&format!("{} {}", name, counter)) {
+        counter += 1;
+    }
+    format!("{} {}", name, counter)
+}
+
+pub fn unused_name(storage: &dyn Storage, name: String, recordings: &[Recording]) -> String {
+    // Adds a counter to a name that's used by a recording or by a file on disk that hasn't been synced yet
+    // The same checks as renaming, so a new recording can't be written over a file the list doesn't know about
+    let mut taken: Vec<String> = recordings
+        .iter()
+        .map(|recording| recording.name.clone())
+        .collect();
+    loop {
+        let candidate = unique_name(name.clone(), &taken);
+        if !File::present(storage, &candidate, "wav") {
+            return candidate;
+        }
+        taken.push(candidate);
+    }
+}
+
+pub fn clean_prefix(prefix: &str) -> String {
+    // Removes characters that can't be in a file name and anything that would make the name reserved
+    let cleaned: String = prefix
+        .chars()
+        .filter(|character| !"/\\:*?\"<>|".contains(*character))
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() || cleaned.contains(FALLBACK_NAME) || RESERVED_NAMES.contains(&cleaned) {
+        default_name_prefix()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+// File system operations that the file handling relies on, kept behind a trait so they can be swapped out in tests
+pub trait Storage {
+    fn read_dir(&self, path: &str) -> Result<Vec<PathBuf>, Error>; // Gets the path of every entry in a folder
+    fn is_file(&self, path: &Path) -> bool; // Checks the metadata of a path to see if it's a file
+    fn rename(&self, from: &str, to: &str) -> Result<(), Error>;
+    fn delete(&self, path: &str) -> Result<(), Error>;
+    fn copy(&self, from: &str, to: &str) -> Result<(), Error>;
+    fn size(&self, path: &str) -> Option<u64>; // Bytes in a file, None if it isn't there
+    fn is_dir(&self, path: &str) -> bool;
+    fn create_dir(&self, path: &str) -> Result<(), Error>; // Also creates any missing parent folders
+    fn remove_dir(&self, path: &str) -> Result<(), Error>; // Only removes empty folders
+}
+
+// The real file system
+pub struct Disk;
+
+impl Storage for Disk {
+    fn read_dir(&self, path: &str) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = vec![];
+        match fs::read_dir(path) {
+            Ok(entries) => {
+                for entry in entries {
+                    // Loop throuh every entry
+                    match entry {
+                        Ok(value) => paths.push(value.path()),
+                        Err(_) => return Err(Error::ReadError(path.to_string())),
+                    }
+                }
+                Ok(paths)
+            }
+            Err(_) => Err(Error::ReadError(path.to_string())),
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        match fs::metadata(path) {
+            Ok(metadata) => metadata.is_file(),
+            Err(_) => false,
+        }
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), Error> {
+        match rename(from, to) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::RenameError),
+        }
+    }
+
+    fn delete(&self, path: &str) -> Result<(), Error> {
+        match remove_file(path) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::DeleteError),
+        }
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<(), Error> {
+        match fs::copy(from, to) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::SaveError),
+        }
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => Some(metadata.len()),
+            _ => None,
+        }
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        Path::new(path).is_dir()
+    }
+
+    fn create_dir(&self, path: &str) -> Result<(), Error> {
+        match fs::create_dir_all(path) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::SaveError),
+        }
+    }
+
+    fn remove_dir(&self, path: &str) -> Result<(), Error> {
+        match fs::remove_dir(path) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::DeleteError),
+        }
+    }
+}
+
+// Files
+#[derive(PartialEq)]
+pub enum File {
+    Names(Vec<String>),
+}
+
+impl File {
+    pub fn search(
+        storage: &dyn Storage,
+        path: &str,
+        extension: &str,
+        ordered: bool,
+    ) -> Result<File, Error> {
+        // Searches for files at the specified path and the same extension, and returns either a list of names or an error
+        let mut names = vec![];
+        match storage.read_dir(path) {
+            // Attemps to read the files at the specified path
+            Ok(paths) => {
+                for path in paths {
+                    // Loop throuh every entry
+                    if storage.is_file(&path) {
+                        // If it's a file not a folder
+                        if let Some(file_type) = path.extension() {
+                            // Gets the extension of the file
+                            if file_type == extension {
+                                // Checks if it's correct
+                                let file_name = match path.file_name() {
+                                    // Gets the file name
+                                    Some(value) => value.to_owned(),
+                                    None => OsString::from("Couldn't read name"),
+                                };
+                                names.push(match file_name.into_string() {
+                                    // Pushes the file name onto the list of names
+                                    Ok(mut value) => File::truncate(&mut value, ".", 0), // Truncates the extension on the name
+                                    Err(_) => String::from("Couldn't read name"),
+                                });
+                            }
+                        }
+                    }
+                }
+
+                if ordered {
+                    // If true passed as the ordering value
+                    names.sort_by(|string1, string2| {
+                        // Sorts the names list using a custom rule set
+                        let compare1 =
+                            TextNum::split_text_and_numbers(string1.to_string().to_lowercase()); // Splits string into letters and whole numbers
+                        let compare2 =
+                            TextNum::split_text_and_numbers(string2.to_string().to_lowercase());
+                        // The largest bias is sorted after the smaller one
+                        let mut bias1 = 0;
+                        let mut bias2 = 0;
+
+                        for item in 0..if compare1.len() <= compare2.len() {
+                            // Loops through all the items in the smallest list
+                            compare1.len()
+                        } else {
+                            compare2.len()
+                        } {
+                            if let (TextNum::Text(_), TextNum::Number(_)) =
+                                // Checks if the first list is text and the second is a number
+                                (&compare1[item], &compare2[item])
+                            {
+                                bias1 = i32::MAX; // Sets bias1 to the maximum value for an i32
+                                break; // Skips the rest of the checks as they no longer matter
+                            } else if let (TextNum::Number(_), TextNum::Text(_)) =
+                                // Does the opposite
+                                (&compare1[item], &compare2[item])
+                            {
+                                bias2 = i32::MAX;
+                                break;
+                            } else if let (TextNum::Text(first), TextNum::Text(second)) =
+                                // Checks if they are both text
+                                (&compare1[item], &compare2[item])
+                            {
+                                let first_chars: Vec<char> = first.chars().collect(); // Converts the current vector index into its own vector
+                                let second_chars: Vec<char> = second.chars().collect();
+                                for char in 0..if first.len() <= second.len() {
+                                    // Iterates through the shorter vector
+                                    if first.len() < second.len() {
+                                        bias2 += 1; // Prioritises the longer list appearing after the shorter one
+                                    }
+                                    first.len()
+                                } else {
+                                    bias1 += 1;
+                                    second.len()
+                                } {
+                                    match first_chars[char].cmp(&second_chars[char]) {
+                                        // Compares the values in alphabetical order
+                                        Ordering::Greater => {
+                                            bias1 += 1; // Prioritises the later characters in the alphabet appearing after the earlier ones
+                                        }
+                                        Ordering::Equal => {}
+                                        Ordering::Less => {
+                                            bias2 += 1;
+                                        }
+                                    }
+                                }
+                            } else if let (TextNum::Number(first), TextNum::Number(second)) =
+                                // If both are numbers
+                                (&compare1[item], &compare2[item])
+                            {
+                                match first.cmp(&second) {
+                                    // Compare the numbers
+                                    Ordering::Greater => {
+                                        bias1 += 1; // Prioritise the greater number appearing last
+                                    }
+                                    Ordering::Equal => {}
+                                    Ordering::Less => {
+                                        bias2 += 1;
+                                    }
+                                }
+                            }
+                        }
+
+                        if bias1 > bias2 {
+                            Ordering::Greater
+                        } else if bias1 < bias2 {
+                            Ordering::Less
+                        } else {
+                            Ordering::Equal
+                        }
+                    });
+                }
+                Ok(File::Names(names)) // Return the list of names
+            }
+            Err(error) => Err(error), // Return an error if an error is encountered
+        }
+    }
+
+    pub fn truncate(name: &mut String, stop_char: &str, pass: u32) -> String {
+        // Truncates strings to the designated stop character
+        let copy = name.clone();
+        let mut length = name.len() - 1;
+        let mut found = 0;
+        loop {
+            if name.ends_with(stop_char) {
+                // Checks if the last character is the same as the stop character
+                name.remove(length); // Remove it
+                length -= 1;
+                if found == pass {
+                    // Checks if it's passed enough stop characters
+                    break;
+                }
+                found += 1;
+            } else {
+                if length == 1 {
+                    // Returns the original input if no or not enough stop characters found
+                    return copy;
+                }
+                name.remove(length);
+                length -= 1;
+            }
+        }
+
+        name.to_string() // Returns the truncated string
+    }
+
+    pub fn rename(storage: &dyn Storage, old: &String, name: String) -> Option<Error> {
+        // Renames the inputted file or returns an error
+        let path = match File::get_directory() {
+            // Gets current path
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        match storage.rename(
+            // Attempts to rename the file
+            &format!("{}/{}.wav", path, old),
+            &format!("{}/{}.wav", path, name),
+        ) {
+            Ok(_) => (),
+            Err(error) => {
+                return Some(error); // Return an error if unsuccessful
+            }
+        };
+
+        let snapshots = match File::snapshot_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        match storage.rename(
+            &format!("{}/{}.bin", snapshots, old),
+            &format!("{}/{}.bin", snapshots, name),
+        ) {
+            Ok(_) => (),
+            Err(error) => {
+                return Some(error);
+            }
+        };
+
+        None // Return nothing if no error
+    }
+
+    pub fn delete(storage: &dyn Storage, name: String) -> Option<Error> {
+        // Attempts to delete the inputted file or returns an error
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        match storage.delete(&format!("{}/{}.wav", path, name)) {
+            Ok(_) => (),
+            Err(error) => {
+                return Some(error);
+            }
+        };
+        match File::snapshot_directory() {
+            Ok(snapshots) => match storage.delete(&format!("{}/{}.bin", snapshots, name)) {
+                Ok(_) => None,
+                Err(_) => None,
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub fn exists(new: String, old_list: &Vec<Recording>) -> bool {
+        // Checks if a name already exists in the current save
+        let mut check = false;
+        for item in 0..old_list.len() {
+            // Loops through the name sin a list
+            if new == old_list[item].name {
+                // If it exists return true
+                check = true;
+                break;
+            }
+        }
+
+        check
+    }
+
+    pub fn reserved(name: &str) -> bool {
+        // Checks if a name belongs to the app's own files, ignoring case since some file systems do
+        RESERVED_NAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(name))
+    }
+
+    pub fn rename_reserved(storage: &dyn Storage, name: &str) -> Result<String, Error> {
+        // Gives a recording with a reserved name a free one and returns it
+        // Only the wav is moved since the snapshot with the same name is the app's own file
+        let path = File::get_directory()?;
+        let mut new_name = format!("{} recording", name);
+        let mut count = 2;
+        while File::present(storage, &new_name, "wav") {
+            new_name = format!("{} recording {}", name, count);
+            count += 1;
+        }
+
+        storage.rename(
+            &format!("{}/{}.wav", path, name),
+            &format!("{}/{}.wav", path, new_name),
+        )?;
+        Ok(new_name)
+    }
+
+    pub fn present(storage: &dyn Storage, name: &str, extension: &str) -> bool {
+        // Checks if a file exists in the working directory
+        match File::get_directory() {
+            Ok(path) => storage.is_file(Path::new(&format!("{}/{}.{}", path, name, extension))),
+            Err(_) => false,
+        }
+    }
+
+    pub fn backup(storage: &dyn Storage, name: &str, extension: &str) -> Option<Error> {
+        // Keeps a copy of a file that couldn't be read by adding '.bak' to the end of it
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        storage
+            .copy(
+                &format!("{}/{}.{}", path, name, extension),
+                &format!("{}/{}.{}.bak", path, name, extension),
+            )
+            .err()
+    }
+
+    pub fn reveal(name: &str) -> Option<Warning> {
+        // Opens the system file manager at a recording, or at the data folder if the recording isn't there
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(_) => return Some(Warning::RevealFailed),
+        };
+        let file = format!("{}/{}.wav", path, name);
+        let selected = Path::new(&file).is_file();
+
+        let mut command;
+        if cfg!(target_os = "windows") {
+            command = Command::new("explorer");
+            if selected {
+                command.arg(format!("/select,{}", file.replace('/', "\\")));
+            } else {
+                command.arg(path.replace('/', "\\"));
+            }
+        } else if cfg!(target_os = "macos") {
+            command = Command::new("open");
+            if selected {
+                command.arg("-R").arg(&file);
+            } else {
+                command.arg(&path);
+            }
+        } else {
+            command = Command::new("xdg-open"); // Can only open folders, not select files
+            command.arg(&path);
+        }
+
+        // Doesn't wait because explorer reports failure even when it opens
+        match command.spawn() {
+            Ok(_) => None,
+            Err(_) => Some(Warning::RevealFailed),
+        }
+    }
+
+    pub fn snapshot_directory() -> Result<String, Error> {
+        // Gets the folder snapshots are kept in, which is the data folder itself unless they're kept in a subfolder
+        let path = File::get_directory()?;
+        if SNAPSHOTS_IN_FOLDER.load(AtomicOrdering::Relaxed) {
+            Ok(format!("{}/{}", path, SNAPSHOT_FOLDER))
+        } else {
+            Ok(path)
+        }
+    }
+
+    pub fn arrange_snapshots(storage: &dyn Storage, in_folder: bool) -> Option<Error> {
+        // Moves snapshots into or out of the snapshot folder to match the option, the settings file always stays in the data folder
+        // Also moves snapshots left in the data folder by older versions, so it's safe to run every time the app starts
+        SNAPSHOTS_IN_FOLDER.store(in_folder, AtomicOrdering::Relaxed);
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        let folder = format!("{}/{}", path, SNAPSHOT_FOLDER);
+        let (from, to) = if in_folder {
+            if let Err(error) = storage.create_dir(&folder) {
+                return Some(error);
+            }
+            (path, folder.clone())
+        } else if storage.is_dir(&folder) {
+            (folder.clone(), path)
+        } else {
+            return None; // Nothing has been moved into the folder yet
+        };
+
+        let names = match File::search(storage, &from, "bin", false) {
+            Ok(File::Names(value)) => value,
+            Err(error) => return Some(error),
+        };
+        let mut error = None;
+        for name in names {
+            let destination = format!("{}/{}.bin", to, name);
+            if File::reserved(&name) || storage.is_file(Path::new(&destination)) {
+                continue; // Never moves the settings, or replaces a snapshot that's already there
+            }
+            if let Err(value) = storage.rename(&format!("{}/{}.bin", from, name), &destination) {
+                error = Some(value); // Keep going so one stuck file doesn't hold back the rest
+            }
+        }
+
+        if !in_folder {
+            let _ = storage.remove_dir(&folder); // Only removed once it's empty
+        }
+
+        error
+    }
+
+    pub fn get_directory() -> Result<String, Error> {
+        // Gets the working directory
+        if let Ok(value) = env::var(DATA_DIR_VARIABLE) {
+            // An override is only used if it's a folder that can be written to, otherwise the usual folder is used
+            if File::writable(&value) {
+                return Ok(value.trim_end_matches(['/', '\\']).to_string());
+            }
+        }
+
+        // Gets the path that the executable is saved at and works out the root from it
+        match env::current_exe() {
+            Ok(value) => File::root_of(&value).ok_or(Error::DirectoryError),
+            Err(_) => Err(Error::DirectoryError),
+        }
+    }
+
+    pub fn writable(path: &str) -> bool {
+        // Checks if a path is a folder that files can be written to
+        match fs::metadata(path) {
+            Ok(metadata) => metadata.is_dir() && !metadata.permissions().readonly(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn export_directory(chosen: &Option<String>) -> Result<(String, bool), Error> {
+        // Gets the folder to export to and whether it had to fall back to the data folder
+        match chosen {
+            Some(path) if File::writable(path) => {
+                Ok((path.trim_end_matches(['/', '\\']).to_string(), false))
+            }
+            Some(_) => Ok((File::get_directory()?, true)),
+            None => Ok((File::get_directory()?, false)),
+        }
+    }
+
+    pub fn root_of(executable: &Path) -> Option<String> {
+        // The working root is two folders above the executable's folder, like the project folder for target/release
+        // Executables installed too shallow for that use their own folder instead of the top of the drive
+        let folder = executable
+            .parent()
+            .filter(|folder| !folder.as_os_str().is_empty())?;
+        let root = match folder.ancestors().nth(2) {
+            Some(root) if root.parent().is_some() => root,
+            _ => folder,
+        };
+        root.to_str().map(|root| root.to_string()) // Paths that aren't valid text can't be used to build file names
+    }
+}
+
+// Types of data that the app works with
+pub enum DataType {
+    Settings(Settings),
+    SnapShot(SnapShot),
+}
+
+// Types of data that the app can load
+pub enum LoadType {
+    Settings,
+    Snapshot,
+}
+
+// -------- Structs --------
+// What the startup consistency check found and fixed
+pub struct Consistency {
+    pub added: Vec<String>,      // Recordings on disk that weren't in the settings
+    pub removed: Vec<String>,    // Recordings in the settings that aren't on disk
+    pub duplicates: Vec<String>, // Recordings listed in the settings more than once
+    pub snapshots_created: Vec<String>, // Recordings that didn't have a snapshot
+    pub orphaned_snapshots: Vec<String>, // Snapshots without a recording, left for the orphan repair so the user can decide
+}
+
+impl Consistency {
+    pub fn repaired(&self) -> bool {
+        // Checks if anything was changed
+        self.added.len() > 0
+            || self.removed.len() > 0
+            || self.duplicates.len() > 0
+            || self.snapshots_created.len() > 0
+    }
+}
+
+impl Settings {
+    pub fn check_consistency(&mut self, storage: &dyn Storage) -> Result<Consistency, Error> {
+        // Makes sure every recording on disk has exactly one entry in the settings and one snapshot
+        let path = File::get_directory()?;
+        let File::Names(mut file_names) = File::search(storage, &path, "wav", true)?;
+        let File::Names(mut snapshot_names) =
+            File::search(storage, &File::snapshot_directory()?, "bin", true)?;
+        file_names.retain(|name| !File::reserved(name)); // Sync renames these before they get an entry
+        snapshot_names.retain(|name| !File::reserved(name)); // The settings file
+
+        let mut report = Consistency {
+            added: vec![],
+            removed: vec![],
+            duplicates: vec![],
+            snapshots_created: vec![],
+            orphaned_snapshots: vec![],
+        };
+
+        // Keeps the first entry for each recording, in the same order sync uses
+        let mut recordings = vec![];
+        for name in &file_names {
+            let mut entries = self
+                .recordings
+                .iter()
+                .filter(|recording| recording.name == *name);
+            let first = entries.next().cloned();
+            if entries.next().is_some() {
+                report.duplicates.push(name.clone());
+            }
+            match first {
+                Some(recording) => recordings.push(recording),
+                None => {
+                    recordings.push(self.imported(name));
+                    report.added.push(name.clone());
+                }
+            }
+        }
+        for recording in &self.recordings {
+            if !file_names.contains(&recording.name) && !report.removed.contains(&recording.name) {
+                report.removed.push(recording.name.clone());
+            }
+        }
+        self.recordings = recordings;
+
+        for name in &file_names {
+            if !snapshot_names.contains(name) {
+                if let Some(error) = SnapShot::create(name) {
+                    return Err(error);
+                }
+                report.snapshots_created.push(name.clone());
+            }
+        }
+        for name in snapshot_names {
+            if !file_names.contains(&name) {
+                report.orphaned_snapshots.push(name);
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+// Snapshot files that are out of step with the recordings on disk
+pub struct Orphans {
+    pub snapshots: Vec<String>, // Snapshots without a matching recording
+    pub corrupt: Vec<String>,   // Recordings whose snapshot can't be read
+}
+
+impl Orphans {
+    pub fn scan(storage: &dyn Storage) -> Result<Orphans, Error> {
+        // Finds orphaned and unreadable snapshots without changing any files
+        let path = File::get_directory()?;
+        let File::Names(mut file_names) = File::search(storage, &path, "wav", false)?;
+        let File::Names(snapshot_names) =
+            File::search(storage, &File::snapshot_directory()?, "bin", false)?;
+        file_names.retain(|name| !File::reserved(name)); // Sync renames these, their snapshot is the app's own file
+
+        let mut orphans = Orphans {
+            snapshots: vec![],
+            corrupt: vec![],
+        };
+
+        for name in snapshot_names {
+            if !File::reserved(&name) && !file_names.contains(&name) {
+                // Snapshot left behind after its recording was removed outside the app
+                orphans.snapshots.push(name);
+            }
+        }
+
+        for name in file_names {
+            if let Err(_) = load(&name, LoadType::Snapshot) {
+                // Snapshot is missing or can't be parsed
+                orphans.corrupt.push(name);
+            }
+        }
+
+        Ok(orphans)
+    }
+
+    pub fn found(&self) -> bool {
+        // Checks if anything needs repairing
+        self.snapshots.len() > 0 || self.corrupt.len() > 0
+    }
+
+    pub fn summary(&self) -> SharedString {
+        // Describes what was found so the user can decide whether to repair it
+        let mut parts = vec![];
+        if self.snapshots.len() > 0 {
+            parts.push(format!("{} orphaned snapshot(s)", self.snapshots.len()));
+        }
+        if self.corrupt.len() > 0 {
+            parts.push(format!("{} unreadable snapshot(s)", self.corrupt.len()));
+        }
+
+        SharedString::from(format!("Found {}", parts.join(" and ")))
+    }
+
+    pub fn repair(&self, storage: &dyn Storage) -> Option<Error> {
+        // Deletes orphaned snapshots and replaces unreadable ones with empty snapshots
+        let path = match File::snapshot_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+
+        let mut error = None;
+        for name in &self.snapshots {
+            if let Err(value) = storage.delete(&format!("{}/{}.bin", path, name)) {
+                error = Some(value); // Keep going so one bad file doesn't block the rest
+            }
+        }
+
+        for name in &self.corrupt {
+            match SnapShot::create(name) {
+                Some(value) => {
+                    error = Some(value);
+                }
+                None => (),
+            }
+        }
+
+        error
+    }
+}
+
+// Totals across every recording, cached so the files are only read again when the recordings change
+pub struct LibraryStats {
+    pub names: Vec<String>, // Recordings the totals were worked out from
+    pub duration: Duration,
+    pub size: u64, // Bytes used by the recordings and their snapshots
+}
+
+impl LibraryStats {
+    pub fn gather(
+        storage: &dyn Storage,
+        recordings: &Vec<Recording>,
+    ) -> Result<LibraryStats, Error> {
+        // Adds up the length and size of every recording
+        let path = File::get_directory()?;
+        let snapshots = File::snapshot_directory()?;
+        let mut stats = LibraryStats {
+            names: vec![],
+            duration: Duration::ZERO,
+            size: 0,
+        };
+
+        for recording in recordings {
+            stats.names.push(recording.name.clone());
+            for (folder, extension) in [(&path, "wav"), (&snapshots, "bin")] {
+                if let Some(size) =
+                    storage.size(&format!("{}/{}.{}", folder, recording.name, extension))
+                {
+                    stats.size += size;
+                }
+            }
+
+            // Only the header is read, so this stays quick for long recordings
+            if let Ok(reader) = WavReader::open(format!("{}/{}.wav", path, recording.name)) {
+                let sample_rate = reader.spec().sample_rate;
+                if sample_rate > 0 {
+                    stats.duration +=
+                        Duration::from_secs_f64(reader.duration() as f64 / sample_rate as f64);
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
+    pub fn is_current(&self, recordings: &Vec<Recording>) -> bool {
+        // Checks if the totals were worked out from the same recordings
+        self.names.len() == recordings.len()
+            && self
+                .names
+                .iter()
+                .zip(recordings)
+                .all(|(name, recording)| *name == recording.name)
+    }
+}
+
+// -------- Functions --------
+pub fn save(data: DataType, file: &str) -> Option<Error> {
+    // Save data to files
+    let path = match File::get_directory() {
+        Ok(value) => value,
+        Err(error) => return Some(error),
+    };
+    match data {
+        // Checks if saving settings data or snapshot data
+        DataType::Settings(value) => {
+            match save_file(format!("{}/{}.bin", path, file), SAVE_VERSION, &value) {
+                // Saves settings daat
+                Ok(_) => {
+                    return None;
+                }
+                Err(_) => {
+                    return Some(Error::SaveError);
+                }
+            }
+        }
+        DataType::SnapShot(value) => {
+            // The player passes the recording's whole path, so only the name is kept
+            let name = Path::new(file)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or(file.to_string());
+            let path = match File::snapshot_directory() {
+                Ok(value) => value,
+                Err(error) => return Some(error),
+            };
+            match save_file(format!("{}/{}.bin", path, name), SAVE_VERSION, &value) {
+                // Saves snapshot data
+                Ok(_) => None,
+                Err(_) => Some(Error::SaveError),
+            }
+        }
+    }
+}
+
+pub fn load(file: &str, kind: LoadType) -> Result<DataType, Error> {
+    // Loads data from file
+    let path = match File::get_directory() {
+        Ok(value) => value,
+        Err(error) => return Err(error),
+    };
+    match kind {
+        // Checks to see what kind of data it should be loading
+        // Files saved at an older version are upgraded by savefile, filling new fields with their defaults
+        LoadType::Settings => match load_file(format!("{}/{}.bin", path, file), SAVE_VERSION) {
+            // Loads settings data
+            Ok(value) => {
+                let mut settings: Settings = value;
+                if settings.clamp() {
+                    // Hand edited or corrupt values would push the filters to extremes
+                    VALUES_CLAMPED.store(true, AtomicOrdering::Relaxed);
+                }
+                return Ok(DataType::Settings(settings));
+            }
+            Err(error) => {
+                return Err(load_error(error, file));
+            }
+        },
+        LoadType::Snapshot => {
+            let snapshots = File::snapshot_directory()?;
+            match load_file(format!("{}/{}.bin", snapshots, file), SAVE_VERSION) {
+                // Loads snapshot data
+                Ok(value) => {
+                    let mut snapshot: SnapShot = value;
+                    if snapshot.clamp() {
+                        VALUES_CLAMPED.store(true, AtomicOrdering::Relaxed);
+                    }
+                    let limit = wav_length(file)
+                        .map(|length| (length.as_millis() / PLAYER_TICK.as_millis()) as i32);
+                    if snapshot.drop_invalid(limit) {
+                        FRAMES_DROPPED.store(true, AtomicOrdering::Relaxed);
+                    }
+                    return Ok(DataType::SnapShot(snapshot));
+                }
+                Err(error) => {
+                    return Err(load_error(error, file));
+                }
+            }
+        }
+    }
+}
+
+pub fn load_error(error: SavefileError, file: &str) -> Error {
+    // Converts a savefile error into an app error, keeping the name of the file so the user knows which one is bad
+    match error {
+        SavefileError::WrongVersion { .. } => Error::VersionError, // Can't downgrade data from a newer version
+        _ => Error::LoadError(file.to_string()),
+    }
+}
+
+pub fn detect_start_offset(name: &str) -> Result<f32, Error> {
+    // Works out how many seconds of silence a recording starts with without changing the file
+    let (spec, channels) = read_channels(name)?;
+    Ok(match first_sound(&channels) {
+        Some(frame) => frame as f32 / spec.sample_rate as f32,
+        None => 0.0, // Nothing to skip to in a silent recording
+    })
+}
+
+pub fn pad_silence(name: &str, frames: u64) -> Option<Error> {
+    // Writes silent frames onto the end of a recording until it's the given number of frames long
+    // Used for kept empty recordings as the recorder never writes the silence it skips, though it may have written a short sound
+    let path = match File::get_directory() {
+        Ok(value) => value,
+        Err(error) => return Some(error),
+    };
+
+    let written = match WavReader::open(format!("{}/{}.wav", path, name)) {
+        Ok(reader) => reader.duration() as u64,
+        Err(_) => return Some(Error::ReadError(name.to_string())),
+    };
+    let mut writer = match WavWriter::append(format!("{}/{}.wav", path, name)) {
+        Ok(value) => value,
+        Err(_) => return Some(Error::WriteError),
+    };
+    let channels = writer.spec().channels as u64;
+    for _ in 0..frames.saturating_sub(written) * channels {
+        if writer.write_sample(0.0_f32).is_err() {
+            return Some(Error::WriteError);
+        }
+    }
+
+    match writer.finalize() {
+        Ok(_) => None,
+        Err(_) => Some(Error::WriteError),
+    }
+}
+
+pub fn write_channels(name: &str, spec: WavSpec, channels: &[Vec<f32>]) -> Option<Error> {
+    // Writes samples split up by channel to a recording, replacing it if it's already there
+    // The samples go to a temporary file first so a failure leaves any existing recording alone
+    let path = match File::get_directory() {
+        Ok(value) => value,
+        Err(error) => return Some(error),
+    };
+    let file = format!("{}/{}.wav", path, name);
+    let temporary = format!("{}.tmp", file);
+    let mut writer = match WavWriter::create(&temporary, spec) {
+        Ok(value) => value,
+        Err(_) => return Some(Error::WriteError),
+    };
+    // Samples are written back in the recording's own format, so files that weren't made by the app keep theirs
+    for frame in 0..channels.first().map_or(0, |channel| channel.len()) {
+        for channel in channels {
+            let written = match spec.sample_format {
+                SampleFormat::Float => writer.write_sample(channel[frame]),
+                SampleFormat::Int => writer.write_sample(int_sample(channel[frame], spec)),
+            };
+            if written.is_err() {
+                return Some(Error::WriteError);
+            }
+        }
+    }
+    if writer.finalize().is_err() {
+        return Some(Error::WriteError);
+    }
+
+    match rename(&temporary, &file) {
+        Ok(_) => None,
+        Err(_) => Some(Error::RenameError),
+    }
+}
+
+pub fn channel_label(channels: u16) -> String {
+    // Names a channel count for messages
+    match channels {
+        1 => String::from("mono"),
+        2 => String::from("stereo"),
+        count => format!("{} channels", count),
+    }
+}
+
+pub fn wav_spec(name: &str) -> Option<WavSpec> {
+    // Reads a recording's format from its header, nothing if it can't be read
+    let path = File::get_directory().ok()?;
+    WavReader::open(format!("{}/{}.wav", path, name))
+        .ok()
+        .map(|reader| reader.spec())
+}
+
+pub fn free_space(path: &str) -> Option<u64> {
+    // Asks the file system how many bytes can still be written to the disk a folder is on, nothing if it can't tell
+    fs4::available_space(path).ok()
+}
+
+pub fn recording_bytes(seconds: u64) -> u64 {
+    // Space a recording of some length takes up, two channels of 32 bit samples
+    seconds * RECORDING_SAMPLE_RATE as u64 * 2 * 4
+}
+
+pub fn space_summary(free: Option<u64>) -> SharedString {
+    // Says roughly how long could be recorded in the space left, nothing if it isn't known
+    match free {
+        Some(free) => {
+            let seconds = free.saturating_sub(DISK_SPACE_RESERVE) / recording_bytes(1);
+            SharedString::from(format!(
+                "About {} h {} min of recording space left",
+                seconds / 3600,
+                seconds % 3600 / 60
+            ))
+        }
+        None => SharedString::new(),
+    }
+}
+
+pub fn wav_length(name: &str) -> Option<Duration> {
+    // Reads how long a recording is from its header, nothing if it can't be read
+    let path = File::get_directory().ok()?;
+    let reader = WavReader::open(format!("{}/{}.wav", path, name)).ok()?;
+    match reader.spec().sample_rate {
+        0 => None,
+        rate => Some(Duration::from_secs_f64(
+            reader.duration() as f64 / rate as f64,
+        )),
+    }
+}
+
+pub fn wav_channels(name: &str) -> Option<u16> {
+    // Reads how many channels a recording has from its header, nothing if it can't be read
+    wav_spec(name).map(|spec| spec.channels)
+}
+
+pub fn convert_recording(name: &str, channels: u16, sample_rate: Option<u32>) -> Option<Error> {
+    // Rewrites a recording with a different number of channels and, if given, a different sample rate
+    // Going to mono averages every channel, otherwise the channels there are repeat to fill the new ones so mono is copied to both sides
+    let (spec, samples) = match read_channels(name) {
+        Ok(value) => value,
+        Err(error) => return Some(error),
+    };
+    let sample_rate = sample_rate.unwrap_or(spec.sample_rate);
+    if (spec.channels == channels && spec.sample_rate == sample_rate)
+        || samples.is_empty()
+        || channels == 0
+    {
+        return None;
+    }
+    let samples: Vec<Vec<f32>> = samples
+        .iter()
+        .map(|channel| resample(channel, spec.sample_rate, sample_rate))
+        .collect();
+    let converted: Vec<Vec<f32>> = if channels == 1 {
+        let count = samples.len() as f32;
+        vec![(0..samples[0].len())
+            .map(|frame| samples.iter().map(|channel| channel[frame]).sum::<f32>() / count)
+            .collect()]
+    } else {
+        (0..channels as usize)
+            .map(|channel| samples[channel % samples.len()].clone())
+            .collect()
+    };
+    write_channels(
+        name,
+        WavSpec {
+            channels,
+            sample_rate,
+            ..spec
+        },
+        &converted,
+    )
+}
+
+pub fn format_mismatch(name: &str, first: WavSpec, other: WavSpec) -> Option<String> {
+    // Says how a recording's format differs from the first one being merged, or nothing if they match
+    if other.sample_rate != first.sample_rate {
+        Some(format!(
+            "{} is {} Hz instead of {} Hz",
+            name, other.sample_rate, first.sample_rate
+        ))
+    } else if other.channels != first.channels {
+        Some(format!(
+            "{} has {} channels instead of {}",
+            name, other.channels, first.channels
+        ))
+    } else if other.bits_per_sample != first.bits_per_sample
+        || other.sample_format != first.sample_format
+    {
+        Some(format!(
+            "{} is {} bit instead of {} bit",
+            name, other.bits_per_sample, first.bits_per_sample
+        ))
+    } else {
+        None
+    }
+}
+
+pub fn merge(names: &[String], recordings: &Vec<Recording>) -> Result<String, Error> {
+    // Joins recordings end to end into a new one along with their automation and returns its name
+    // Formats are all checked before anything is read so a mismatch is found quickly
+    if names.len() < 2 {
+        return Err(Error::MergeCountError);
+    }
+    let path = File::get_directory()?;
+    let mut first = None;
+    for name in names {
+        let spec = match WavReader::open(format!("{}/{}.wav", path, name)) {
+            Ok(reader) => reader.spec(),
+            Err(_) => return Err(Error::ReadError(name.clone())),
+        };
+        match first {
+            None => first = Some(spec),
+            Some(first) => {
+                if let Some(reason) = format_mismatch(name, first, spec) {
+                    return Err(Error::FormatMismatchError(reason));
+                }
+            }
+        }
+    }
+
+    let mut joined: Vec<Vec<f32>> = vec![];
+    let mut automation = SnapShot::new();
+    let mut spec = None;
+    for name in names {
+        // Each recording's automation starts from where the audio before it ends
+        let offset = joined.first().map_or(0, |channel| channel.len());
+        let (read, channels) = read_channels(name)?;
+        let tick = (offset as u64 * 1000 / read.sample_rate as u64 / PLAYER_TICK.as_millis() as u64)
+            as i32;
+        let snapshot = match load(name, LoadType::Snapshot) {
+            Ok(DataType::SnapShot(value)) => value,
+            _ => SnapShot::new(),
+        };
+        for (values, frame) in snapshot.section(0, None).frames {
+            automation.frames.push((values, frame + tick));
+        }
+
+        if joined.is_empty() {
+            joined = channels;
+        } else {
+            for (channel, samples) in joined.iter_mut().zip(channels) {
+                channel.extend(samples);
+            }
+        }
+        spec = Some(read);
+    }
+
+    let name = unused_name(&Disk, format!("{} merged", names[0]), recordings);
+    if let Some(spec) = spec {
+        if let Some(error) = write_channels(&name, spec, &joined) {
+            return Err(error);
+        }
+    }
+    match automation.save(&name) {
+        Some(error) => Err(error),
+        None => Ok(name),
+    }
+}
+
+pub fn export(name: &str, directory: &str, format: ExportFormat) -> Result<String, Error> {
+    // Writes a copy of a recording to a folder in the chosen format and returns the name it was given
+    // A number is added if the folder already has a recording by that name so nothing there is written over
+    let (spec, channels) = read_channels(name)?;
+    let File::Names(taken) = File::search(&Disk, directory, "wav", false)?;
+    let exported = unique_name(name.to_string(), &taken);
+    let spec = format.spec(spec);
+    let mut writer = match WavWriter::create(format!("{}/{}.wav", directory, exported), spec) {
+        Ok(value) => value,
+        Err(_) => return Err(Error::WriteError),
+    };
+    for frame in 0..channels.first().map_or(0, |channel| channel.len()) {
+        for channel in &channels {
+            let written = match spec.sample_format {
+                SampleFormat::Float => writer.write_sample(channel[frame]),
+                SampleFormat::Int => writer.write_sample(int_sample(channel[frame], spec)),
+            };
+            if written.is_err() {
+                return Err(Error::WriteError);
+            }
+        }
+    }
+    match writer.finalize() {
+        Ok(_) => Ok(exported),
+        Err(_) => Err(Error::WriteError),
+    }
+}
+
+pub fn sample_scale(spec: WavSpec) -> f32 {
+    // Value of a full scale integer sample at the file's bit depth
+    (1_i64 << (spec.bits_per_sample.clamp(1, 32) - 1)) as f32
+}
+
+pub fn int_sample(sample: f32, spec: WavSpec) -> i32 {
+    // Turns a -1 to 1 sample into an integer one at the file's bit depth, clipping anything past full scale
+    let scale = sample_scale(spec);
+    (sample * scale).round().clamp(-scale, scale - 1.0) as i32
+}
+
+pub fn read_channels(name: &str) -> Result<(WavSpec, Vec<Vec<f32>>), Error> {
+    // Reads a recording and splits its samples up by channel
+    let path = File::get_directory()?;
+    let mut reader = match WavReader::open(format!("{}/{}.wav", path, name)) {
+        Ok(value) => value,
+        Err(_) => return Err(Error::ReadError(name.to_string())),
+    };
+    let spec = reader.spec();
+    // Integer samples are scaled into the same -1 to 1 range as float ones so everything after works on either
+    let samples: Result<Vec<f32>, _> = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect(),
+        SampleFormat::Int => {
+            let scale = sample_scale(spec);
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|value| value as f32 / scale))
+                .collect()
+        }
+    };
+    let samples = match samples {
+        Ok(value) => value,
+        Err(_) => return Err(Error::ReadError(name.to_string())),
+    };
+
+    let mut channels = vec![vec![]; spec.channels as usize];
+    for (index, sample) in samples.iter().enumerate() {
+        channels[index % spec.channels as usize].push(*sample);
+    }
+
+    Ok((spec, channels))
+}
+
+pub fn normalise(name: &str) -> Option<Error> {
+    // Scales a recording so its loudest sample sits at the normalise peak
+    // The result is written to a temporary file first so a failure leaves the original alone
+    let path = match File::get_directory() {
+        Ok(value) => value,
+        Err(error) => return Some(error),
+    };
+    let file = format!("{}/{}.wav", path, name);
+
+    let mut reader = match WavReader::open(&file) {
+        Ok(value) => value,
+        Err(_) => return Some(Error::ReadError(name.to_string())),
+    };
+    let spec = reader.spec();
+    let samples: Vec<f32> = match reader.samples::<f32>().collect() {
+        Ok(value) => value,
+        Err(_) => return Some(Error::ReadError(name.to_string())), // Also reached for files that don't use float samples
+    };
+
+    let peak = samples
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    if peak == 0.0 {
+        return None; // Nothing to turn up
+    }
+    let gain = NORMALISE_PEAK / peak;
+
+    let temporary = format!("{}.tmp", file);
+    let mut writer = match WavWriter::create(&temporary, spec) {
+        Ok(value) => value,
+        Err(_) => return Some(Error::WriteError),
+    };
+    for sample in samples {
+        if writer.write_sample(sample * gain).is_err() {
+            return Some(Error::WriteError);
+        }
+    }
+    if writer.finalize().is_err() {
+        return Some(Error::WriteError);
+    }
+
+    match rename(&temporary, &file) {
+        Ok(_) => None,
+        Err(_) => Some(Error::RenameError),
+    }
+}
@@ -0,0 +1,417 @@
+// Recording, playback, file, and settings logic shared by the app and its tests
+// main.rs only wires these into the UI
+
+// -------- Imports --------
+use cpal::traits::{DeviceTrait, HostTrait}; // Listing and picking output devices
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter}; // Imports for reading and rewriting recorded data
+use kira::{
+    // Imports for playing back recordings and editing them
+    backend::cpal::CpalBackendSettings,
+    effect::{
+        eq_filter::{EqFilterBuilder, EqFilterHandle, EqFilterKind},
+        filter::{FilterBuilder, FilterHandle, FilterMode},
+        panning_control::{PanningControlBuilder, PanningControlHandle},
+        volume_control::{VolumeControlBuilder, VolumeControlHandle},
+        Effect, EffectBuilder,
+    },
+    info::Info,
+    sound::{
+        static_sound::{StaticSoundData, StaticSoundSettings},
+        FromFileError, Sound, SoundData,
+    },
+    track::TrackBuilder,
+    AudioManagerSettings,
+    DefaultBackend,
+    Easing,
+    Frame,
+    Mix,
+    Tween,
+};
+use rand::random_range; // Random numbers
+use savefile::{load_file, save_file, SavefileError}; // Saving settings and snapshot data
+use savefile_derive::Savefile;
+use slint::{
+    ModelRc, PhysicalPosition, PhysicalSize, SharedString, ToSharedString, VecModel, Window,
+}; // Imports for UI
+use std::{
+    // File reading, current time, and reference variables
+    cmp::Ordering,
+    collections::VecDeque,
+    env,
+    f32::consts::TAU,
+    f64::consts::PI,
+    ffi::OsString,
+    fs::{self, remove_file, rename},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        mpsc, Arc, Mutex, RwLock, RwLockWriteGuard,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use thread_priority::{set_current_thread_priority, ThreadPriority}; // Running audio threads ahead of others
+
+// -------- Constants --------
+// Version of the saved data layout, passed to every save and load
+// Bump this whenever a field is added to a saved struct and mark the field with #[savefile_versions = "N.."] and a default
+// so that files saved by older versions are upgraded on load instead of failing
+pub const SAVE_VERSION: u32 = 44;
+pub const SETTINGS_FILE: &str = "settings"; // Name of the file the settings are saved to
+pub const SHARED_PRESETS_EXTENSION: &str = "csv"; // Preset files with this extension are text with the bands in decibels
+pub const RESERVED_NAMES: [&str; 1] = [SETTINGS_FILE]; // Names recordings can't use because the app's own files share the folder
+pub const DATA_DIR_VARIABLE: &str = "AUDIO_DATA_DIR"; // Environment variable that points the app at a different data folder
+pub const SNAPSHOT_FOLDER: &str = "snapshots"; // Subfolder of the data folder that snapshots are kept in when the option is on
+pub const SAVE_DEBOUNCE: Duration = Duration::from_millis(300); // How long the dials have to stay still before settings are written to disk
+pub const RECORDING_SAMPLE_RATE: u32 = 48000; // Sample rate that new recordings are written at
+pub const RESAMPLE_TAPS: usize = 32; // Filter zero crossings on each side of a resampled sample, more is sharper but slower
+pub const RESAMPLE_PHASES: usize = 1024; // Most filter positions worked out ahead of time, rates that don't divide evenly use the nearest one
+pub const RESAMPLE_CACHE_SIZE: usize = 2; // Recordings kept at the playback rate so playing them again doesn't redo the work
+pub const DEFAULT_RECORDING_WARN_AFTER: u32 = 600; // Seconds of recording before the user is warned that it's getting long
+pub const DEFAULT_PAN_GLIDE_MS: u32 = 120; // Milliseconds captured pan moves glide over unless changed in the options panel
+pub const DEFAULT_CAPTURE_AUTOSAVE_SECONDS: u32 = 5; // Seconds between saves of captured inputs unless changed in the options panel
+pub const DEFAULT_PARAMETER_GLIDE_MS: u32 = 10; // Milliseconds dial changes glide over during playback, the same as kira's default tween
+pub const DEFAULT_FALLBACK_LIMIT: u32 = 10; // 'Default taken...' recordings allowed before new recordings are refused
+pub const FALLBACK_NAME: &str = "Default taken..."; // Start of the name given to new recordings when the next 'Recording N' is taken
+pub const DEFAULT_METRONOME_BPM: u32 = 120;
+pub const DEFAULT_BEATS_PER_BAR: u32 = 4;
+pub const PLAYER_TICK: Duration = Duration::from_millis(20); // How often the player updates the effects, snapshot frames are counted in these
+pub const STOP_REPLY_LIMIT: Duration = Duration::from_secs(2); // Longest the UI waits for the player to save captured inputs before giving up on a delete
+pub const AUTOMATION_LOOKAHEAD: usize = 25; // Player ticks before a snapshot frame that it's shown as coming up, about half a second
+pub const CLICK_LENGTH: f32 = 0.03; // Seconds that a metronome click lasts for
+
+// qruhear picks its own capture buffer size so it can't be set here, instead callbacks of any length are accepted
+// and a channel that arrives ahead of the other is held until the other catches up. Holding more adds latency before
+// the audio is written, holding less means a late channel is treated as a dropout sooner
+pub const CHANNEL_DRIFT_LIMIT: usize = 9600; // Most samples one channel can get ahead of the other by before they're dropped
+pub const DROPOUT_TOLERANCE: Duration = Duration::from_millis(50); // How much later than expected a capture buffer can arrive before it counts as a dropout
+pub const SOUND_FLOOR: f32 = 0.001; // Quietest a sample can be and still count towards a recording having sound in it, about -60 dB
+pub const MIN_VISIBLE_WINDOW: i32 = 100; // Pixels of a restored window that have to be on a display for it to be left where it was
+pub const DEFAULT_MIN_SOUND_MS: u32 = 50; // How long a recording has to be above the sound floor before it isn't thrown away as empty
+pub const DISK_SPACE_RESERVE: u64 = 50_000_000; // Bytes left free on the disk, recording stops before going under this so the file can be finished
+pub const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(5); // How often free space is checked while recording
+pub const DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(500); // How often the device thread looks for unplugged outputs
+pub const MONITOR_BUFFER_LIMIT: usize = 4800; // Most frames the monitor can fall behind by before old audio is dropped
+pub const HIGH_PASS_OPEN: f32 = 20.0; // High pass cutoff in hertz that lets everything through
+pub const LOW_PASS_OPEN: f32 = 20000.0; // Low pass cutoff in hertz that lets everything through
+pub const SILENT_DIAL: i32 = -7; // Dial value that mutes a band
+pub const MIN_DIAL: i32 = SILENT_DIAL; // Lowest value a dial can be turned to, anything loaded below this is raised to it
+pub const MAX_DIAL: i32 = 7; // Highest value a dial can be turned to, anything loaded above this is lowered to it
+
+// Set when loaded data had values outside of the dial range, shown as a warning the next time errors are checked
+pub static VALUES_CLAMPED: AtomicBool = AtomicBool::new(false);
+// Set when a loaded snapshot had frames before the start or after the end of its recording, shown the same way
+pub static FRAMES_DROPPED: AtomicBool = AtomicBool::new(false);
+// Set from the settings so snapshots can be found without the settings being passed around
+pub static SNAPSHOTS_IN_FOLDER: AtomicBool = AtomicBool::new(false);
+// Set whenever the settings or the copy on disk change, so the unsaved check only compares them when they might differ
+pub static SETTINGS_CHANGED: AtomicBool = AtomicBool::new(true);
+pub const SILENT_GAIN: f32 = -60.0; // Decibels used for a muted band
+pub const GAIN_PER_STEP: f32 = 4.0; // Decibels per dial step so that a difference can be heard
+pub const METER_FLOOR: f32 = -60.0; // Quietest level in decibels that shows on the output meter
+pub const NORMALISE_PEAK: f32 = 0.891; // Loudest sample after normalising, about 1 dB under full scale to leave room for the effects
+pub const NOISE_PROFILE_FILE: &str = "noise profile"; // Name of the file the captured noise profile is saved to
+pub const NOISE_WINDOW: usize = 1024; // Samples in each slice of audio the noise reduction looks at, has to be a power of two
+pub const NOISE_OVER_SUBTRACTION: f32 = 1.5; // How much more than the profiled noise is taken away so it doesn't flutter back in
+pub const NOISE_FLOOR_GAIN: f32 = 0.05; // Lowest a frequency is turned down to so the result doesn't sound hollow
+pub const PANNING_PER_STEP: f32 = 0.15; // Panning per dial step, smaller as panning is more sensitive to changes
+
+// -------- Modules --------
+// Each module brings in everything above with use crate::*, and the others through the re-exports below
+mod dsp;
+mod files;
+mod settings;
+mod snapshot;
+mod threads;
+mod tracker;
+
+pub use dsp::*;
+pub use files::*;
+pub use settings::*;
+pub use snapshot::*;
+pub use threads::*;
+pub use tracker::*;
+
+// -------- Enums --------
+// How serious a notice is, the UI has its own copy that it styles notifications with
+#[derive(Clone, Copy, PartialEq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+// Errors
+#[derive(Clone, PartialEq)] // Derives attributes like .clone() and ==
+pub enum Error {
+    // Keeps track of errors
+    SaveError,           // Error while saving any data
+    LoadError(String),   // Error while loading any data, with the name of the file that failed
+    RecordError,         // Error while recording audio
+    WriteError,          // Error while saving audio data
+    ReadError(String),   // Error while reading data on disk, with the name of the file that failed
+    RenameError,         // Error while renaming file
+    DeleteError,         // Error while deleting file
+    FallbackError,       // Attempt to rename recording to 'Default taken...'
+    EmptyError,          // Attempt to rename recording to ''
+    ExistsError,         // Attempt to rename recording to an already existing name
+    SaveFileRenameError, // Attempt to rename recording to a reserved name like 'settings'
+    PlaybackError(String), // Error playing audio, with what the audio library said went wrong
+    ShuffleError,        // Not enough recordings to shuffle
+    DirectoryError,      // Returned directory not the working directory
+    RecorderThreadError, // Recorder thread failed to start
+    PlayerThreadError,   // Player thread failed to start
+    MessageError,        // Unexpected message sent to thread
+    EmptyRecordingError, // Specifically when a recording is made that contains no sound and couldn't be automatically deleted
+    VersionError,        // Data was saved by a newer version of the app
+    RebuiltError,        // Settings couldn't be read and were rebuilt from the recordings on disk
+    ReservedNameError,   // A recording on disk had a reserved name and was renamed
+    EmptyFileError, // A recording file has no audio in it, usually because writing it was cut short
+    NormaliseError, // A new recording couldn't be normalised after it finished
+    FallbackLimitError, // Too many recordings have fallen back to 'Default taken...' names
+    NoiseProfileError, // A noise profile couldn't be taken as the recording is shorter than one noise window
+    MissingProfileError, // Noise reduction was started before a noise profile was captured
+    DeviceLostError, // The device audio was going to was unplugged so playback or recording was paused
+    NameTakenError, // The next 'Recording N' was taken and the user chose not to record under another name
+    NoSelectionError, // Cropping was asked for without a loop region to keep
+    SplitEdgeError, // Splitting was asked for at the very start or end so one part would be empty
+    MergeCountError, // Merging was asked for with fewer than two recordings queued
+    FormatMismatchError(String), // Recordings being merged don't share a format, with why
+    MissingFileError(String), // The current recording's file was gone when it was played so it was skipped, with its name
+    ClickLoadError(String),   // A custom metronome click couldn't be loaded, with its path
+    PresetRangeError(String), // Presets weren't imported as one had values out of range, with its name
+    NoPresetsError,           // A settings file had no presets to import
+    NoSpaceError,             // Recording wasn't started as the disk is almost full
+    SpaceRunningOutError,     // Recording was stopped before the disk filled up
+    StopTimeoutError,         // The player didn't finish saving captured inputs in time so a delete was called off
+}
+
+impl Error {
+    pub fn get_text(&self) -> SharedString {
+        // Takes an error value and returns a shared string to send to the ui
+        match self {
+            Error::SaveError => SharedString::from("Failed to save data"),
+            Error::LoadError(file) => {
+                SharedString::from(format!("Data doesn't exist ... {}", file))
+            }
+            Error::RecordError => SharedString::from("Recording failed"),
+            Error::WriteError => SharedString::from("Failed to write audio"),
+            Error::ReadError(file) => SharedString::from(format!("File read failed ... {}", file)),
+            Error::RenameError => SharedString::from("Failed to rename file"),
+            Error::DeleteError => SharedString::from("Failed to delete file"),
+            Error::FallbackError => SharedString::from("Name can't contain 'Default taken...'"),
+            Error::EmptyError => SharedString::from("Name has to contain something"),
+            Error::ExistsError => SharedString::from("Name already exists"),
+            Error::SaveFileRenameError => SharedString::from("Name is reserved for app data"),
+            Error::PlaybackError(detail) => {
+                SharedString::from(format!("Failed to play audio ... {}", detail))
+            }
+            Error::ShuffleError => {
+                SharedString::from("At least three recordings required to shuffle")
+            }
+            Error::DirectoryError => SharedString::from("Couldn't find correct file directory"),
+            Error::RecorderThreadError => {
+                SharedString::from("Recording thread crashed ... Restart required")
+            }
+            Error::PlayerThreadError => {
+                SharedString::from("Audio thread crashed ... Restart required")
+            }
+            Error::MessageError => SharedString::from("Incorrect message sent to thread"),
+            Error::EmptyRecordingError => {
+                SharedString::from("Failed to delete new empty recording")
+            }
+            Error::VersionError => SharedString::from("Data was saved by a newer version"),
+            Error::RebuiltError => {
+                SharedString::from("Settings were unreadable ... Rebuilt from recordings")
+            }
+            Error::ReservedNameError => {
+                SharedString::from("A recording used a reserved name ... Renamed")
+            }
+            Error::EmptyFileError => SharedString::from("Recording has no audio to play"),
+            Error::NormaliseError => SharedString::from("Failed to normalise new recording"),
+            Error::FallbackLimitError => SharedString::from(
+                "Too many 'Default taken...' recordings ... Rename them in the options panel",
+            ),
+            Error::NoiseProfileError => {
+                SharedString::from("Recording is too short to take a noise profile from")
+            }
+            Error::MissingProfileError => {
+                SharedString::from("Capture a noise profile from a silent recording first")
+            }
+            Error::DeviceLostError => {
+                SharedString::from("Audio device was unplugged ... Paused until it's back")
+            }
+            Error::NameTakenError => SharedString::from(
+                "Next recording name is taken ... Rename it or pick another option for taken names",
+            ),
+            Error::NoSelectionError => {
+                SharedString::from("Set a loop start or end to choose the part to keep")
+            }
+            Error::SplitEdgeError => {
+                SharedString::from("Can't split at the very start or end ... Both parts need audio")
+            }
+            Error::MergeCountError => {
+                SharedString::from("Queue at least two recordings to merge them")
+            }
+            Error::FormatMismatchError(reason) => {
+                SharedString::from(format!("Can't merge ... {}", reason))
+            }
+            Error::ClickLoadError(path) => {
+                SharedString::from(format!("Click sound couldn't be loaded ... {}", path))
+            }
+            Error::PresetRangeError(name) => SharedString::from(format!(
+                "Preset is out of range ... Nothing was imported from the file with {}",
+                name
+            )),
+            Error::NoPresetsError => SharedString::from("File doesn't have any presets to import"),
+            Error::NoSpaceError => {
+                SharedString::from("Disk is almost full ... Free up space to record")
+            }
+            Error::SpaceRunningOutError => {
+                SharedString::from("Disk was almost full ... Recording stopped and saved")
+            }
+            Error::StopTimeoutError => {
+                SharedString::from("Captured inputs were still saving ... Try deleting again")
+            }
+            Error::MissingFileError(name) => SharedString::from(format!(
+                "{} is missing from the folder ... Skipped it",
+                name
+            )),
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        // How serious an error is so the UI can style it and decide whether to hide it automatically
+        match self {
+            Error::ShuffleError | Error::MergeCountError | Error::NoPresetsError => Severity::Info,
+            Error::FallbackError
+            | Error::EmptyError
+            | Error::ExistsError
+            | Error::SaveFileRenameError
+            | Error::MessageError
+            | Error::EmptyRecordingError
+            | Error::RebuiltError
+            | Error::ReservedNameError
+            | Error::EmptyFileError
+            | Error::NoiseProfileError
+            | Error::MissingProfileError
+            | Error::DeviceLostError
+            | Error::NameTakenError
+            | Error::NoSelectionError
+            | Error::SplitEdgeError
+            | Error::FormatMismatchError(_)
+            | Error::MissingFileError(_)
+            | Error::ClickLoadError(_)
+            | Error::PresetRangeError(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+// Warnings
+#[derive(Clone, Copy, PartialEq)]
+pub enum Warning {
+    // Keeps track of notices that don't stop anything from working
+    LongRecording,          // Recording has gone past the warning threshold
+    ValuesClamped,          // Loaded data had dial values that were out of range
+    FramesDropped,          // A loaded snapshot had frames outside of its recording
+    LowDiskSpace,           // The disk may fill up before a recording reaches the warning length
+    PlaybackCaptured,       // Recording and playing at the same time
+    Dropouts,               // Audio was lost while recording
+    PostRecordDone,         // Every post-record action ran on the new recording
+    FallbacksRenamed,       // 'Default taken...' recordings were given normal names
+    QueueSkipped,           // Queued recordings that no longer exist were passed over
+    RevealFailed,           // The system file manager couldn't be opened
+    ProfileCaptured,        // A new noise profile was saved
+    Denoised,               // A cleaned copy of a recording was added
+    Cropped,                // A recording was cut down to its loop region
+    Split,                  // A recording was split into two new ones
+    Merged,                 // Queued recordings were joined into a new one
+    Exported,               // A recording was written to the export folder
+    PresetsImported,        // Presets from another settings file were added to the list
+    PresetsExported,        // Presets were written to a file with their bands in decibels
+    AutomationReset(usize), // Every recording's snapshot was replaced with an empty one, with how many
+    ExportFallback,         // The export folder couldn't be written to so the data folder was used
+    ChannelsMissing, // The input doesn't have one of the chosen channels so a fallback is being recorded
+    PriorityNotRaised, // The system didn't let an audio thread run ahead of others
+    LibraryRepaired, // Recordings that were out of step with the files on disk were fixed on startup
+    OutputMissing,   // The chosen output device isn't connected so the default one is used
+    DeviceRestored,  // An unplugged device came back and what it stopped was started again
+    ClickCaptured,   // The metronome wasn't started because its output is the one being recorded
+    MonitorCaptured, // The monitor wasn't started because its output is the one being recorded
+}
+
+impl Warning {
+    pub fn get_text(self) -> SharedString {
+        // Takes a warning value and returns a shared string to send to the ui
+        match self {
+            Warning::LongRecording => SharedString::from("Recording is getting long"),
+            Warning::LowDiskSpace => SharedString::from(
+                "Disk space may run out before the recording gets long ... It will stop in time",
+            ),
+            Warning::FramesDropped => SharedString::from(
+                "Some saved automation was outside of its recording and has been left out",
+            ),
+            Warning::ValuesClamped => {
+                SharedString::from("Some saved dial values were out of range and have been reset")
+            }
+            Warning::PlaybackCaptured => {
+                SharedString::from("Playback will be captured if it uses the recorded device")
+            }
+            Warning::Dropouts => SharedString::from("Some audio was lost during the recording"),
+            Warning::PostRecordDone => SharedString::from("Finished processing the new recording"),
+            Warning::FallbacksRenamed => {
+                SharedString::from("Renamed 'Default taken...' recordings")
+            }
+            Warning::QueueSkipped => {
+                SharedString::from("Skipped queued recordings that are missing")
+            }
+            Warning::RevealFailed => SharedString::from("Couldn't open the file manager"),
+            Warning::ProfileCaptured => SharedString::from("Captured a new noise profile"),
+            Warning::Denoised => SharedString::from("Added a cleaned copy of the recording"),
+            Warning::Cropped => SharedString::from("Cropped the recording to its loop region"),
+            Warning::Split => SharedString::from("Split the recording into two new ones"),
+            Warning::Merged => SharedString::from("Joined the queue into a new recording"),
+            Warning::Exported => SharedString::from("Exported the recording"),
+            Warning::AutomationReset(count) => SharedString::from(format!(
+                "Reset the automation of {} recording{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            )),
+            Warning::PresetsImported => {
+                SharedString::from("Imported the presets ... Any with a name in use were numbered")
+            }
+            Warning::PresetsExported => {
+                SharedString::from("Exported the presets ... Bands are given in decibels")
+            }
+            Warning::ExportFallback => SharedString::from(
+                "Export folder can't be written to ... Exported to the data folder",
+            ),
+            Warning::ChannelsMissing => {
+                SharedString::from("Input doesn't have the chosen channels ... Recording others")
+            }
+            Warning::PriorityNotRaised => {
+                SharedString::from("Audio threads couldn't be given a higher priority")
+            }
+            Warning::LibraryRepaired => {
+                SharedString::from("Fixed recordings that were out of step with the files on disk")
+            }
+            Warning::OutputMissing => {
+                SharedString::from("Chosen output isn't connected ... Playing through the default")
+            }
+            Warning::DeviceRestored => {
+                SharedString::from("Audio device is back ... Carrying on where it left off")
+            }
+            Warning::ClickCaptured => SharedString::from(
+                "Metronome would be recorded ... Play through another output to hear it",
+            ),
+            Warning::MonitorCaptured => SharedString::from(
+                "Monitor would feed back into the recording ... Play through another output",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
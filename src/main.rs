@@ -1,2428 +1,2692 @@
 // Prevent console window in addition to Slint window in Windows release builds when, e.g., starting the app via file manager. Ignored on other platforms.
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+// -------- Modules --------
+mod ui; // Sends the library's data to the window and reads edits back from it
+
 // -------- Imports --------
-use hound::{SampleFormat, WavSpec, WavWriter}; // Imports for writing recorded data to disk
-use kira::{
-    // Imports for playing back recordings and editing them
-    effect::{
-        eq_filter::{EqFilterBuilder, EqFilterKind},
-        panning_control::PanningControlBuilder,
-    },
-    sound::static_sound::StaticSoundData,
-    track::TrackBuilder,
-    AudioManager,
-    AudioManagerSettings,
-    DefaultBackend,
-    Tween,
-};
-use qruhear::{rucallback, RUBuffers, RUHear}; // Imports for recording audio
-use rand::random_range; // Random numbers
-use savefile::{load_file, save_file}; // Saving settings and snapshot data
-use savefile_derive::Savefile;
-use slint::{Model, ModelRc, SharedString, ToSharedString, VecModel}; // Imports for UI
+use audio::*; // Recording, playback, file, and settings logic from the library crate
+use slint::{
+    winit_030::{winit::monitor::MonitorHandle, WinitWindowAccessor}, // Finding the displays a restored window can go on
+    CloseRequestResponse,
+    Model,
+    ModelRc,
+    SharedString,
+    VecModel,
+}; // Imports for UI
 use std::{
-    // Threads, file reading, current time, and reference variables
-    cmp::Ordering,
-    env,
+    // Current time and reference variables
     error::Error as STDError,
-    ffi::OsString,
-    fs::{self, remove_file, rename},
-    sync::{mpsc, Arc, Mutex, RwLock},
-    thread::{self},
+    path::Path,
+    sync::{atomic::Ordering as AtomicOrdering, mpsc, Arc, RwLock},
     time::{Duration, Instant},
 };
+use ui::*;
 
 slint::include_modules!(); // Imports the auto generated functions used to control the UI variables
 
-// -------- Enums --------
-// Errors
-#[derive(Clone, Copy, PartialEq)] // Derives attributes like .clone() and ==
-enum Error {
-    // Keeps track of errors
-    SaveError,           // Error while saving any data
-    LoadError,           // Error while loading any data
-    RecordError,         // Error while recording audio
-    WriteError,          // Error while saving audio data
-    ReadError,           // Error while reading data on disk
-    RenameError,         // Error while renaming file
-    DeleteError,         // Error while deleting file
-    FallbackError,       // Attempt to rename recording to 'Default taken...'
-    EmptyError,          // Attempt to rename recording to ''
-    ExistsError,         // Attempt to rename recording to an already existing name
-    SaveFileRenameError, // Attempt to rename recording to 'settings'
-    PlaybackError,       // Error playing audio
-    ShuffleError,        // Not enough recordings to shuffle
-    DirectoryError,      // Returned directory not the working directory
-    RecorderThreadError, // Recorder thread failed to start
-    PlayerThreadError,   // Player thread failed to start
-    MessageError,        // Unexpected message sent to thread
-    EmptyRecordingError, // Specifically when a recording is made that contains no sound and couldn't be automatically deleted
-}
-
-impl Error {
-    fn get_text(self) -> SharedString {
-        // Takes an error value and returns a shared string to send to the ui
-        match self {
-            Error::SaveError => SharedString::from("Failed to save data"),
-            Error::LoadError => SharedString::from("Data doesn't exist"),
-            Error::RecordError => SharedString::from("Recording failed"),
-            Error::WriteError => SharedString::from("Failed to write audio"),
-            Error::ReadError => SharedString::from("File read failed"),
-            Error::RenameError => SharedString::from("Failed to rename file"),
-            Error::DeleteError => SharedString::from("Failed to delete file"),
-            Error::FallbackError => SharedString::from("Name can't contain 'Default taken...'"),
-            Error::EmptyError => SharedString::from("Name has to contain something"),
-            Error::ExistsError => SharedString::from("Name already exists"),
-            Error::SaveFileRenameError => SharedString::from("Can't rename to 'settings'"),
-            Error::PlaybackError => SharedString::from("Failed to play audio"),
-            Error::ShuffleError => {
-                SharedString::from("At least three recordings required to shuffle")
-            }
-            Error::DirectoryError => SharedString::from("Couldn't find correct file directory"),
-            Error::RecorderThreadError => {
-                SharedString::from("Recording thread crashed ... Restart required")
-            }
-            Error::PlayerThreadError => {
-                SharedString::from("Audio thread crashed ... Restart required")
-            }
-            Error::MessageError => SharedString::from("Incorrect message sent to thread"),
-            Error::EmptyRecordingError => {
-                SharedString::from("Failed to delete new empty recording")
-            }
-        }
-    }
+fn main() -> Result<(), Box<dyn STDError>> {
+    let ui = AppWindow::new()?;
 
-    fn send(self, ui: &AppWindow) {
-        // Takes an error value and updates the ui
-        ui.set_error_notification(self.get_text());
-        ui.set_error_recieved(true);
-    }
-}
+    let errors = Arc::new(RwLock::new(None)); // Creates error handler
 
-// Holds values used when sorting
-#[derive(PartialEq)]
-enum TextNum {
-    Text(String),
-    Number(i32),
-}
+    let warnings = Arc::new(RwLock::new(None)); // Creates warning handler
 
-impl TextNum {
-    fn split_text_and_numbers(input: String) -> Vec<TextNum> {
-        // Takes a string and returns a vector of itself
-        let mut text = String::new();
-        let mut number = String::new();
-        let mut list = vec![];
-
-        let mut adding_text = false; // Keeps track of whether the last character was text
-        let mut adding_number = false; // Keeps track of whether the last character was a number
-
-        let mut index = 0;
-        for char in input.chars() {
-            // Loops over every character in the input string
-            match char.to_string().parse::<i32>() {
-                // Attempts to parse the char into an i32
-                Ok(_) => {
-                    // If parse is successful
-                    adding_number = true;
-                    number.push(char); // Adds char to the numbers list
-                    if adding_text {
-                        adding_text = false;
-                        if text.len() > 0 {
-                            list.push(TextNum::Text(text.clone())); // Pushes the text string onto the final list
-                            text.clear(); // Clears the text string for another use
-                        }
+    // Creates a variable that can be used across threads and move blocks and can be read from without locking
+    let tracker = Arc::new(Tracker::new(
+        match load(SETTINGS_FILE, LoadType::Settings) {
+            Ok(DataType::Settings(value)) => value, // Loads settings
+            Ok(DataType::SnapShot(_)) => {
+                // If passed snapshot data then create new settings and save the file
+                Tracker::write(
+                    errors.clone(),
+                    Some(Error::LoadError(SETTINGS_FILE.to_string())),
+                );
+                match save(DataType::Settings(Settings::new()), SETTINGS_FILE) {
+                    Some(error) => {
+                        Tracker::write(errors.clone(), Some(error));
                     }
-                    if index == input.len() - 1 {
-                        // Checks to see if on the last char
-                        list.push(TextNum::Number(number.parse().unwrap())); // Pushes the number string onto the final list after parsing it into an i32
+                    None => {}
+                };
+                Settings::new()
+            }
+            Err(error) if File::present(&Disk, SETTINGS_FILE, "bin") => {
+                // Settings exist but couldn't be read, so keep a copy and rebuild from the recordings on disk
+                Tracker::write(
+                    errors.clone(),
+                    Some(if error == Error::VersionError {
+                        error
+                    } else {
+                        Error::RebuiltError
+                    }),
+                );
+                match File::backup(&Disk, SETTINGS_FILE, "bin") {
+                    Some(error) => {
+                        Tracker::write(errors.clone(), Some(error));
                     }
-                }
-                Err(_) => {
-                    // If parse fails
-                    // Do the same thing as if it was successful but with the opposite strings
-                    adding_text = true;
-                    text.push(char);
-                    if adding_number {
-                        adding_number = false;
-                        if number.len() > 0 {
-                            list.push(TextNum::Number(number.parse().unwrap()));
-                            number.clear();
-                        }
+                    None => {}
+                };
+                let settings = match Settings::rebuild() {
+                    Ok(value) => value,
+                    Err(error) => {
+                        Tracker::write(errors.clone(), Some(error));
+                        Settings::new()
+                    }
+                };
+                match save(DataType::Settings(settings.clone()), SETTINGS_FILE) {
+                    Some(error) => {
+                        Tracker::write(errors.clone(), Some(error));
                     }
-                    if index == input.len() - 1 {
-                        list.push(TextNum::Text(text.clone()));
+                    None => {}
+                };
+                settings
+            }
+            Err(_) => {
+                match save(DataType::Settings(Settings::new()), SETTINGS_FILE) {
+                    Some(error) => {
+                        Tracker::write(errors.clone(), Some(error));
                     }
+                    None => {}
+                };
+                Settings::new() // Creates new settings if it didn't exist already
+            }
+        },
+    ));
+
+    {
+        // Makes sure every recording has one entry and one snapshot before anything else reads them
+        let mut settings = Tracker::edit(&tracker.settings);
+        // Snapshots are put where the option says first, which also moves any left next to the recordings by older versions
+        if let Some(error) = File::arrange_snapshots(&Disk, settings.snapshot_folder) {
+            Tracker::write(errors.clone(), Some(error));
+        }
+        match settings.check_consistency(&Disk) {
+            Ok(report) if report.repaired() => {
+                Tracker::write(warnings.clone(), Some(Warning::LibraryRepaired));
+                if let Some(error) = Tracker::save_settings(&settings, tracker.saved.clone()) {
+                    Tracker::write(errors.clone(), Some(error));
                 }
             }
-            index += 1;
+            Ok(_) => (), // Orphaned snapshots are shown by the orphan scan
+            Err(error) => {
+                Tracker::write(errors.clone(), Some(error));
+            }
         }
-
-        list // Return the final list
     }
-}
 
-// Types of playback
-#[derive(PartialEq)]
-enum Playback {
-    Input(SnapShot),
-    Capture(SnapShot),
-    Generic(SnapShot),
-}
+    // Lets the threads show errors and finished playback straight away instead of waiting for the UI's timers
+    let notifier = notifier(ui.as_weak(), tracker.settings.read().unwrap().push_updates);
+    notifier.wake(); // Shows anything that went wrong while loading once the UI is running
 
-// Mpsc messages
-enum Message {
-    File(String),                 // Path
-    PlayAudio((Playback, usize)), // Type, index of current recording
-    StopAudio,
-    StartRecording,
-    StopRecording,
-}
+    // Starts the threads that record, play, click, and monitor, each is sent messages through the sender it returns
+    let record_sender = start_recorder(&tracker, &errors, &warnings, &notifier);
+    let audio_sender = start_player(&tracker, &errors, &warnings, &notifier);
+    let metronome_sender = start_metronome(&tracker, &errors, &notifier);
+    let monitor_sender = start_monitor(&tracker, &errors, &notifier);
+    start_device_watch(&tracker, &errors, &notifier);
 
-// Files
-#[derive(PartialEq)]
-enum File {
-    Names(Vec<String>),
-}
+    // Update callback
+    ui.on_update({
+        let ui_handle = ui.as_weak();
 
-impl File {
-    fn search(path: &str, extension: &str, ordered: bool) -> Result<File, Error> {
-        // Searches for files at the specified path and the same extension, and returns either a list of names or an error
-        let mut names = vec![];
-        match fs::read_dir(path) {
-            // Attemps to read the files at the specified path
-            Ok(directories) => {
-                for entry in directories {
-                    // Loop throuh every entry
-                    match entry {
-                        Ok(directory) => {
-                            let path = directory.path(); // Get the path of each entry
-
-                            if path.is_file() {
-                                // If it's a file not a folder
-                                if let Some(file_type) = path.extension() {
-                                    // Gets the extension of the file
-                                    if file_type == extension {
-                                        // Checks if it's correct
-                                        let file_name = match path.file_name() {
-                                            // Gets the file name
-                                            Some(value) => value.to_owned(),
-                                            None => OsString::from("Couldn't read name"),
-                                        };
-                                        names.push(match file_name.into_string() {
-                                            // Pushes the file name onto the list of names
-                                            Ok(mut value) => File::truncate(&mut value, ".", 0), // Truncates the extension on the name
-                                            Err(_) => String::from("Couldn't read name"),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                        Err(_) => {
-                            return Err(Error::ReadError);
-                        }
-                    }
-                }
+        let notifier = notifier.clone();
 
-                if ordered {
-                    // If true passed as the ordering value
-                    names.sort_by(|string1, string2| {
-                        // Sorts the names list using a custom rule set
-                        let compare1 =
-                            TextNum::split_text_and_numbers(string1.to_string().to_lowercase()); // Splits string into letters and whole numbers
-                        let compare2 =
-                            TextNum::split_text_and_numbers(string2.to_string().to_lowercase());
-                        // The largest bias is sorted after the smaller one
-                        let mut bias1 = 0;
-                        let mut bias2 = 0;
-
-                        for item in 0..if compare1.len() <= compare2.len() {
-                            // Loops through all the items in the smallest list
-                            compare1.len()
-                        } else {
-                            compare2.len()
-                        } {
-                            if let (TextNum::Text(_), TextNum::Number(_)) =
-                                // Checks if the first list is text and the second is a number
-                                (&compare1[item], &compare2[item])
-                            {
-                                bias1 = i32::MAX; // Sets bias1 to the maximum value for an i32
-                                break; // Skips the rest of the checks as they no longer matter
-                            } else if let (TextNum::Number(_), TextNum::Text(_)) =
-                                // Does the opposite
-                                (&compare1[item], &compare2[item])
-                            {
-                                bias2 = i32::MAX;
-                                break;
-                            } else if let (TextNum::Text(first), TextNum::Text(second)) =
-                                // Checks if they are both text
-                                (&compare1[item], &compare2[item])
-                            {
-                                let first_chars: Vec<char> = first.chars().collect(); // Converts the current vector index into its own vector
-                                let second_chars: Vec<char> = second.chars().collect();
-                                for char in 0..if first.len() <= second.len() {
-                                    // Iterates through the shorter vector
-                                    if first.len() < second.len() {
-                                        bias2 += 1; // Prioritises the longer list appearing after the shorter one
-                                    }
-                                    first.len()
-                                } else {
-                                    bias1 += 1;
-                                    second.len()
-                                } {
-                                    match first_chars[char].cmp(&second_chars[char]) {
-                                        // Compares the values in alphabetical order
-                                        Ordering::Greater => {
-                                            bias1 += 1; // Prioritises the later characters in the alphabet appearing after the earlier ones
-                                        }
-                                        Ordering::Equal => {}
-                                        Ordering::Less => {
-                                            bias2 += 1;
-                                        }
-                                    }
-                                }
-                            } else if let (TextNum::Number(first), TextNum::Number(second)) =
-                                // If both are numbers
-                                (&compare1[item], &compare2[item])
-                            {
-                                match first.cmp(&second) {
-                                    // Compare the numbers
-                                    Ordering::Greater => {
-                                        bias1 += 1; // Prioritise the greater number appearing last
-                                    }
-                                    Ordering::Equal => {}
-                                    Ordering::Less => {
-                                        bias2 += 1;
-                                    }
-                                }
-                            }
-                        }
+        let startup_ref_count = tracker.settings.clone();
 
-                        if bias1 > bias2 {
-                            Ordering::Greater
-                        } else if bias1 < bias2 {
-                            Ordering::Less
-                        } else {
-                            Ordering::Equal
-                        }
-                    });
+        let monitor_recording = tracker.monitor_recording.clone();
+
+        let error_handle = errors.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let occured = error_handle.read().unwrap().clone(); // Copied out so the lock is free to clear it
+            match occured {
+                // Checks for errors
+                Some(error) => {
+                    error.send(&ui);
+                    Tracker::write(error_handle.clone(), None);
                 }
-                Ok(File::Names(names)) // Return the list of names
-            }
-            Err(_) => Err(Error::ReadError), // Return an error if an error is encountered
-        }
-    }
+                None => {}
+            };
 
-    fn truncate(name: &mut String, stop_char: &str, pass: u32) -> String {
-        // Truncates strings to the designated stop character
-        let copy = name.clone();
-        let mut length = name.len() - 1;
-        let mut found = 0;
-        loop {
-            if name.ends_with(stop_char) {
-                // Checks if the last character is the same as the stop character
-                name.remove(length); // Remove it
-                length -= 1;
-                if found == pass {
-                    // Checks if it's passed enough stop characters
-                    break;
-                }
-                found += 1;
-            } else {
-                if length == 1 {
-                    // Returns the original input if no or not enough stop characters found
-                    return copy;
+            if ui.get_started() {
+                // Syncs settings data on initial load
+                // Acquires write access to the loaded data
+                let mut settings = notifier.edit(&startup_ref_count);
+                settings.sync(&Disk, &ui);
+                settings.send_options(&ui);
+                if let Ok(path) = File::get_directory() {
+                    ui.set_space_summary(space_summary(free_space(&path)));
                 }
-                name.remove(length);
-                length -= 1;
+                ui.set_noise_profile_source(NoiseProfile::summary());
             }
-        }
 
-        name.to_string() // Returns the truncated string
-    }
+            // Aquires read access to the loaded data
+            let settings = startup_ref_count.read().unwrap();
 
-    fn rename(old: &String, name: String) -> Option<Error> {
-        // Renames the inputted file or returns an error
-        let path = match File::get_directory() {
-            // Gets current path
-            Ok(value) => value,
-            Err(error) => return Some(error),
-        };
-        match rename(
-            // Attempts to rename the file
-            format!("{}/{}.wav", path, old),
-            format!("{}/{}.wav", path, name),
-        ) {
-            Ok(_) => (),
-            Err(_) => {
-                return Some(Error::RenameError); // Return an error if unsuccessful
-            }
-        };
+            let index_data = settings.get_index_data();
 
-        match rename(
-            format!("{}/{}.bin", path, old),
-            format!("{}/{}.bin", path, name),
-        ) {
-            Ok(_) => (),
-            Err(_) => {
-                return Some(Error::RenameError);
-            }
-        };
+            // Sends a list of preset names to the ui to be displayed
+            ui.set_preset_names(Preset::send_names(
+                &settings.presets,
+                &index_data.preset_length,
+            ));
 
-        None // Return nothing if no error
-    }
+            // Sends a nested list of preset values to the ui to be displayed
+            ui.set_preset_values(Preset::send_values(
+                &settings.presets,
+                &index_data.preset_length,
+            ));
+            ui.set_preset_filter_kinds(Preset::send_filter_kinds(&settings.presets));
+            settings.send_default_preset(&ui); // Presets may have been added, renamed or deleted
 
-    fn delete(name: String) -> Option<Error> {
-        // Attempts to delete the inputted file or returns an error
-        let path = match File::get_directory() {
-            Ok(value) => value,
-            Err(error) => return Some(error),
-        };
-        match remove_file(format!("{}/{}.wav", path, name)) {
-            Ok(_) => (),
-            Err(_) => {
-                return Some(Error::DeleteError);
-            }
-        };
-        match remove_file(format!("{}/{}.bin", path, name)) {
-            Ok(_) => None,
-            Err(_) => None,
-        }
-    }
+            // Sends recording names to the ui to be displayed
+            ui.set_recording_names(Recording::send_names(&settings.recordings));
+            ui.set_recording_dropouts_flags(Recording::send_dropouts(&settings.recordings));
+            ui.set_recording_colours(Recording::send_colours(&settings.recordings));
+            ui.set_unconverted_summary(settings.unconverted_summary()); // Sync may have found recordings to convert
+            ui.set_recording_hidden(Recording::send_hidden(
+                &settings.recordings,
+                &ui.get_recording_search(),
+            ));
 
-    fn exists(new: String, old_list: &Vec<Recording>) -> bool {
-        // Checks if a name already exists in the current save
-        let mut check = false;
-        for item in 0..old_list.len() {
-            // Loops through the name sin a list
-            if new == old_list[item].name {
-                // If it exists return true
-                check = true;
-                break;
+            // Keeps the monitor EQ in line with the current recording
+            if let Some(recording) = settings.recordings.get(ui.get_current_recording() as usize) {
+                Tracker::write(monitor_recording.clone(), recording.clone());
             }
-        }
 
-        check
-    }
+            ui.set_queue_summary(settings.queue_summary()); // Renames and deletions can change how the queue reads
 
-    fn get_directory() -> Result<String, Error> {
-        // Gets the working directory
-        let mut error = None;
-        let mut string = String::new();
-        match env::current_exe() {
-            // Gets the path that the executable is saved at
-            Ok(value) => {
-                let mut name = match value.into_os_string().into_string() {
-                    // Converts the value into something easier to work with
-                    Ok(value) => value,
-                    Err(_) => {
-                        error = Some(Error::DirectoryError); // Returns an error if unsuccessful
-                        string
-                    }
-                };
-                string = File::truncate(&mut name, "/", 2); // Truncates 2 file paths to get the working root
-            }
-            Err(_) => {
-                error = Some(Error::DirectoryError);
+            // Sends recording values to the ui to be displayed
+            if !ui.get_locked() {
+                ui.set_recording_values(Recording::send_values(
+                    &settings.recordings,
+                    &index_data.recording_length,
+                ));
             }
-        };
 
-        match error {
-            // If an error occured at some point in the process, return an error otherwise the file path
-            Some(value) => Err(value),
-            None => Ok(string),
+            if ui.get_current_recording() < settings.recordings.len() as i32 {
+                // Sets dial values to current recording data
+                if settings.recordings.len() > 0 {
+                    ui.set_current_dial_values(ModelRc::new(VecModel::from(
+                        settings.recordings[ui.get_current_recording() as usize]
+                            .parse_vec_from_recording(),
+                    )));
+                    settings.recordings[ui.get_current_recording() as usize].send_current(&ui);
+                    match settings.recordings[ui.get_current_recording() as usize].playback_override
+                    {
+                        Some(mode) => {
+                            ui.set_playback_override_set(true);
+                            ui.set_playback_override(mode.to_ui());
+                        }
+                        None => ui.set_playback_override_set(false),
+                    }
+                }
+            }
         }
-    }
-}
-
-// Types of data that the app works with
-enum DataType {
-    Settings(Settings),
-    SnapShot(SnapShot),
-}
+    });
 
-// Types of data that the app can load
-enum LoadType {
-    Settings,
-    Snapshot,
-}
+    // Updates locked values
+    ui.on_update_locked_values({
+        let ui_handle = ui.as_weak();
 
-// -------- Structs --------
-// Index data for Settings struct
-struct IndexData {
-    preset_length: usize,
-    recording_length: usize,
-}
+        let settings_handle = tracker.settings.clone();
 
-// Recorded input data
-#[derive(Savefile, Clone, PartialEq)]
-struct SnapShot {
-    frames: Vec<([i32; 6], i32)>, // Dial values, frame
-}
+        let locked_handle = tracker.locked.clone();
 
-impl SnapShot {
-    fn create(name: &str) -> Option<Error> {
-        // Saves an empty snapshot to disk or returns an error
-        match SnapShot::new().save(name) {
-            Some(error) => {
-                return Some(error);
-            }
-            None => {}
-        };
+        move || {
+            let ui = ui_handle.unwrap();
 
-        None
-    }
+            let settings = settings_handle.read().unwrap();
 
-    fn new() -> SnapShot {
-        // New snapshot in memory
-        SnapShot {
-            frames: vec![([0, 0, 0, 0, 0, 0], 0)],
-        }
-    }
+            let mut locked = locked_handle.write().unwrap();
 
-    fn edited(previous: [i32; 6], next: [i32; 6]) -> bool {
-        // Checks if the dial values have changed
-        for number in 0..6 {
-            if previous[number] == next[number] {
-                continue;
-            } else {
-                return true;
+            if settings.recordings.len() > 0 {
+                // Sets locked vales to current recording data
+                ui.set_dial_values_when_locked(Recording::send_values(
+                    &settings.recordings,
+                    &settings.get_index_data().recording_length,
+                ));
+                // Sets tracker locked values
+                *locked = settings.recordings[ui.get_current_recording() as usize].clone();
             }
         }
+    });
 
-        false
-    }
-
-    fn save(self, name: &str) -> Option<Error> {
-        // Saves a snapshot to disk that doesn't have to be empty - Used when a snapshot already exists
-        save(DataType::SnapShot(self), name)
-    }
-}
-
-// Preset data
-#[derive(Savefile, Clone)]
-struct Preset {
-    name: String,
-    sub_bass: i32,
-    bass: i32,
-    low_mids: i32,
-    high_mids: i32,
-    treble: i32,
-    pan: i32,
-}
-
-impl Preset {
-    fn from(values: [i32; 6]) -> Preset {
-        // Creates a preset from dial values
-        Preset {
-            name: String::from("New Preset"),
-            sub_bass: values[0],
-            bass: values[1],
-            low_mids: values[2],
-            high_mids: values[3],
-            treble: values[4],
-            pan: values[5],
-        }
-    }
+    // Syncs UI and settings with current locked values
+    ui.on_sync_with_locked_values({
+        let ui_handle = ui.as_weak();
 
-    fn send_names(list: &Vec<Preset>, length: &usize) -> ModelRc<SharedString> {
-        // Sends preset names to UI
-        let mut preset_names = vec![];
-        for preset in 0..*length {
-            preset_names.push(list[preset].name.to_shared_string());
-        }
+        let notifier = notifier.clone();
 
-        // ModelRc is the type of list that the UI uses
-        ModelRc::new(VecModel::from(preset_names)) // Creates new ModelRc from the names list
-    }
+        let settings_handle = tracker.settings.clone();
 
-    fn send_values(list: &Vec<Preset>, length: &usize) -> ModelRc<ModelRc<i32>> {
-        // Sends preset dial values to the UI
-        let mut all_preset_values = vec![];
-        for values in 0..*length {
-            let mut preset_values = vec![];
+        let locked_handle = tracker.locked.clone();
 
-            preset_values.push(list[values].sub_bass);
-            preset_values.push(list[values].bass);
-            preset_values.push(list[values].low_mids);
-            preset_values.push(list[values].high_mids);
-            preset_values.push(list[values].treble);
-            preset_values.push(list[values].pan);
+        move || {
+            let ui = ui_handle.unwrap();
 
-            all_preset_values.push(ModelRc::new(VecModel::from(preset_values)));
-        }
-        ModelRc::new(VecModel::from(all_preset_values))
-    }
-}
+            let mut settings = notifier.edit(&settings_handle);
 
-// Recording data
-#[derive(Savefile, Clone)]
-struct Recording {
-    name: String,
-    sub_bass: i32,
-    bass: i32,
-    low_mids: i32,
-    high_mids: i32,
-    treble: i32,
-    pan: i32,
-}
+            let locked = locked_handle.read().unwrap();
 
-impl Recording {
-    fn new(name: &String) -> Recording {
-        // Creates a new recording
-        Recording {
-            name: name.to_string(),
-            sub_bass: 0,
-            bass: 0,
-            low_mids: 0,
-            high_mids: 0,
-            treble: 0,
-            pan: 0,
-        }
-    }
+            // Sets settings data to locked values
+            settings.recordings[ui.get_current_recording() as usize].sub_bass = locked.sub_bass;
+            settings.recordings[ui.get_current_recording() as usize].bass = locked.bass;
+            settings.recordings[ui.get_current_recording() as usize].low_mids = locked.low_mids;
+            settings.recordings[ui.get_current_recording() as usize].high_mids = locked.high_mids;
+            settings.recordings[ui.get_current_recording() as usize].treble = locked.treble;
+            settings.recordings[ui.get_current_recording() as usize].pan = locked.pan;
+            settings.recordings[ui.get_current_recording() as usize].filter_kinds =
+                locked.filter_kinds;
+            settings.recordings[ui.get_current_recording() as usize].high_pass = locked.high_pass;
+            settings.recordings[ui.get_current_recording() as usize].low_pass = locked.low_pass;
+            settings.recordings[ui.get_current_recording() as usize].invert_left =
+                locked.invert_left;
+            settings.recordings[ui.get_current_recording() as usize].invert_right =
+                locked.invert_right;
+            settings.recordings[ui.get_current_recording() as usize].auto_makeup =
+                locked.auto_makeup;
 
-    fn from(name: &String, values: [i32; 6]) -> Recording {
-        // Creates a new recording from a name and dial values
-        Recording {
-            name: name.to_string(),
-            sub_bass: values[0],
-            bass: values[1],
-            low_mids: values[2],
-            high_mids: values[3],
-            treble: values[4],
-            pan: values[5],
+            // Sets dials to locked values
+            if settings.recordings.len() > 0 {
+                ui.set_current_dial_values(ModelRc::new(VecModel::from(
+                    settings.recordings[ui.get_current_recording() as usize]
+                        .parse_vec_from_recording(),
+                )));
+                settings.recordings[ui.get_current_recording() as usize].send_current(&ui);
+            }
         }
-    }
-
-    fn parse(&self) -> [i32; 6] {
-        // Parses recording data into dial values
-        let mut list: [i32; 6] = [0, 0, 0, 0, 0, 0];
-
-        list[0] = self.sub_bass;
-        list[1] = self.bass;
-        list[2] = self.low_mids;
-        list[3] = self.high_mids;
-        list[4] = self.treble;
-        list[5] = self.pan;
-
-        list
-    }
-
-    fn parse_vec_from_recording(&self) -> Vec<i32> {
-        // Parses recording data into a vector
-        let mut list = vec![];
-
-        list.push(self.sub_bass);
-        list.push(self.bass);
-        list.push(self.low_mids);
-        list.push(self.high_mids);
-        list.push(self.treble);
-        list.push(self.pan);
+    });
 
-        list
-    }
+    // Saves settings to file and memory
+    ui.on_save({
+        let ui_handle = ui.as_weak();
 
-    fn parse_vec_from_list(list: [i32; 6]) -> Vec<i32> {
-        // Parses a vector from dial values
-        let mut new = vec![];
+        let notifier = notifier.clone();
 
-        new.push(list[0]);
-        new.push(list[1]);
-        new.push(list[2]);
-        new.push(list[3]);
-        new.push(list[4]);
-        new.push(list[5]);
+        let saved_handle = tracker.saved.clone();
 
-        new
-    }
+        let update_ref_count = tracker.settings.clone();
 
-    fn send_names(list: &Vec<Recording>) -> ModelRc<SharedString> {
-        // Sends recording names to UI
-        let mut new_list = vec![];
+        let empty = tracker.empty_recording.clone();
 
-        for recording in 0..list.len() {
-            new_list.push(list[recording].name.to_shared_string());
-        }
+        let just_recorded = tracker.recording_check.clone();
 
-        ModelRc::new(VecModel::from(new_list))
-    }
+        let save_requested = tracker.save_requested.clone();
 
-    fn send_values(list: &Vec<Recording>, length: &usize) -> ModelRc<ModelRc<i32>> {
-        // Sends recording dial values to UI
-        let mut all_recording_values = vec![];
-        for values in 0..*length {
-            let mut recording_values = vec![];
+        let dropped_recording = tracker.dropped_recording.clone();
 
-            recording_values.push(list[values].sub_bass);
-            recording_values.push(list[values].bass);
-            recording_values.push(list[values].low_mids);
-            recording_values.push(list[values].high_mids);
-            recording_values.push(list[values].treble);
-            recording_values.push(list[values].pan);
+        move || {
+            let ui = ui_handle.unwrap();
 
-            all_recording_values.push(ModelRc::new(VecModel::from(recording_values)));
-        }
-        ModelRc::new(VecModel::from(all_recording_values))
-    }
+            // Skips if an empty recording was just created
+            if Tracker::read(empty.clone()) && Tracker::read(just_recorded.clone()) {
+                Tracker::write(just_recorded.clone(), false);
+                return;
+            }
 
-    fn rename(
-        // Renames recordings
-        old: &Vec<Recording>,
-        new: ModelRc<SharedString>,
-    ) -> Result<Vec<Recording>, (Vec<Recording>, Error)> {
-        // Returns either a vector of the new names or if there was an error, a vector of new and old names plus an error value
-        let mut recording_list = vec![];
-
-        // Checks for different kinds of errors
-        let mut fallback_error_occured = false;
-        let mut empty_error_occured = false;
-        let mut exists_error_occured = false;
-        let mut save_file_rename_error_occured = false;
-        let mut rename_failed = (false, None); // Occured, Error type
-
-        for name in 0..old.len() {
-            // Loops through all the old names
-            if new.row_data(name).unwrap() != old[name].name {
-                // Checks if the new name doesn't equal the old name
-                if new
-                    .row_data(name)
-                    .unwrap()
-                    .contains(&String::from("Default taken..."))
-                // Checks if the new name contains the fallback name
-                {
-                    recording_list.push(Recording::from(&old[name].name, old[name].parse())); // Pushes the old name to the list of names
-                    fallback_error_occured = true;
-                    break;
-                } else if new.row_data(name).unwrap() == String::from("settings") {
-                    // Checks if the new name is 'settings'
-                    recording_list.push(Recording::from(&old[name].name, old[name].parse()));
-                    save_file_rename_error_occured = true;
-                    break;
-                } else if new.row_data(name).unwrap().is_empty()
-                    || new.row_data(name).unwrap() == String::from("")
-                // Checks if the new name doesn't exist or equals ''
-                {
-                    recording_list.push(Recording::from(&old[name].name, old[name].parse()));
-                    empty_error_occured = true;
-                    break;
-                } else if File::exists(String::from(new.row_data(name).unwrap()), &old) {
-                    // Checks if the new name already exists
-                    recording_list.push(Recording::from(&old[name].name, old[name].parse()));
-                    exists_error_occured = true;
-                    break;
-                } else {
-                    match File::rename(&old[name].name, String::from(new.row_data(name).unwrap())) {
-                        // Renames file if all the checks pass
-                        Some(error) => {
-                            rename_failed = (true, Some(error));
+            // This block is used to drop the write lock on the stored data as soon as the last write is completed
+            // This frees it to be used in the function called underneath and in any threads where it is needed
+            {
+                // Acquires write access to the loaded data
+                let mut settings = notifier.edit(&update_ref_count);
+                settings.sync(&Disk, &ui); // Syncs settings data
+
+                // Flags a new recording that lost audio, waiting for a later save if the recorder hasn't finished yet
+                let mut dropped = dropped_recording.write().unwrap();
+                if let Some(name) = dropped.clone() {
+                    for recording in settings.recordings.iter_mut() {
+                        if recording.name == name {
+                            recording.had_dropouts = true;
+                            *dropped = None;
                         }
-                        None => {}
                     }
-                    recording_list.push(Recording::from(
-                        &String::from(new.row_data(name).unwrap()),
-                        old[name].parse(),
-                    )); // Pushes new name to list
                 }
-            } else {
-                recording_list.push(Recording::from(&old[name].name, old[name].parse()));
-                // Skips recordings that were unchanged
             }
-        }
-
-        if exists_error_occured {
-            // Checks if any errors occured and returns them and a list or just a list
-            Err((recording_list, Error::ExistsError))
-        } else if empty_error_occured {
-            Err((recording_list, Error::EmptyError))
-        } else if fallback_error_occured {
-            Err((recording_list, Error::FallbackError))
-        } else if save_file_rename_error_occured {
-            Err((recording_list, Error::SaveFileRenameError))
-        } else if rename_failed.0 {
-            Err((recording_list, rename_failed.1.unwrap()))
-        } else {
-            Ok(recording_list)
-        }
-    }
 
-    fn shuffle(length: usize) -> Vec<i32> {
-        // Shuffles recordings
-        let mut new = vec![];
-        let mut avaliable = vec![];
+            ui.invoke_update(); // Updates UI
 
-        for number in 0..length {
-            // Creates a list of numbers 0 to list length -1
-            avaliable.push(number);
-        }
+            if ui.get_dials_edited() {
+                // Dial drags fire a save for every step so only write to disk once they go quiet
+                Tracker::write(save_requested.clone(), Some(Instant::now()));
+                ui.set_save_pending(true);
+                return;
+            }
 
-        for _ in 0..length {
-            let random = random_range(0..avaliable.len()); // Creates a random number between 0 and the length of the avaliable numbers list
-            new.push(avaliable[random] as i32); // Pushes the value at the index to the shuffle list
-            avaliable.remove(random); // Removes the used number from the avaliable list
+            // Aquires read access to the loaded data
+            let settings = update_ref_count.read().unwrap();
+            // Save data if not locked or recording inputs
+            if !ui.get_locked() && !ui.get_input_recording() {
+                Tracker::write(save_requested.clone(), None); // This save includes any pending dial edits
+                ui.set_save_pending(false);
+                match Tracker::save_settings(&settings, saved_handle.clone()) {
+                    Some(error) => {
+                        error.send(&ui);
+                    }
+                    None => {}
+                }
+            }
         }
+    });
 
-        new
-    }
-}
+    // Writes debounced dial edits to disk once the dials have stopped moving
+    ui.on_flush_save({
+        let ui_handle = ui.as_weak();
 
-// All settings data
-#[derive(Savefile, Clone)]
-struct Settings {
-    presets: Vec<Preset>,
-    recordings: Vec<Recording>,
-}
+        let saved_handle = tracker.saved.clone();
 
-impl Settings {
-    fn new() -> Settings {
-        // Creates empty settings data
-        Settings {
-            presets: vec![],
-            recordings: vec![],
-        }
-    }
+        let settings_handle = tracker.settings.clone();
 
-    fn get_index_data(&self) -> IndexData {
-        // Gets the length of each list in the settings struct
-        IndexData {
-            preset_length: self.presets.len(),
-            recording_length: self.recordings.len(),
-        }
-    }
+        let save_requested = tracker.save_requested.clone();
 
-    fn sync(&mut self, ui: &AppWindow) {
-        // Sync settings data with files and UI
-        let index_data = self.get_index_data();
+        move || {
+            let ui = ui_handle.unwrap();
 
-        let mut dials = [0, 0, 0, 0, 0, 0];
-        for index in 0..6 {
-            // Gets dial values from UI
-            match ui.get_current_dial_values().row_data(index) {
-                Some(value) => dials[index] = value,
+            match Tracker::read(save_requested.clone()) {
+                Some(requested) => {
+                    if requested.elapsed() < SAVE_DEBOUNCE {
+                        return; // Dials are still moving
+                    }
+                }
                 None => {
-                    dials = [0, 0, 0, 0, 0, 0];
-                    break;
+                    ui.set_save_pending(false);
+                    return;
                 }
-            };
-        }
-
-        // Check for new preset creation
-        if ui.get_new_preset_created() {
-            self.presets.push(Preset::from(dials)); // Update the settings data with the new preset created from the values of the dials
-        }
-
-        // Check for preset deletion
-        if ui.get_preset_deleted() {
-            if self.presets.len() > ui.get_deleted_preset_index() as usize {
-                self.presets.remove(ui.get_deleted_preset_index() as usize); // Deletes deleted preset from settings data
-                ui.set_can_delete(true); // Tells the UI that the item has finished being deleted to enable more things to be deleted
             }
-        }
 
-        // Check for preset rename
-        if ui.get_preset_renamed() {
-            for preset in 0..index_data.preset_length {
-                self.presets[preset].name =
-                    String::from(match ui.get_preset_names().row_data(preset) {
-                        // Renames preset with the value in the UI
-                        Some(name) => name,
-                        None => SharedString::from("New Preset"), // Sets to default value if something went wrong retrieving the new name form the UI
-                    });
+            // Skip while locked or recording inputs like a normal save, the edit stays pending until then
+            if ui.get_locked() || ui.get_input_recording() {
+                return;
             }
-        }
 
-        // Check for recording edits
-        if index_data.recording_length > 0 {
-            let position = ui.get_current_recording() as usize;
-            if ui.get_dials_edited() {
-                self.recordings[position] = Recording::from(&self.recordings[position].name, dials);
-                // Updates settings data with edited values
+            Tracker::write(save_requested.clone(), None);
+            ui.set_save_pending(false);
+
+            let settings = settings_handle.read().unwrap().clone();
+            match Tracker::save_settings(&settings, saved_handle.clone()) {
+                Some(error) => {
+                    error.send(&ui);
+                }
+                None => ui.invoke_sync_unsaved(), // Nothing was edited so there's no wake to clear the unsaved notice
             }
         }
+    });
 
-        // Check for recording deletion
-        if ui.get_recording_deleted() {
-            self.recordings
-                .remove(ui.get_deleted_recording_index() as usize); // Removes recording data from settings
-            ui.set_can_delete(true);
-        }
+    // Starts and stops recordings
+    ui.on_record({
+        let ui_handle = ui.as_weak();
 
-        // Check for recording renaming
-        if ui.get_recording_renamed() {
-            self.recordings = match Recording::rename(&self.recordings, ui.get_recording_names()) {
-                // Renames recording
-                Ok(value) => value,
-                Err(error) => {
-                    error.1.send(ui); // Sends error value to UI
-                    error.0
-                }
-            };
-        }
+        let notifier = notifier.clone();
 
-        // Sync recording data with any changes that might have been made to the application files
-        let path = match File::get_directory() {
-            Ok(value) => value,
-            Err(error) => {
-                error.send(ui);
-                String::new()
-            }
-        };
-        let file_names = match File::search(&path, "wav", true) {
-            // Gets wav file names
-            Ok(File::Names(value)) => value,
-            Err(error) => {
-                error.send(ui);
-                vec![String::from("Couldn't read files")]
-            }
-        };
+        let sender_handle = record_sender.clone();
 
-        let mut snapshot_names = match File::search(&path, "bin", true) {
-            // Gets binary file names
-            Ok(File::Names(value)) => value,
-            Err(error) => {
-                error.send(ui);
-                vec![String::from("Couldn't read files")]
-            }
-        };
+        let audio_sender_handle = audio_sender.clone();
 
-        for name in 0..snapshot_names.len() {
-            if snapshot_names[name] == "settings" {
-                snapshot_names.remove(name); // Removes the settings file from the list of binary files
-                break;
-            }
-        }
+        let metronome_handle = metronome_sender.clone();
 
-        let mut updated_recordings = vec![];
+        let metronome_running = tracker.metronome_running.clone();
 
-        if file_names.len() > 0 {
-            for name in 0..file_names.len() {
-                // Loops over all the names
-                if self.recordings.len() > 0 {
-                    for recording in 0..self.recordings.len() {
-                        if self.recordings[recording].name == file_names[name] {
-                            // If the recording is known, then add the old recording to the list
-                            updated_recordings.push(Recording::from(
-                                &file_names[name],
-                                Recording::parse(&self.recordings[recording]),
-                            ));
-                            break;
-                        }
-                        if recording == self.recordings.len() - 1 {
-                            updated_recordings.push(Recording::new(&file_names[name]));
-                            // If it's unknown then create a new recording
-                        }
+        let monitor_handle = monitor_sender.clone();
+
+        let monitor_running = tracker.monitor_running.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let error_handle = errors.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            // Starts and stops the metronome and monitor in lockstep with the recording
+            let (mut metronome, mut monitor, device) = {
+                let settings = settings_handle.read().unwrap();
+                (
+                    settings.metronome,
+                    settings.monitor_input,
+                    settings.output_device.clone(),
+                )
+            };
+            if (metronome || monitor) && !ui.get_recording() && captured_output(&device) {
+                // The click would be recorded along with everything else the output plays
+                // and the monitor would play the input back into itself, echoing louder each time round
+                if monitor {
+                    monitor = false;
+                    Warning::MonitorCaptured.send(&ui);
+                }
+                if metronome {
+                    metronome = false;
+                    Warning::ClickCaptured.send(&ui);
+                }
+            }
+            for error in [
+                follow_recording(
+                    &metronome_handle,
+                    metronome_running.clone(),
+                    ui.get_recording(),
+                    metronome,
+                ),
+                follow_recording(
+                    &monitor_handle,
+                    monitor_running.clone(),
+                    ui.get_recording(),
+                    monitor,
+                ),
+            ] {
+                if let Some(error) = error {
+                    notifier.write(error_handle.clone(), Some(error));
+                }
+            }
+
+            match sender_handle.send(if ui.get_recording() {
+                // Sends message to recording thread
+                // Sends stop message and updates UI
+                ui.set_app_state(ui.get_app_state().with_recording(false));
+                Message::StopRecording
+            } else {
+                // Sends start message and updates UI
+                ui.set_app_state(ui.get_app_state().with_recording(true));
+                if ui.get_audio_playback() {
+                    Warning::PlaybackCaptured.send(&ui); // Recording the system output will pick up the playback
+                }
+                Message::StartRecording
+            }) {
+                Ok(_) => (),
+                Err(_) => {
+                    notifier.write(error_handle.clone(), Some(Error::MessageError));
+                }
+            }
+            if !ui.get_recording() {
+                // Remembers what's playing as the new recording can change its position in the list
+                let playing = if ui.get_audio_playback() {
+                    let settings = settings_handle.read().unwrap();
+                    match settings.recordings.get(ui.get_current_recording() as usize) {
+                        Some(recording) => Some(recording.name.clone()),
+                        None => None,
                     }
                 } else {
-                    updated_recordings.push(Recording::new(&file_names[name])); // Adds new recording to settings data
-                }
-
-                // Syncs snapshots
-                if snapshot_names.len() > 0 {
-                    for file in 0..snapshot_names.len() {
-                        if snapshot_names.len() > 0 {
-                            if file_names[name] != snapshot_names[file] {
-                                // If the names of the files and snapshots don't match then create a new snapshot file
-                                match SnapShot::create(&file_names[name]) {
-                                    Some(error) => {
-                                        error.send(ui);
-                                    }
-                                    None => (),
+                    None
+                };
+
+                // If UI not recording then save and shuffle songs
+                ui.invoke_save();
+                ui.invoke_gen_shuffle();
+
+                if let Some(name) = playing {
+                    // Points the UI and the player at the recording that's still playing
+                    let settings = settings_handle.read().unwrap();
+                    for index in 0..settings.recordings.len() {
+                        if settings.recordings[index].name == name {
+                            ui.set_current_recording(index as i32);
+                            let snapshot_data = match load(&name, LoadType::Snapshot) {
+                                Ok(DataType::SnapShot(data)) => data,
+                                _ => SnapShot::new(),
+                            };
+                            match audio_sender_handle.send(Message::PlayAudio((
+                                Playback::Generic(snapshot_data),
+                                index,
+                            ))) {
+                                Ok(_) => (),
+                                Err(_) => {
+                                    notifier.write(error_handle.clone(), Some(Error::MessageError));
                                 }
-                            } else {
-                                snapshot_names.remove(file); // Remove snapshot name from list so that the next check doesn't autoatically fail
-                                break;
                             }
+                            break;
                         }
                     }
-                } else {
-                    match SnapShot::create(&file_names[name]) {
-                        // Creates a new snapshot if there's a file but no snapshots
-                        Some(error) => {
-                            error.send(ui);
-                        }
-                        None => (),
-                    }
                 }
             }
         }
+    });
 
-        self.recordings = updated_recordings; // Updates the settings data with the updated data
-    }
-}
+    // Deletes recordings
+    ui.on_delete_recordings({
+        let ui_handle = ui.as_weak();
 
-// Keeps track of the settings, the recording thread, whether recordings are being played, and the values of the dials during a set of audio frames
-struct Tracker {
-    settings: Arc<RwLock<Settings>>,
-    locked: Arc<RwLock<Recording>>, // Values to hold while locked
-    playing: Arc<RwLock<bool>>,     // Something is playing
-    snapshot_frame_values: Arc<RwLock<[i32; 6]>>, // Values of the currently active snapshot frame group
-    empty_recording: Arc<RwLock<bool>>,           // Whether the newest reecording is empty
-    recording_check: Arc<RwLock<bool>>, // Whether a recording is in progress or just happened
-    preloaded: Arc<RwLock<bool>>,       // Whether any audio data is loaded in memory
-}
+        let notifier = notifier.clone();
 
-impl Tracker {
-    fn new(settings: Settings) -> Tracker {
-        // Creates a new tracker
-        Tracker {
-            settings: Arc::new(RwLock::new(settings)),
-            locked: Arc::new(RwLock::new(Recording::new(&String::new()))),
-            playing: Arc::new(RwLock::new(false)),
-            snapshot_frame_values: Arc::new(RwLock::new([0, 0, 0, 0, 0, 0])),
-            empty_recording: Arc::new(RwLock::new(true)),
-            recording_check: Arc::new(RwLock::new(false)),
-            preloaded: Arc::new(RwLock::new(false)),
-        }
-    }
+        let settings_handle = tracker.settings.clone();
 
-    fn write<T>(handle: Arc<RwLock<T>>, set: T) {
-        // Wrtes data to tracked data
-        let mut writer = handle.write().unwrap();
-        *writer = set;
-    }
+        let sender_handle = audio_sender.clone();
 
-    fn read<T: Copy>(handle: Arc<RwLock<T>>) -> T {
-        // Reads and returns tracked data
-        let reader = handle.read().unwrap();
-        *reader
-    }
-}
+        let error_handle = errors.clone();
 
-// -------- Functions --------
-fn save(data: DataType, file: &str) -> Option<Error> {
-    // Save data to files
-    let path = match File::get_directory() {
-        Ok(value) => value,
-        Err(error) => return Some(error),
-    };
-    match data {
-        // Checks if saving settings data or snapshot data
-        DataType::Settings(value) => match save_file(format!("{}/{}.bin", path, file), 0, &value) {
-            // Saves settings daat
-            Ok(_) => {
-                return None;
-            }
-            Err(_) => {
-                return Some(Error::SaveError);
-            }
-        },
-        DataType::SnapShot(value) => match save_file(format!("{}/{}.bin", path, file), 0, &value) {
-            // Saves snapshot data
-            Ok(_) => {
-                return None;
-            }
-            Err(_) => match save_file(format!("{}.bin", file), 0, &value) {
-                // Tries again but without the path variable incase file was inputted as a path
-                Ok(_) => None,
-                Err(_) => Some(Error::SaveError),
-            },
-        },
-    }
-}
+        move || {
+            let ui = ui_handle.unwrap();
 
-fn load(file: &str, kind: LoadType) -> Result<DataType, Error> {
-    // Loads data from file
-    let path = match File::get_directory() {
-        Ok(value) => value,
-        Err(error) => return Err(error),
-    };
-    match kind {
-        // Checks to see what kind of data it should be loading
-        LoadType::Settings => match load_file(format!("{}/{}.bin", path, file), 0) {
-            // Loads settings data
-            Ok(value) => {
-                return Ok(DataType::Settings(value));
-            }
-            Err(_) => {
-                return Err(Error::LoadError);
-            }
-        },
-        LoadType::Snapshot => match load_file(format!("{}/{}.bin", path, file), 0) {
-            // Loads snapshot data
-            Ok(value) => {
-                return Ok(DataType::SnapShot(value));
-            }
-            Err(_) => {
-                return Err(Error::LoadError);
+            let name = String::from(ui.get_deleted_recording_name());
+
+            // Stops playback first so the player never reads past the end of the shorter list
+            if ui.get_audio_or_input_playback() {
+                let capturing = ui.get_input_recording();
+                let (reply, saved) = mpsc::channel();
+                if let Err(error) = sender_handle.send(Message::StopAndReply(reply)) {
+                    notifier.write(
+                        error_handle.clone(),
+                        Some(Error::PlaybackError(error.to_string())),
+                    );
+                }
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                if capturing {
+                    // The player saves captured inputs when it stops, so the files are only deleted once it says it's done
+                    // or they'd be written again straight after
+                    ui.set_locked(false);
+                    if saved.recv_timeout(STOP_REPLY_LIMIT).is_err() {
+                        Error::StopTimeoutError.send(&ui);
+                        return;
+                    }
+                }
             }
-        },
-    }
-}
-
-fn main() -> Result<(), Box<dyn STDError>> {
-    let ui = AppWindow::new()?;
 
-    let errors = Arc::new(RwLock::new(None)); // Creates error handler
+            let removed = settings_handle
+                .read()
+                .unwrap()
+                .recordings
+                .iter()
+                .position(|recording| recording.name == name);
 
-    // Creates a variable that can be used across threads and move blocks and can be read from without locking
-    let tracker = Arc::new(Tracker::new(match load("settings", LoadType::Settings) {
-        Ok(DataType::Settings(value)) => value, // Loads settings
-        Ok(DataType::SnapShot(_)) => {
-            // If passed snapshot data then create new settings and save the file
-            Tracker::write(errors.clone(), Some(Error::LoadError));
-            match save(DataType::Settings(Settings::new()), "settings") {
-                Some(error) => {
-                    Tracker::write(errors.clone(), Some(error));
-                }
-                None => {}
-            };
-            Settings::new()
-        }
-        Err(_) => {
-            match save(DataType::Settings(Settings::new()), "settings") {
+            match File::delete(&Disk, name) {
+                // Deletes recordings
                 Some(error) => {
-                    Tracker::write(errors.clone(), Some(error));
+                    error.send(&ui);
                 }
                 None => {}
             };
-            Settings::new() // Creates new settings if it didn't exist already
-        }
-    }));
-
-    let (record_sender, record_receiver) = mpsc::channel::<Message>(); // Creates recorder message sender and receiver
-
-    // Creates references to the required values in the tracker
-    let record_error_handle = errors.clone();
-    let recording_empty_handle = tracker.empty_recording.clone();
-    let check = tracker.recording_check.clone();
-    match thread::Builder::new() // Spawns a new thread for recording audio
-        .name(String::from("Recorder"))
-        .spawn(move || {
-            let audio_spec = WavSpec {
-                // Decides on the settings of the recording
-                channels: 2,
-                sample_rate: 48000,
-                bits_per_sample: 32,
-                sample_format: SampleFormat::Float,
-            };
 
-            let path = match File::get_directory() {
-                Ok(value) => value,
-                Err(_) => {
-                    Tracker::write(record_error_handle.clone(), Some(Error::DirectoryError));
-                    String::new()
-                }
-            };
+            ui.invoke_save(); // Saves changes
 
-            let empty = recording_empty_handle.clone(); // New reference for the loop do avoid memory issues
-            loop {
-                match record_receiver.recv() {
-                    // Blocks until message received
-                    Ok(Message::StartRecording) => (),
-                    _ => {
-                        Tracker::write(record_error_handle.clone(), Some(Error::MessageError));
-                        continue; // Write an error and start looking for another message
-                    }
-                }
+            // Keeps the same recording selected unless it was the one deleted
+            if let Some(removed) = removed {
+                let remaining = settings_handle.read().unwrap().recordings.len();
+                ui.set_current_recording(index_after_removal(
+                    ui.get_current_recording().max(0) as usize,
+                    removed,
+                    remaining,
+                ) as i32);
+            }
+            ui.invoke_skip_audio(); // Loads whichever recording is current now
+        }
+    });
 
-                Tracker::write(empty.clone(), true);
-                Tracker::write(check.clone(), true);
+    // Deletes a recording that has no audio once the user confirms
+    ui.on_delete_empty_file({
+        let ui_handle = ui.as_weak();
 
-                let taken_names = match File::search(&path, "wav", false) {
-                    Ok(File::Names(value)) => value,
-                    Err(_) => vec![String::from("Couldn't read files")],
-                };
+        let empty_file_handle = tracker.empty_file.clone();
 
-                let mut fallbacks = 0;
-                for name in &taken_names {
-                    // Checks how many times something has had to been renamed to the fallback name
-                    if (*name).contains(&String::from("Default taken...")) {
-                        fallbacks += 1;
-                    }
-                }
+        move || {
+            let ui = ui_handle.unwrap();
 
-                let recording_amount = taken_names.len();
+            let name = empty_file_handle.write().unwrap().take();
+            if let Some(name) = name {
+                match File::delete(&Disk, name) {
+                    Some(error) => {
+                        error.send(&ui);
+                    }
+                    None => {}
+                };
+            }
 
-                let mut new_name = String::new();
+            ui.invoke_save(); // Saves changes
+        }
+    });
 
-                if recording_amount > 0 {
-                    let potential = format!("Recording {}", recording_amount + 1); // Tests a potential name
-                    for item in 0..recording_amount {
-                        if potential != taken_names[item] {
-                            // If the potential name isn't already a thing
-                            new_name = format!("{}.wav", potential); // Update new name
-                        } else {
-                            new_name = format!("Default taken... {}.wav", fallbacks + 1); // Makes a new default taken name if it has been taken
-                            break;
-                        }
-                    }
-                } else {
-                    new_name = String::from("Recording 1.wav"); // Creates this name if first recording
-                }
+    // Skips song
+    ui.on_skip_audio({
+        let ui_handle = ui.as_weak();
 
-                let mut writer = // Creates a new writer
-                    match WavWriter::create(format!("{}/{}", path, new_name), audio_spec) {
-                        Ok(value) => value,
-                        Err(_) => {
-                            Tracker::write(record_error_handle.clone(), Some(Error::WriteError));
-                            continue;
-                        }
-                    };
+        let notifier = notifier.clone();
 
-                let mut initial_silence = true;
+        let error_handle = errors.clone();
 
-                let empty2 = empty.clone(); // New reference to avoid more memory issues
-                let record_callback = move |data: RUBuffers| {
-                    // Run when callback called
-                    let mut interleaved = vec![];
+        let sender_handle = audio_sender.clone();
 
-                    let channel1_len = data[0].len();
-                    let channel2_len = data[1].len();
+        let settings_handle = tracker.settings.clone();
 
-                    for sample in 0..(if channel1_len > channel2_len {
-                        // Loops through the channel with the least amount of data
-                        channel2_len
-                    } else {
-                        channel1_len
-                    }) {
-                        if initial_silence {
-                            if data[0][sample] != 0.0 || data[1][sample] != 0.0 {
-                                // If either channel has audio playing
-                                initial_silence = false;
-                                Tracker::write(empty2.clone(), false); // Tells the tracker that this recording should be saved
-                                continue;
-                            } else {
-                                continue;
-                            }
-                        } else {
-                            // Pushes the data from each channel to the interleaved list
-                            interleaved.push(data[0][sample]);
-                            interleaved.push(data[1][sample]);
-                        }
-                    }
+        let player_handle = tracker.player.clone();
 
-                    if !initial_silence {
-                        for sample in &interleaved {
-                            writer.write_sample(*sample).unwrap(); // Writes the data from the interleaved list to file
-                        }
-                    }
-                };
+        move || {
+            let ui = ui_handle.unwrap();
 
-                let callback = rucallback!(record_callback); // Initiates a callback
+            let settings = settings_handle.read().unwrap();
 
-                let mut recorder = RUHear::new(callback); // Creates a new recorder
+            Tracker::write(player_handle.clone(), PlayerState::Empty); // Tells thread that nothing has been preloaded
 
-                match recorder.start() {
-                    // Starts a recorder
-                    Ok(_) => {}
-                    Err(_) => {
-                        Tracker::write(record_error_handle.clone(), Some(Error::RecordError));
-                        continue;
-                    }
-                };
+            let file = if settings.recordings.len() > 0 {
+                // Gets the name of the recording that should be played
+                settings.recordings[ui.get_current_recording() as usize]
+                    .name
+                    .clone()
+            } else {
+                String::new()
+            };
 
-                loop {
-                    match record_receiver.recv() {
-                        // Blocks until a stop message is received
-                        Ok(Message::StopRecording) => break,
-                        _ => {
-                            Tracker::write(record_error_handle.clone(), Some(Error::MessageError));
-                            continue;
-                        }
-                    }
+            let path = match File::get_directory() {
+                Ok(value) => value,
+                Err(error) => {
+                    error.send(&ui);
+                    String::new()
                 }
+            };
 
-                match recorder.stop() {
-                    // Stops recording
-                    Ok(_) => {}
-                    Err(_) => {
-                        Tracker::write(record_error_handle.clone(), Some(Error::RecordError));
-                        continue;
+            let snapshot_data = if settings.recordings.len() > 0 {
+                // Loads the snapshot data of that recording
+                match load(
+                    &settings.recordings[ui.get_current_recording() as usize].name,
+                    LoadType::Snapshot,
+                ) {
+                    Ok(DataType::SnapShot(data)) => data,
+                    _ => {
+                        Error::LoadError(
+                            settings.recordings[ui.get_current_recording() as usize]
+                                .name
+                                .clone(),
+                        )
+                        .send(&ui);
+                        SnapShot::new()
                     }
-                };
+                }
+            } else {
+                SnapShot::new()
+            };
 
-                if Tracker::read(empty.clone()) {
-                    // If recording empty
-                    match File::delete(File::truncate(&mut new_name, ".", 0)) {
-                        // Delete any recording data that had been saved so far
-                        Some(_) => {
-                            Tracker::write(
-                                record_error_handle.clone(),
-                                Some(Error::EmptyRecordingError),
+            if settings.recordings.len() > 0 {
+                for _ in 0..if ui.get_starting_threads() {
+                    // If threads are starting for the first time only send load messgae once, otherwise twice
+                    ui.set_starting_threads(false);
+                    1
+                } else {
+                    2
+                } {
+                    match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
+                        // Sends load message and file path
+                        Ok(_) => (),
+                        Err(error) => {
+                            notifier.write(
+                                error_handle.clone(),
+                                Some(Error::PlaybackError(error.to_string())),
                             );
                         }
-                        None => (),
                     }
-                } else {
-                    match SnapShot::create(&File::truncate(&mut new_name, ".", 0)) {
-                        // Creates a new snapshot if there's a file but no snapshots
-                        Some(error) => {
-                            Tracker::write(record_error_handle.clone(), Some(error));
+                }
+                if ui.get_audio_playback() {
+                    // If already generic playing
+                    match sender_handle.send(Message::PlayAudio((
+                        // Sends message to play new recording as a generic playback along with snapshot data
+                        Playback::Generic(snapshot_data),
+                        ui.get_current_recording() as usize,
+                    ))) {
+                        Ok(_) => (),
+                        Err(error) => {
+                            notifier.write(
+                                error_handle.clone(),
+                                Some(Error::PlaybackError(error.to_string())),
+                            );
+                        }
+                    }
+                } else if ui.get_input_playback() {
+                    // If already input playback
+                    match sender_handle.send(Message::PlayAudio((
+                        // Sends message to play new recordings input data along with its snapshot data
+                        Playback::Input(snapshot_data),
+                        ui.get_current_recording() as usize,
+                    ))) {
+                        Ok(_) => (),
+                        Err(error) => {
+                            notifier.write(
+                                error_handle.clone(),
+                                Some(Error::PlaybackError(error.to_string())),
+                            );
+                        }
+                    }
+                } else if ui.get_input_recording() {
+                    // If recording inputs
+                    for _ in 0..2 {
+                        let snapshot_data = SnapShot::none(); // Send message to record inputs twice
+                        match sender_handle.send(Message::PlayAudio((
+                            Playback::Capture(snapshot_data),
+                            ui.get_current_recording() as usize,
+                        ))) {
+                            Ok(_) => (),
+                            Err(error) => {
+                                notifier.write(
+                                    error_handle.clone(),
+                                    Some(Error::PlaybackError(error.to_string())),
+                                );
+                            }
                         }
-                        None => (),
                     }
                 }
             }
-        }) {
-        Ok(_) => (),
-        Err(_) => {
-            Tracker::write(errors.clone(), Some(Error::RecorderThreadError)); // Error if thread fails to start
-        }
-    };
-
-    let (audio_sender, audio_receiver) = mpsc::channel::<Message>(); // Message sender and reciever for audio playback
-
-    // Creates references for required values in audio thread
-    let player_error_handle = errors.clone();
-    let player_settings_handle = tracker.settings.clone();
-    let player_frame_handle = tracker.snapshot_frame_values.clone();
-    let player_finished = tracker.playing.clone();
-    let loaded = tracker.preloaded.clone();
-    match thread::Builder::new() // Creates audio thread
-        .name(String::from("Player"))
-        .spawn(move || {
-            // Initialises some variables
-            let mut sound_data;
-
-            let mut length;
-
-            let mut file;
-
-            'one: loop {
-                match audio_receiver.recv() {
-                    // Blocks until a load file message is received
-                    Ok(Message::File(name)) => {
-                        file = name;
-                        sound_data = match StaticSoundData::from_file(&file) {
-                            // Loads audio data from file
-                            Ok(value) => {
-                                length = value.duration(); // Gets the length of the audio
-                                Tracker::write(loaded.clone(), true);
-                                value
-                            }
-                            Err(_) => {
-                                Tracker::write(player_error_handle.clone(), Some(Error::ReadError));
-                                continue 'one;
-                            }
-                        };
+        }
+    });
+
+    // On generic playback
+    ui.on_play_generic({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let error_handle = errors.clone();
+
+        let sender_handle = audio_sender.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let player_handle = tracker.player.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+
+            let snapshot_data = match load(
+                // Load snapshot data
+                &settings.recordings[ui.get_current_recording() as usize].name,
+                LoadType::Snapshot,
+            ) {
+                Ok(DataType::SnapShot(data)) => data,
+                _ => {
+                    Error::LoadError(
+                        settings.recordings[ui.get_current_recording() as usize]
+                            .name
+                            .clone(),
+                    )
+                    .send(&ui);
+                    return;
+                }
+            };
+
+            if Tracker::read(player_handle.clone()) != PlayerState::Empty {
+                () // Do nothing if data has been preloaded
+            } else {
+                // Load new data
+                let file = if settings.recordings.len() > 0 {
+                    settings.recordings[ui.get_current_recording() as usize]
+                        .name
+                        .clone()
+                } else {
+                    String::new()
+                };
+
+                let path = match File::get_directory() {
+                    Ok(value) => value,
+                    Err(error) => {
+                        error.send(&ui);
+                        String::new()
                     }
-                    _ => {
-                        Tracker::write(player_error_handle.clone(), Some(Error::MessageError));
-                        continue 'one;
+                };
+
+                match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
+                    Ok(_) => (),
+                    Err(error) => {
+                        notifier.write(
+                            error_handle.clone(),
+                            Some(Error::PlaybackError(error.to_string())),
+                        );
                     }
                 }
+            }
 
-                'two: loop {
-                    let mut capturing = false;
-                    match audio_receiver.recv() {
-                        // Blocks until message received
-                        Ok(Message::File(_)) => break 'two, // Breaks the second loop to load a file
-                        Ok(Message::PlayAudio(mut playback)) => {
-                            if let Playback::Capture(_) = playback.0 {
-                                capturing = true; // Sets capturing check to true if playback type is Capture
-                            }
-                            let mut audio_manager = match AudioManager::<DefaultBackend>::new(
-                                // Create a new audio manager
-                                AudioManagerSettings::default(),
-                            ) {
-                                Ok(value) => value,
-                                Err(_) => {
-                                    Tracker::write(
-                                        player_error_handle.clone(),
-                                        Some(Error::PlaybackError),
-                                    );
-                                    continue 'two;
-                                }
-                            };
+            match sender_handle.send(if ui.get_audio_playback() {
+                // Send message to start and stop playback and update UI accordingly
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                Message::StopAudio
+            } else {
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Audio));
+                if ui.get_recording() {
+                    Warning::PlaybackCaptured.send(&ui);
+                }
+                Message::PlayAudio((
+                    Playback::Generic(snapshot_data),
+                    ui.get_current_recording() as usize,
+                ))
+            }) {
+                Ok(_) => (),
+                Err(error) => {
+                    notifier.write(
+                        error_handle.clone(),
+                        Some(Error::PlaybackError(error.to_string())),
+                    );
+                }
+            }
 
-                            // Filter setup
-                            let sub_bass =
-                                EqFilterBuilder::new(EqFilterKind::LowShelf, 40.0, 0.0, 1.0);
-                            let bass = EqFilterBuilder::new(EqFilterKind::Bell, 155.0, 0.0, 0.82);
-                            let low_mids =
-                                EqFilterBuilder::new(EqFilterKind::Bell, 625.0, 0.0, 0.83);
-                            let high_mids =
-                                EqFilterBuilder::new(EqFilterKind::Bell, 1500.0, 0.0, 1.5);
-                            let treble =
-                                EqFilterBuilder::new(EqFilterKind::HighShelf, 12000.0, 0.0, 0.75);
-                            let pan = PanningControlBuilder::default();
-
-                            // Filter handles for real time updating
-                            let mut builder = TrackBuilder::new();
-                            let mut sub_bass_handle = builder.add_effect(sub_bass);
-                            let mut bass_handle = builder.add_effect(bass);
-                            let mut low_mids_handle = builder.add_effect(low_mids);
-                            let mut high_mids_handle = builder.add_effect(high_mids);
-                            let mut treble_handle = builder.add_effect(treble);
-                            let mut panning_handle = builder.add_effect(pan);
-
-                            let mut track = match audio_manager.add_sub_track(builder) {
-                                // Creates a track with the filter handles enabled
-                                Ok(value) => value,
-                                Err(_) => {
-                                    Tracker::write(
-                                        player_error_handle.clone(),
-                                        Some(Error::PlaybackError),
-                                    );
-                                    continue 'two;
-                                }
-                            };
+            if settings.recordings.len() > 0 {
+                ui.set_current_dial_values(ModelRc::new(VecModel::from(
+                    settings.recordings[ui.get_current_recording() as usize]
+                        .parse_vec_from_recording(),
+                )));
+            }
+        }
+    });
+
+    // Input playback
+    ui.on_play_captured_inputs({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let dials = tracker.snapshot_frame_values.clone();
+
+        let error_handle = errors.clone();
+
+        let sender_handle = audio_sender.clone();
+
+        let player_handle = tracker.player.clone();
+
+        let overrides_handle = tracker.dial_overrides.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+
+            // Every dial follows the captured inputs again whenever input playback starts or stops
+            Tracker::write(overrides_handle.clone(), [None; 6]);
+            ui.set_dial_overrides(ModelRc::new(VecModel::from(vec![false; 6])));
+
+            let snapshot_data = match load(
+                // Load snapshot data
+                &settings.recordings[ui.get_current_recording() as usize].name,
+                LoadType::Snapshot,
+            ) {
+                Ok(DataType::SnapShot(data)) => data,
+                _ => {
+                    Error::LoadError(
+                        settings.recordings[ui.get_current_recording() as usize]
+                            .name
+                            .clone(),
+                    )
+                    .send(&ui);
+                    return;
+                }
+            };
+
+            Tracker::write(
+                dials.clone(),
+                Recording::parse(&settings.recordings[ui.get_current_recording() as usize]),
+            );
+
+            if Tracker::read(player_handle.clone()) != PlayerState::Empty {
+                ()
+            } else {
+                let file = if settings.recordings.len() > 0 {
+                    settings.recordings[ui.get_current_recording() as usize]
+                        .name
+                        .clone()
+                } else {
+                    String::new()
+                };
+
+                let path = match File::get_directory() {
+                    Ok(value) => value,
+                    Err(error) => {
+                        error.send(&ui);
+                        String::new()
+                    }
+                };
+
+                match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
+                    Ok(_) => (),
+                    Err(error) => {
+                        notifier.write(
+                            error_handle.clone(),
+                            Some(Error::PlaybackError(error.to_string())),
+                        );
+                    }
+                }
+            }
+
+            match sender_handle.send(if ui.get_input_playback() {
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                // Puts the recording's own values back on the dials instead of leaving the last captured frame showing
+                // Input playback only previews the captured inputs, the saved values were never changed
+                let recording = &settings.recordings[ui.get_current_recording() as usize];
+                ui.set_current_dial_values(ModelRc::new(VecModel::from(
+                    recording.parse_vec_from_recording(),
+                )));
+                recording.send_current(&ui);
+                Message::StopAudio
+            } else {
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Input));
+                Message::PlayAudio((
+                    Playback::Input(snapshot_data),
+                    ui.get_current_recording() as usize,
+                ))
+            }) {
+                Ok(_) => (),
+                Err(error) => {
+                    notifier.write(
+                        error_handle.clone(),
+                        Some(Error::PlaybackError(error.to_string())),
+                    );
+                }
+            }
+        }
+    });
+
+    // Checks if capturing would replace automation that was captured before
+    ui.on_has_automation({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+
+            match settings.recordings.get(ui.get_current_recording() as usize) {
+                Some(recording) => match load(&recording.name, LoadType::Snapshot) {
+                    Ok(DataType::SnapShot(data)) => !data.is_empty(),
+                    _ => false, // Nothing readable to lose
+                },
+                None => false,
+            }
+        }
+    });
+
+    // Record inputs
+    ui.on_capture_inputs({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let error_handle = errors.clone();
+
+        let sender_handle = audio_sender.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let player_handle = tracker.player.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+
+            let snapshot_data = if ui.get_append_capture() {
+                // Keeps the frames that were captured before so the new ones are added to them
+                match load(
+                    &settings.recordings[ui.get_current_recording() as usize].name,
+                    LoadType::Snapshot,
+                ) {
+                    Ok(DataType::SnapShot(data)) => data,
+                    _ => {
+                        Error::LoadError(
+                            settings.recordings[ui.get_current_recording() as usize]
+                                .name
+                                .clone(),
+                        )
+                        .send(&ui);
+                        return;
+                    }
+                }
+            } else {
+                SnapShot::none()
+            };
+
+            if Tracker::read(player_handle.clone()) != PlayerState::Empty {
+                ()
+            } else {
+                let file = if settings.recordings.len() > 0 {
+                    settings.recordings[ui.get_current_recording() as usize]
+                        .name
+                        .clone()
+                } else {
+                    String::new()
+                };
+
+                let path = match File::get_directory() {
+                    Ok(value) => value,
+                    Err(error) => {
+                        error.send(&ui);
+                        String::new()
+                    }
+                };
+
+                match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
+                    Ok(_) => (),
+                    Err(error) => {
+                        notifier.write(
+                            error_handle.clone(),
+                            Some(Error::PlaybackError(error.to_string())),
+                        );
+                    }
+                }
+            }
+
+            match sender_handle.send(if ui.get_input_playback() {
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                ui.set_locked(false);
+                Message::StopAudio
+            } else {
+                ui.set_app_state(ui.get_app_state().playing(PlaybackState::Capture));
+                Message::PlayAudio((
+                    Playback::Capture(snapshot_data),
+                    ui.get_current_recording() as usize,
+                ))
+            }) {
+                Ok(_) => (),
+                Err(error) => {
+                    notifier.write(
+                        error_handle.clone(),
+                        Some(Error::PlaybackError(error.to_string())),
+                    );
+                }
+            }
+        }
+    });
+
+    // Moves on to the recording auto next would play, following the queue and shuffle
+    ui.on_next_recording({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+            if settings.recordings.is_empty() {
+                return;
+            }
+            // Plays the next queued recording if following the queue, falling back to the library if none are left
+            let queued = if settings.follow_queue {
+                let (next, skipped) = settings.next_in_queue(ui.get_current_recording() as usize);
+                if skipped {
+                    Warning::QueueSkipped.send(&ui);
+                }
+                next
+            } else {
+                None
+            };
+            // Skips to first recording if on last recording, otherwise skips to next recording
+            // Also handles shuffle logic
+            if let Some(index) = queued {
+                ui.set_current_recording(index as i32);
+            } else if ui.get_shuffle() && settings.get_index_data().recording_length > 2 {
+                if ui.get_current_shuffle_index() == (ui.get_shuffle_order().row_count() - 1) as i32
+                {
+                    // If on last index in shuffle list, reshuffle and set index to 0
+                    ui.invoke_gen_shuffle();
+                    ui.set_current_shuffle_index(0);
+                } else {
+                    ui.set_current_shuffle_index(ui.get_current_shuffle_index() + 1);
+                    // Otherwise increase shuffle index by one
+                }
+                ui.set_current_recording(
+                    ui.get_shuffle_order()
+                        .row_data(ui.get_current_shuffle_index() as usize)
+                        .unwrap(),
+                ); // Set current recording to shuffle index
+            } else {
+                if ui.get_current_recording() == (settings.recordings.len() - 1) as i32 {
+                    ui.set_current_recording(0);
+                } else {
+                    ui.set_current_recording(ui.get_current_recording() + 1);
+                }
+            }
+        }
+    });
+
+    // Update UI when playing is finished
+    ui.on_sync_playing_with_backend({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let player_handle = tracker.player.clone();
+
+        let sender_handle = audio_sender.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let error_handle = errors.clone();
+
+        let looping_handle = tracker.looping.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            // The current recording's own playback type is used over the global one if it has one
+            let playback = match settings_handle
+                .read()
+                .unwrap()
+                .recordings
+                .get(ui.get_current_recording() as usize)
+                .and_then(|recording| recording.playback_override)
+            {
+                Some(mode) => mode.to_ui(),
+                None => ui.get_playback(),
+            };
+            Tracker::write(looping_handle.clone(), playback == PlaybackType::Loop); // Lets the player keep to the loop region
+
+            let finished = {
+                // Taken here so each finish is only acted on once
+                let mut player = player_handle.write().unwrap();
+                let finished = *player == PlayerState::Finished;
+                if finished {
+                    *player = PlayerState::Loaded;
+                }
+                finished
+            };
+            if finished || ui.get_auto_next_gap_done() {
+                // If finished playing
+                if playback == PlaybackType::AutoNext
+                    && !ui.get_auto_next_gap_done()
+                    && !ui.get_input_recording()
+                    && ui.get_auto_next_gap_seconds() > 0
+                {
+                    // Leaves a gap before the next track, the UI timer picks it back up and pressing anything cancels it
+                    ui.set_auto_next_waiting(true);
+                    return;
+                }
+                ui.set_auto_next_gap_done(false);
+
+                let settings = settings_handle.read().unwrap();
+
+                if playback == PlaybackType::None {
+                    // If playback type is set to stop playing at the end of the song
+                    // Update UI and do nothing
+                    if ui.get_input_playback() || ui.get_input_recording() {
+                        drop(settings);
+                        ui.invoke_sync_with_locked_values();
+                        ui.invoke_save();
+                    }
+                    ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                } else if playback == PlaybackType::Loop || playback == PlaybackType::AutoNext
+                // If looping or auto skippng to next song
+                {
+                    match sender_handle.send(if ui.get_input_recording() {
+                        // Stop audio if recording inputs
+                        ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                        drop(settings);
+                        ui.invoke_sync_with_locked_values();
+                        ui.invoke_save();
+                        Message::StopAudio
+                    } else {
+                        if playback == PlaybackType::AutoNext {
+                            // If auto skipping
+                            ui.invoke_next_recording();
+                            ui.invoke_skip_audio(); // Invokes skip callback
+                        }
+                        let snapshot_data = match load(
+                            // Load snapshot data
+                            &settings.recordings[ui.get_current_recording() as usize].name,
+                            LoadType::Snapshot,
+                        ) {
+                            Ok(DataType::SnapShot(data)) => data,
+                            _ => {
+                                Error::LoadError(
+                                    settings.recordings[ui.get_current_recording() as usize]
+                                        .name
+                                        .clone(),
+                                )
+                                .send(&ui);
+                                SnapShot::new()
+                            }
+                        };
+                        Message::PlayAudio((
+                            // Send the correct play message to UI depending on what button has been pressed
+                            if ui.get_audio_playback() {
+                                Playback::Generic(snapshot_data)
+                            } else if ui.get_input_playback() {
+                                Playback::Input(snapshot_data)
+                            } else {
+                                Playback::Generic(snapshot_data)
+                            },
+                            ui.get_current_recording() as usize,
+                        ))
+                    }) {
+                        Ok(_) => (),
+                        Err(_) => {
+                            notifier.write(error_handle.clone(), Some(Error::MessageError));
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    // Update dial values when playing back inputs
+    ui.on_snapshot_dial_update({
+        let ui_handle = ui.as_weak();
+
+        let dials = tracker.snapshot_frame_values.clone();
+
+        let automation_handle = tracker.automation_point.clone();
+
+        let overrides_handle = tracker.dial_overrides.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            // Dials the user has taken over show their own values instead of the captured ones
+            let dial_values = with_overrides(
+                Tracker::read(dials.clone()),
+                Tracker::read(overrides_handle.clone()),
+            );
+
+            ui.set_current_dial_values(ModelRc::new(VecModel::from(
+                Recording::parse_vec_from_list(dial_values),
+            )));
+
+            let (point, points, upcoming) = Tracker::read(automation_handle.clone());
+            ui.set_automation_point(point as i32);
+            ui.set_automation_points(points as i32);
+            ui.set_automation_upcoming(upcoming);
+        }
+    });
+
+    // Takes a dial over from the captured inputs during input playback
+    ui.on_override_dial({
+        let overrides_handle = tracker.dial_overrides.clone();
+
+        move |dial, value| {
+            if let Some(slot) = overrides_handle.write().unwrap().get_mut(dial as usize) {
+                *slot = Some(value.clamp(MIN_DIAL, MAX_DIAL));
+            }
+        }
+    });
+
+    // Hands a dial back to the captured inputs
+    ui.on_release_dial({
+        let overrides_handle = tracker.dial_overrides.clone();
+
+        move |dial| {
+            if let Some(slot) = overrides_handle.write().unwrap().get_mut(dial as usize) {
+                *slot = None;
+            }
+        }
+    });
+
+    // Shows how many automation points have been captured so far
+    ui.on_sync_captured_points({
+        let ui_handle = ui.as_weak();
+
+        let captured_handle = tracker.captured_points.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            ui.set_captured_points(Tracker::read(captured_handle.clone()) as i32);
+        }
+    });
+
+    // Shows what each dial value does under the dial
+    ui.on_dial_readout(|index, value| dial_readout(index as usize, value));
+
+    // Pauses whatever was using a device that was unplugged and starts it again once the device is back
+    ui.on_watch_devices({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        let position_handle = tracker.position.clone();
+
+        let resume_handle = tracker.resume_from.clone();
+
+        let playback_device = tracker.playback_device.clone();
+
+        let recording_device = tracker.recording_device.clone();
+
+        let outputs_handle = tracker.outputs.clone();
+
+        let watching_handle = tracker.watching_devices.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            // Devices are listed by the device thread, this only compares against what it last saw
+            let outputs = outputs_handle.read().unwrap().clone();
+
+            let current = ui.get_current_recording() as usize;
+            let position = Tracker::read(position_handle.clone());
+            let chosen = settings_handle.read().unwrap().output_device.clone();
+            let state = ui.get_app_state();
+            // Playback through the system default follows it when it changes so only a chosen device can be lost
+            let active = match state.playback() {
+                _ if chosen.is_empty() => None,
+                PlaybackState::Audio => Some(Interrupted::Audio(current, position)),
+                PlaybackState::Input => Some(Interrupted::Input(current, position)),
+                PlaybackState::Capture => Some(Interrupted::Capture),
+                PlaybackState::Stopped => None,
+            };
+            let recording = if state.recording() {
+                Some(Interrupted::Recording)
+            } else {
+                None
+            };
+
+            let (playback_change, recording_change) = {
+                let mut playback_watch = playback_device.write().unwrap();
+                let mut recording_watch = recording_device.write().unwrap();
+                let playback_needs = playback_watch.needs_devices(active);
+                let recording_needs = recording_watch.needs_devices(recording);
+                Tracker::write(watching_handle.clone(), playback_needs || recording_needs);
+
+                // Waits for the device thread's first look after something starts rather than comparing against nothing
+                let playback_change = match &outputs {
+                    None if playback_needs => DeviceChange::Unchanged,
+                    _ => playback_watch.check(active, || {
+                        outputs.as_ref().and_then(|outputs| outputs.chosen(&chosen))
+                    }),
+                };
+                // The recorder captures the default output it started on, which is only lost if it's unplugged
+                let recording_change = match &outputs {
+                    None if recording_needs => DeviceChange::Unchanged,
+                    _ => {
+                        let captured = outputs
+                            .as_ref()
+                            .and_then(|outputs| outputs.captured(&recording_watch));
+                        recording_watch.check(recording, || captured)
+                    }
+                };
+                (playback_change, recording_change)
+            };
+
+            match playback_change {
+                DeviceChange::Lost(interrupted) => {
+                    // Stops through the same callbacks as the buttons so the UI and the player stay in step
+                    match interrupted {
+                        Interrupted::Audio(..) => ui.invoke_play_generic(),
+                        Interrupted::Input(..) => ui.invoke_play_captured_inputs(),
+                        Interrupted::Capture => ui.invoke_capture_inputs(),
+                        Interrupted::Recording => (),
+                    }
+                    Error::DeviceLostError.send(&ui);
+                }
+                DeviceChange::Restored(interrupted) => match interrupted {
+                    // Only carries on if the same recording is still selected
+                    Interrupted::Audio(index, position) | Interrupted::Input(index, position)
+                        if index == current =>
+                    {
+                        Tracker::write(resume_handle.clone(), Some(position));
+                        if let Interrupted::Audio(..) = interrupted {
+                            ui.invoke_play_generic();
+                        } else {
+                            ui.invoke_play_captured_inputs();
+                            ui.invoke_update_locked_values();
+                        }
+                        Warning::DeviceRestored.send(&ui);
+                    }
+                    _ => (),
+                },
+                DeviceChange::Unchanged => (),
+            }
+
+            match recording_change {
+                DeviceChange::Lost(_) => {
+                    ui.invoke_record(); // Stops and keeps what was recorded before the device went
+                    Error::DeviceLostError.send(&ui);
+                }
+                DeviceChange::Restored(_) => {
+                    ui.invoke_record(); // Carries on in a new recording
+                    Warning::DeviceRestored.send(&ui);
+                }
+                DeviceChange::Unchanged => (),
+            }
+        }
+    });
+
+    // Check for any errors and update UI
+    ui.on_check_for_errors({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let error_handle = errors.clone();
+
+        let warning_handle = warnings.clone();
+
+        let sender = audio_sender.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let position_handle = tracker.position.clone();
+
+        let resume_handle = tracker.resume_from.clone();
+
+        let empty_file_handle = tracker.empty_file.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            match Tracker::read(warning_handle.clone()) {
+                // Warnings only need showing, nothing has to be stopped
+                Some(warning) => {
+                    warning.send(&ui);
+                    Tracker::write(warning_handle.clone(), None);
+                }
+                None => (),
+            }
+
+            if VALUES_CLAMPED.swap(false, AtomicOrdering::Relaxed) {
+                Warning::ValuesClamped.send(&ui);
+            }
+            if FRAMES_DROPPED.swap(false, AtomicOrdering::Relaxed) {
+                Warning::FramesDropped.send(&ui);
+            }
+
+            let occured = error_handle.read().unwrap().clone();
+            match occured {
+                Some(error) => {
+                    let mut recovered = false; // Whether playback was restarted so it shouldn't be stopped in the UI
+                    let mut notice = error.clone(); // What's shown, a skipped file is reported instead of the read that failed
+                    match &error {
+                        Error::ReadError(file)
+                            if (ui.get_audio_playback() || ui.get_input_playback())
+                                && settings_handle.read().unwrap().skip_missing
+                                && !Path::new(&file).is_file() =>
+                        {
+                            // The file went missing after the last sync, so playback moves on and the entry is dropped
+                            let name = Path::new(&file)
+                                .file_stem()
+                                .map_or(file.clone(), |name| name.to_string_lossy().to_string());
+                            let dead = ui.get_current_recording();
+                            ui.invoke_next_recording(); // Picked while the entry is still there so the queue and shuffle carry on from it
+                            let remaining = {
+                                let mut settings = notifier.edit(&settings_handle);
+                                if let Some(index) = settings
+                                    .recordings
+                                    .iter()
+                                    .position(|recording| recording.name == name)
+                                {
+                                    settings.recordings.remove(index);
+                                    let next = ui.get_current_recording();
+                                    if next > index as i32 {
+                                        ui.set_current_recording(next - 1); // Moved up into the gap
+                                    } else if next == dead {
+                                        ui.set_current_recording(0); // It was the only one left to move to
+                                    }
+                                }
+                                settings.recordings.len()
+                            };
+                            notice = Error::MissingFileError(name);
+                            if remaining > 0 {
+                                if ui.get_shuffle() {
+                                    ui.invoke_gen_shuffle(); // The old order points at the wrong recordings now
+                                    ui.set_current_shuffle_index(0);
+                                }
+                                ui.invoke_save();
+                                ui.invoke_update();
+                                ui.invoke_skip_audio();
+                                recovered = true;
+                            } else {
+                                ui.invoke_save();
+                                ui.invoke_update();
+                            }
+                        }
+                        Error::MessageError => {
+                            // Reload audio if incorrect mesaage sent to thread
+                            // This ensures that it won't keep failing
+                            if ui.get_audio_playback() || ui.get_input_playback() {
+                                // Carries on from where playback was with the same type of playback instead of starting again
+                                Tracker::write(
+                                    resume_handle.clone(),
+                                    Some(Tracker::read(position_handle.clone())),
+                                );
+                                ui.invoke_skip_audio();
+                                recovered = true;
+                            } else if ui.get_input_recording() {
+                                let settings = settings_handle.read().unwrap();
+
+                                let file = if settings.recordings.len() > 0 {
+                                    settings.recordings[ui.get_current_recording() as usize]
+                                        .name
+                                        .clone()
+                                } else {
+                                    String::new()
+                                };
+
+                                let path = match File::get_directory() {
+                                    Ok(value) => value,
+                                    Err(error) => {
+                                        error.send(&ui);
+                                        String::new()
+                                    }
+                                };
+                                match sender.send(Message::File(format!("{}/{}.wav", path, file))) {
+                                    Ok(_) => (),
+                                    Err(_) => (),
+                                }
+                            }
+                        }
+                        Error::SpaceRunningOutError => {
+                            // Stops like the record button would so the file is finished and saved
+                            if ui.get_recording() {
+                                ui.invoke_record();
+                            }
+                        }
+                        Error::EmptyFileError => {
+                            // Offers to delete the file since it can never be played
+                            if let Some(name) = empty_file_handle.read().unwrap().clone() {
+                                ui.set_empty_file_name(SharedString::from(name));
+                                ui.set_empty_file_found(true);
+                            }
+                        }
+                        Error::ReadError(_) => {
+                            // Load new data
+                            let settings = settings_handle.read().unwrap();
+                            let file = if settings.recordings.len() > 0 {
+                                settings.recordings[ui.get_current_recording() as usize]
+                                    .name
+                                    .clone()
+                            } else {
+                                String::new()
+                            };
+
+                            let path = match File::get_directory() {
+                                Ok(value) => value,
+                                Err(error) => {
+                                    error.send(&ui);
+                                    String::new()
+                                }
+                            };
+
+                            for _ in 0..2 {
+                                match sender.send(Message::File(format!("{}/{}.wav", path, file))) {
+                                    Ok(_) => (),
+                                    Err(error) => {
+                                        notifier.write(
+                                            error_handle.clone(),
+                                            Some(Error::PlaybackError(error.to_string())),
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                    // Sets the UI variables of whatever failed to false and sends error to UI
+                    // Recording and playback can run together so one failing shouldn't stop the other
+                    match error {
+                        Error::RecordError
+                        | Error::WriteError
+                        | Error::RecorderThreadError
+                        | Error::FallbackLimitError
+                        | Error::NameTakenError
+                        | Error::ExistsError
+                        | Error::NoSpaceError
+                        | Error::SpaceRunningOutError => {
+                            ui.set_app_state(ui.get_app_state().with_recording(false));
+                        }
+                        _ if recovered => (),
+                        _ => {
+                            ui.set_app_state(ui.get_app_state().playing(PlaybackState::Stopped));
+                        }
+                    }
+                    notice.send(&ui);
+                    Tracker::write(error_handle.clone(), None);
+                }
+                None => (),
+            }
+        }
+    });
+
+    // Looks for snapshot files that don't line up with the recordings and tells the user
+    ui.on_scan_orphans({
+        let ui_handle = ui.as_weak();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            match Orphans::scan(&Disk) {
+                Ok(orphans) => {
+                    ui.set_orphans_found(orphans.found());
+                    ui.set_orphan_summary(orphans.summary());
+                }
+                Err(error) => {
+                    error.send(&ui);
+                }
+            }
+        }
+    });
+
+    // Looks for captured inputs that were autosaved but never finished
+    ui.on_scan_captures({
+        let ui_handle = ui.as_weak();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            match SnapShot::leftover_captures(&Disk) {
+                Ok(names) => {
+                    ui.set_recoverable_capture(SharedString::from(
+                        names.first().cloned().unwrap_or_default(),
+                    ));
+                }
+                Err(error) => {
+                    error.send(&ui);
+                }
+            }
+        }
+    });
+
+    // Uses or throws away the leftover captured inputs that are being shown
+    ui.on_recover_capture({
+        let ui_handle = ui.as_weak();
+
+        move |keep| {
+            let ui = ui_handle.unwrap();
+
+            match SnapShot::recover_capture(&ui.get_recoverable_capture(), keep) {
+                Some(error) => error.send(&ui),
+                None => (),
+            }
+
+            ui.invoke_scan_captures(); // Moves on to the next one if there's more than one
+        }
+    });
+
+    // Shows totals for the whole library, only reading the files again if the recordings have changed
+    ui.on_refresh_library_stats({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        let stats_handle = tracker.library_stats.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+
+            let mut stats = stats_handle.write().unwrap();
+
+            let current = match stats.as_ref() {
+                Some(value) => value.is_current(&settings.recordings),
+                None => false,
+            };
+            if !current {
+                match LibraryStats::gather(&Disk, &settings.recordings) {
+                    Ok(value) => *stats = Some(value),
+                    Err(error) => {
+                        error.send(&ui);
+                        return;
+                    }
+                }
+            }
+
+            if let Some(value) = stats.as_ref() {
+                value.send(&ui);
+            }
+        }
+    });
+
+    // Deletes orphaned snapshots and regenerates unreadable ones once the user confirms
+    ui.on_repair_orphans({
+        let ui_handle = ui.as_weak();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            // Scans again in case the files changed since the summary was shown
+            match Orphans::scan(&Disk) {
+                Ok(orphans) => match orphans.repair(&Disk) {
+                    Some(error) => {
+                        error.send(&ui);
+                    }
+                    None => {}
+                },
+                Err(error) => {
+                    error.send(&ui);
+                }
+            }
+
+            ui.set_orphans_found(false);
+        }
+    });
+
+    // Replaces every recording's captured inputs with nothing once the user confirms
+    ui.on_reset_all_automation({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let (reset, failed) = settings_handle.read().unwrap().reset_automation();
+            match failed {
+                Some(error) => error.send(&ui),
+                None => Warning::AutomationReset(reset).send(&ui),
+            }
+        }
+    });
+
+    // Saves changes made in the options panel
+    ui.on_options_changed({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let saved_handle = tracker.saved.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let mut settings = notifier.edit(&settings_handle);
+            let snapshot_folder = settings.snapshot_folder;
+            settings.read_options(&ui);
+            notifier.set_enabled(settings.push_updates);
+
+            if settings.snapshot_folder != snapshot_folder {
+                // Moves the snapshots over as soon as the option changes
+                if let Some(error) = File::arrange_snapshots(&Disk, settings.snapshot_folder) {
+                    error.send(&ui);
+                }
+            }
+
+            match Tracker::save_settings(&settings, saved_handle.clone()) {
+                Some(error) => {
+                    error.send(&ui);
+                }
+                None => {}
+            }
+        }
+    });
+
+    // Saves the current recording's dials as the starting point for new recordings, or resets it to flat
+    ui.on_set_default_dials({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let saved_handle = tracker.saved.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move |from_current| {
+            let ui = ui_handle.unwrap();
+
+            let mut settings = notifier.edit(&settings_handle);
+            settings.default_dials = if from_current {
+                match settings.recordings.get(ui.get_current_recording() as usize) {
+                    Some(recording) => recording.parse(),
+                    None => return,
+                }
+            } else {
+                flat_dials()
+            };
+            settings.send_options(&ui);
+
+            match Tracker::save_settings(&settings, saved_handle.clone()) {
+                Some(error) => {
+                    error.send(&ui);
+                }
+                None => {}
+            }
+        }
+    });
+
+    // Shows which edited names would fail before anything is renamed
+    ui.on_check_renames({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let settings = settings_handle.read().unwrap();
+            let names = ui
+                .get_recording_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect();
+            ui.set_rename_problems(ModelRc::new(VecModel::from(
+                Recording::check_renames(&Disk, &settings.recordings, &names)
+                    .iter()
+                    .map(|problem| match problem {
+                        Some(error) => error.get_text(),
+                        None => SharedString::new(),
+                    })
+                    .collect::<Vec<SharedString>>(),
+            )));
+        }
+    });
+
+    // Renames just the current recording without going through the full rename list
+    ui.on_rename_recording({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let saved_handle = tracker.saved.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move |index, name| {
+            let ui = ui_handle.unwrap();
+
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let before = settings.recording_names();
+                match Recording::rename_one(
+                    &Disk,
+                    &mut settings.recordings,
+                    index as usize,
+                    &name.to_string(),
+                ) {
+                    Some(error) => {
+                        error.send(&ui);
+                        return;
+                    }
+                    None => {}
+                }
+                settings.follow_renames(&before); // Keeps the queue pointing at the recording
+
+                match Tracker::save_settings(&settings, saved_handle.clone()) {
+                    Some(error) => {
+                        error.send(&ui);
+                    }
+                    None => {}
+                }
+            }
+
+            ui.invoke_update(); // Updates UI
+        }
+    });
 
-                            let _ = match track.play(sound_data.clone()) {
-                                // Plays the track
-                                Ok(value) => value,
-                                Err(_) => {
-                                    Tracker::write(
-                                        player_error_handle.clone(),
-                                        Some(Error::PlaybackError),
-                                    );
-                                    continue 'two;
-                                }
-                            };
+    // Gives recordings stuck with 'Default taken...' names normal ones
+    ui.on_renumber_fallbacks({
+        let ui_handle = ui.as_weak();
 
-                            let start = Instant::now(); // Gets the time the track started playing
-                            let mut frame: usize = 0;
-                            let mut previous_frame = [0, 0, 0, 0, 0, 0];
-                            let mut edited_frame: usize = 0;
-                            let mut snapshot = if let Playback::Capture(ref data) = playback.0 {
-                                // Gets snapshot data
-                                capturing = true;
-                                data.clone()
-                            } else if let Playback::Input(ref data) = playback.0 {
-                                data.clone()
-                            } else if let Playback::Generic(ref data) = playback.0 {
-                                data.clone()
-                            } else {
-                                SnapShot::new()
-                            };
-                            while start.elapsed() < length {
-                                // Loops while the time spent playing is less than the length of the audio
-                                match audio_receiver.try_recv() {
-                                    // Blocks until a file, stop, or playback message is received
-                                    Ok(Message::StopAudio) => {
-                                        if capturing {
-                                            snapshot.frames.remove(0);
-                                            match snapshot.save(&File::truncate(&mut file.clone(), ".", 0)) // Saves new snapshot data to file if capturing
-                                            {
-                                                Some(error) => {
-                                                    Tracker::write(
-                                                        player_error_handle.clone(),
-                                                        Some(error),
-                                                    );
-                                                }
-                                                None => (),
-                                            };
-                                        }
-                                        continue 'two; // Stops audio
-                                    }
-                                    Ok(Message::File(_)) => {
-                                        if capturing {
-                                            snapshot.frames.remove(0);
-                                            match snapshot.save(&File::truncate(
-                                                &mut file.clone(),
-                                                ".",
-                                                0,
-                                            )) {
-                                                Some(error) => {
-                                                    Tracker::write(
-                                                        player_error_handle.clone(),
-                                                        Some(error),
-                                                    );
-                                                }
-                                                None => (),
-                                            };
-                                        }
-                                        continue 'one; // Loads new audio data
-                                    }
-                                    Ok(Message::PlayAudio((Playback::Capture(_), _))) => {
-                                        if capturing {
-                                            snapshot.frames.remove(0);
-                                            match snapshot.save(&File::truncate(
-                                                &mut file.clone(),
-                                                ".",
-                                                0,
-                                            )) {
-                                                Some(error) => {
-                                                    Tracker::write(
-                                                        player_error_handle.clone(),
-                                                        Some(error),
-                                                    );
-                                                }
-                                                None => (),
-                                            };
-                                        }
-                                        continue 'two; // Stops playing
-                                    }
-                                    Ok(Message::PlayAudio((value, _))) => {
-                                        // Changes type of playback
-                                        playback.0 = value;
-                                        if let Playback::Input(ref frames) = playback.0 {
-                                            snapshot = frames.clone();
-                                            Tracker::write(
-                                                player_frame_handle.clone(),
-                                                if snapshot.frames.len() < edited_frame {
-                                                    snapshot.frames[edited_frame].0
-                                                } else {
-                                                    snapshot.frames[snapshot.frames.len() - 1].0
-                                                },
-                                            );
-                                        }
-                                    }
-                                    _ => (),
-                                }
-                                if let Playback::Input(_) = playback.0 {
-                                    // If playback type equals input playback
-                                    if edited_frame < snapshot.frames.len() {
-                                        if frame == snapshot.frames[edited_frame].1 as usize {
-                                            // If current frame is the same as the one saved in the the snapshot data
-                                            Tracker::write(
-                                                player_frame_handle.clone(),
-                                                snapshot.frames[edited_frame].0,
-                                            ); // Write dial data
-                                               // Set the handle values to edit the audio based on snapshot data
-                                            sub_bass_handle.set_gain(
-                                                if snapshot.frames[edited_frame].0[0] == -7 {
-                                                    -60.0 // Make silent if value is -7
-                                                } else {
-                                                    snapshot.frames[edited_frame].0[0] as f32 * 4.0
-                                                    // Multiply dial value by 4 to hear a difference
-                                                },
-                                                Tween::default(),
-                                            );
-                                            bass_handle.set_gain(
-                                                if snapshot.frames[edited_frame].0[1] == -7 {
-                                                    -60.0
-                                                } else {
-                                                    snapshot.frames[edited_frame].0[1] as f32 * 4.0
-                                                },
-                                                Tween::default(),
-                                            );
-                                            low_mids_handle.set_gain(
-                                                if snapshot.frames[edited_frame].0[2] == -7 {
-                                                    -60.0
-                                                } else {
-                                                    snapshot.frames[edited_frame].0[2] as f32 * 4.0
-                                                },
-                                                Tween::default(),
-                                            );
-                                            high_mids_handle.set_gain(
-                                                if snapshot.frames[edited_frame].0[3] == -7 {
-                                                    -60.0
-                                                } else {
-                                                    snapshot.frames[edited_frame].0[3] as f32 * 4.0
-                                                },
-                                                Tween::default(),
-                                            );
-                                            treble_handle.set_gain(
-                                                if snapshot.frames[edited_frame].0[4] == -7 {
-                                                    -60.0
-                                                } else {
-                                                    snapshot.frames[edited_frame].0[4] as f32 * 4.0
-                                                },
-                                                Tween::default(),
-                                            );
-                                            panning_handle.set_panning(
-                                                snapshot.frames[edited_frame].0[5] as f32 * 0.15, // Multiply panning by 0.15 as panning is more sensitive to changes
-                                                Tween::default(),
-                                            );
-                                        }
-                                    }
-                                } else {
-                                    let settings = player_settings_handle.read().unwrap();
-
-                                    if let Playback::Capture(_) = playback.0 {
-                                        // If capturing inputs
-                                        if SnapShot::edited(
-                                            // Checks if a change has been made to the dials since the last change
-                                            previous_frame,
-                                            Recording::parse(&settings.recordings[playback.1]),
-                                        ) {
-                                            snapshot.frames.push((
-                                                // Pushes new values to list
-                                                Recording::parse(&settings.recordings[playback.1]),
-                                                frame as i32,
-                                            ));
-                                            previous_frame = snapshot.frames[edited_frame].0; // Updates the previous frame for next check
-                                            edited_frame += 1;
-                                        }
-                                    }
+        let notifier = notifier.clone();
 
-                                    // Set the handle values based on settings
-                                    sub_bass_handle.set_gain(
-                                        if settings.recordings[playback.1].sub_bass == -7 {
-                                            -60.0
-                                        } else {
-                                            settings.recordings[playback.1].sub_bass as f32 * 4.0
-                                        },
-                                        Tween::default(),
-                                    );
-                                    bass_handle.set_gain(
-                                        if settings.recordings[playback.1].bass == -7 {
-                                            -60.0
-                                        } else {
-                                            settings.recordings[playback.1].bass as f32 * 4.0
-                                        },
-                                        Tween::default(),
-                                    );
-                                    low_mids_handle.set_gain(
-                                        if settings.recordings[playback.1].low_mids == -7 {
-                                            -60.0
-                                        } else {
-                                            settings.recordings[playback.1].low_mids as f32 * 4.0
-                                        },
-                                        Tween::default(),
-                                    );
-                                    high_mids_handle.set_gain(
-                                        if settings.recordings[playback.1].high_mids == -7 {
-                                            -60.0
-                                        } else {
-                                            settings.recordings[playback.1].high_mids as f32 * 4.0
-                                        },
-                                        Tween::default(),
-                                    );
-                                    treble_handle.set_gain(
-                                        if settings.recordings[playback.1].treble == -7 {
-                                            -60.0
-                                        } else {
-                                            settings.recordings[playback.1].treble as f32 * 4.0
-                                        },
-                                        Tween::default(),
-                                    );
-                                    panning_handle.set_panning(
-                                        settings.recordings[playback.1].pan as f32 * 0.15,
-                                        Tween::default(),
-                                    );
-
-                                    drop(settings); // Drop read access of settings
-                                }
+        let saved_handle = tracker.saved.clone();
 
-                                if !capturing {
-                                    // Increases edited frame if equal to snapshot data so it remains in sync if you swap playback type
-                                    if frame
-                                        == snapshot.frames[if edited_frame < snapshot.frames.len() {
-                                            edited_frame
-                                        } else {
-                                            edited_frame - 1
-                                        }]
-                                        .1 as usize
-                                    {
-                                        edited_frame += 1;
-                                    }
-                                }
-                                frame += 1;
+        let settings_handle = tracker.settings.clone();
 
-                                thread::sleep(Duration::from_millis(20)); // Sleeps thread for 20 milliseconds
-                            }
+        move || {
+            let ui = ui_handle.unwrap();
 
-                            Tracker::write(player_finished.clone(), true); // Tells the tracker that playback is finished
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let before = settings.recording_names();
+                let (renamed, error) =
+                    Recording::renumber_fallbacks(&Disk, &mut settings.recordings);
+                settings.follow_renames(&before);
 
-                            if capturing {
-                                // Saves captured inputs to file
-                                match snapshot.save(&File::truncate(&mut file.clone(), ".", 0)) {
-                                    Some(error) => {
-                                        Tracker::write(player_error_handle.clone(), Some(error));
-                                    }
-                                    None => (),
-                                };
-                            }
-                        }
-                        Ok(Message::StopAudio) => continue 'two, // Waits to play again
-                        _ => {
-                            Tracker::write(player_error_handle.clone(), Some(Error::MessageError)); // Writes error if incorrect message sent to thread
-                            continue 'two;
-                        }
+                match Tracker::save_settings(&settings, saved_handle.clone()) {
+                    Some(error) => {
+                        error.send(&ui);
                     }
+                    None => match error {
+                        Some(error) => error.send(&ui),
+                        None if renamed > 0 => Warning::FallbacksRenamed.send(&ui),
+                        None => (),
+                    },
                 }
             }
-        }) {
-        Ok(_) => (),
-        Err(_) => {
-            Tracker::write(errors.clone(), Some(Error::PlayerThreadError));
+
+            ui.invoke_update(); // Updates UI
         }
-    };
+    });
 
-    // Update callback
-    ui.on_update({
+    // Takes a noise profile from the current recording
+    ui.on_capture_noise_profile({
         let ui_handle = ui.as_weak();
 
-        let startup_ref_count = tracker.settings.clone();
+        let settings_handle = tracker.settings.clone();
 
-        let error_handle = errors.clone();
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let recording = match settings_handle
+                .read()
+                .unwrap()
+                .recordings
+                .get(ui.get_current_recording() as usize)
+            {
+                Some(recording) => recording.clone(),
+                None => return,
+            };
+            match NoiseProfile::capture(&recording) {
+                Ok(profile) => match profile.save() {
+                    Some(error) => error.send(&ui),
+                    None => {
+                        ui.set_noise_profile_source(SharedString::from(profile.source));
+                        Warning::ProfileCaptured.send(&ui);
+                    }
+                },
+                Err(error) => error.send(&ui),
+            }
+        }
+    });
+
+    // Writes a copy of the current recording with the profiled noise taken out
+    ui.on_denoise_current({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            match Tracker::read(error_handle.clone()) {
-                // Checks for errors
-                Some(error) => {
+            let profile = match NoiseProfile::load() {
+                Ok(value) => value,
+                Err(error) => {
                     error.send(&ui);
-                    Tracker::write(error_handle.clone(), None);
+                    return;
                 }
-                None => {}
             };
 
-            if ui.get_started() {
-                // Syncs settings data on initial load
-                // Acquires write access to the loaded data
-                let mut settings = startup_ref_count.write().unwrap();
-                settings.sync(&ui);
+            {
+                let settings = settings_handle.read().unwrap();
+                let name = match settings.recordings.get(ui.get_current_recording() as usize) {
+                    Some(recording) => recording.name.clone(),
+                    None => return,
+                };
+                match profile.denoise(&name, &settings.recordings) {
+                    Ok(_) => Warning::Denoised.send(&ui),
+                    Err(error) => error.send(&ui),
+                }
             }
 
-            // Aquires read access to the loaded data
-            let settings = startup_ref_count.read().unwrap();
-
-            let index_data = settings.get_index_data();
+            ui.invoke_update(); // Picks up the new copy
+        }
+    });
 
-            // Sends a list of preset names to the ui to be displayed
-            ui.set_preset_names(Preset::send_names(
-                &settings.presets,
-                &index_data.preset_length,
-            ));
+    // Cuts the current recording down to its loop region, either replacing it or adding a cropped copy
+    ui.on_crop_current({
+        let ui_handle = ui.as_weak();
 
-            // Sends a nested list of preset values to the ui to be displayed
-            ui.set_preset_values(Preset::send_values(
-                &settings.presets,
-                &index_data.preset_length,
-            ));
+        let notifier = notifier.clone();
 
-            // Sends recording names to the ui to be displayed
-            ui.set_recording_names(Recording::send_names(&settings.recordings));
+        let settings_handle = tracker.settings.clone();
 
-            // Sends recording values to the ui to be displayed
-            if !ui.get_locked() {
-                ui.set_recording_values(Recording::send_values(
-                    &settings.recordings,
-                    &index_data.recording_length,
-                ));
-            }
+        move |keep_original| {
+            let ui = ui_handle.unwrap();
 
-            if ui.get_current_recording() < settings.recordings.len() as i32 {
-                // Sets dial values to current recording data
-                if settings.recordings.len() > 0 {
-                    ui.set_current_dial_values(ModelRc::new(VecModel::from(
-                        settings.recordings[ui.get_current_recording() as usize]
-                            .parse_vec_from_recording(),
-                    )));
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let index = ui.get_current_recording() as usize;
+                let recording = match settings.recordings.get(index) {
+                    Some(value) => value.clone(),
+                    None => return,
+                };
+                match recording.crop(&settings.recordings, keep_original) {
+                    Ok(_) => {
+                        if !keep_original {
+                            // The old points were on the uncut timeline
+                            let recording = &mut settings.recordings[index];
+                            recording.loop_start = None;
+                            recording.loop_end = None;
+                            recording.start_offset = 0.0;
+                            recording.send_current(&ui);
+                        }
+                        Warning::Cropped.send(&ui);
+                    }
+                    Err(error) => {
+                        error.send(&ui);
+                        return;
+                    }
                 }
             }
+
+            ui.invoke_save();
+            ui.invoke_update(); // Picks up a cropped copy
         }
     });
 
-    // Updates locked values
-    ui.on_update_locked_values({
+    // Splits the current recording in two where playback is, adding both parts after it
+    ui.on_split_current({
         let ui_handle = ui.as_weak();
 
+        let notifier = notifier.clone();
+
         let settings_handle = tracker.settings.clone();
 
-        let locked_handle = tracker.locked.clone();
+        let position_handle = tracker.position.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            let settings = settings_handle.read().unwrap();
-
-            let mut locked = locked_handle.write().unwrap();
-
-            if settings.recordings.len() > 0 {
-                // Sets locked vales to current recording data
-                ui.set_dial_values_when_locked(Recording::send_values(
-                    &settings.recordings,
-                    &settings.get_index_data().recording_length,
-                ));
-                // Sets tracker locked values
-                *locked = settings.recordings[ui.get_current_recording() as usize].clone();
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let index = ui.get_current_recording() as usize;
+                let recording = match settings.recordings.get(index) {
+                    Some(value) => value.clone(),
+                    None => return,
+                };
+                let at = Tracker::read(position_handle.clone());
+                match recording.split(at, &settings.recordings) {
+                    Ok(parts) => {
+                        // Added straight away so they keep the original's dials instead of the defaults
+                        for (offset, name) in parts.iter().enumerate() {
+                            settings
+                                .recordings
+                                .insert(index + 1 + offset, recording.part(name));
+                        }
+                        Warning::Split.send(&ui);
+                    }
+                    Err(error) => {
+                        error.send(&ui);
+                        return;
+                    }
+                }
             }
+
+            ui.invoke_save();
+            ui.invoke_update();
         }
     });
 
-    // Syncs UI and settings with current locked values
-    ui.on_sync_with_locked_values({
+    // Shows the current recording in the system file manager
+    ui.on_reveal_recording({
         let ui_handle = ui.as_weak();
 
         let settings_handle = tracker.settings.clone();
 
-        let locked_handle = tracker.locked.clone();
-
         move || {
             let ui = ui_handle.unwrap();
 
-            let mut settings = settings_handle.write().unwrap();
+            let name = match settings_handle
+                .read()
+                .unwrap()
+                .recordings
+                .get(ui.get_current_recording() as usize)
+            {
+                Some(recording) => recording.name.clone(),
+                None => String::new(), // Opens the data folder
+            };
+            match File::reveal(&name) {
+                Some(warning) => warning.send(&ui),
+                None => (),
+            }
+        }
+    });
 
-            let locked = locked_handle.read().unwrap();
+    // Uses a sound file for the metronome click if it can be loaded
+    ui.on_choose_click_file({
+        let ui_handle = ui.as_weak();
 
-            // Sets settings data to locked values
-            settings.recordings[ui.get_current_recording() as usize].sub_bass = locked.sub_bass;
-            settings.recordings[ui.get_current_recording() as usize].bass = locked.bass;
-            settings.recordings[ui.get_current_recording() as usize].low_mids = locked.low_mids;
-            settings.recordings[ui.get_current_recording() as usize].high_mids = locked.high_mids;
-            settings.recordings[ui.get_current_recording() as usize].treble = locked.treble;
-            settings.recordings[ui.get_current_recording() as usize].pan = locked.pan;
+        let notifier = notifier.clone();
 
-            // Sets dials to locked values
-            if settings.recordings.len() > 0 {
-                ui.set_current_dial_values(ModelRc::new(VecModel::from(
-                    settings.recordings[ui.get_current_recording() as usize]
-                        .parse_vec_from_recording(),
-                )));
+        let settings_handle = tracker.settings.clone();
+
+        move |path| {
+            let ui = ui_handle.unwrap();
+
+            let click = ClickSound::Custom(path.trim().to_string());
+            match click.sounds() {
+                Ok(_) => {
+                    notifier.edit(&settings_handle).click_sound = click;
+                    ui.invoke_save();
+                }
+                Err(error) => error.send(&ui),
             }
         }
     });
 
-    // Saves settings to file and memory
-    ui.on_save({
+    // Writes a copy of the current recording to the export folder in the chosen format
+    ui.on_export_current({
         let ui_handle = ui.as_weak();
 
-        let update_ref_count = tracker.settings.clone();
+        let settings_handle = tracker.settings.clone();
 
-        let empty = tracker.empty_recording.clone();
+        move || {
+            let ui = ui_handle.unwrap();
 
-        let just_recorded = tracker.recording_check.clone();
+            let settings = settings_handle.read().unwrap();
+            let name = match settings.recordings.get(ui.get_current_recording() as usize) {
+                Some(recording) => recording.name.clone(),
+                None => return,
+            };
+            let (directory, fell_back) = match File::export_directory(&settings.export_directory) {
+                Ok(value) => value,
+                Err(error) => {
+                    error.send(&ui);
+                    return;
+                }
+            };
+            match export(&name, &directory, settings.export_format) {
+                Ok(_) if fell_back => Warning::ExportFallback.send(&ui),
+                Ok(_) => Warning::Exported.send(&ui),
+                Err(error) => error.send(&ui),
+            }
+        }
+    });
 
-        move || {
+    // Moves a preset that was dragged up or down the list
+    ui.on_move_preset({
+        let ui_handle = ui.as_weak();
+
+        let settings_handle = tracker.settings.clone();
+
+        move |from, to| {
             let ui = ui_handle.unwrap();
 
-            // Skips if an empty recording was just created
-            if Tracker::read(empty.clone()) && Tracker::read(just_recorded.clone()) {
-                Tracker::write(just_recorded.clone(), false);
+            if from < 0 {
                 return;
             }
+            let moved = settings_handle
+                .write()
+                .unwrap()
+                .move_preset(from as usize, to.max(0) as usize);
+            if moved {
+                ui.invoke_save();
+            }
+        }
+    });
 
-            // This block is used to drop the write lock on the stored data as soon as the last write is completed
-            // This frees it to be used in the function called underneath and in any threads where it is needed
-            {
-                // Acquires write access to the loaded data
-                let mut settings = update_ref_count.write().unwrap();
-                settings.sync(&ui); // Syncs settings data
+    // Adds the presets from another settings file or a shared presets file
+    ui.on_import_presets({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move |path| {
+            let ui = ui_handle.unwrap();
+
+            let imported = notifier.edit(&settings_handle).import_presets(path.trim());
+            match imported {
+                Ok(_) => {
+                    ui.set_preset_import_path(SharedString::new());
+                    ui.invoke_save();
+                    Warning::PresetsImported.send(&ui);
+                }
+                Err(error) => error.send(&ui),
             }
+        }
+    });
 
-            ui.invoke_update(); // Updates UI
+    // Writes the presets to a file that other apps can read
+    ui.on_export_presets({
+        let ui_handle = ui.as_weak();
 
-            // Aquires read access to the loaded data
-            let settings = update_ref_count.read().unwrap();
-            // Save data if not locked or recording inputs
-            if !ui.get_locked() && !ui.get_input_recording() {
-                match save(DataType::Settings((*settings).clone()), "settings") {
-                    Some(error) => {
-                        error.send(&ui);
-                    }
-                    None => {}
+        let settings_handle = tracker.settings.clone();
+
+        move |path| {
+            let ui = ui_handle.unwrap();
+
+            let exported = settings_handle.read().unwrap().export_presets(path.trim());
+            match exported {
+                Ok(_) => {
+                    ui.set_preset_export_path(SharedString::new());
+                    Warning::PresetsExported.send(&ui);
                 }
+                Err(error) => error.send(&ui),
             }
         }
     });
 
-    // Starts and stops recordings
-    ui.on_record({
+    // Adds the current recording to the end of the queue
+    ui.on_queue_current({
         let ui_handle = ui.as_weak();
 
-        let sender_handle = record_sender.clone();
+        let notifier = notifier.clone();
 
-        let error_handle = errors.clone();
+        let settings_handle = tracker.settings.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            match sender_handle.send(if ui.get_recording() {
-                // Sends message to recording thread
-                // Sends stop message and updates UI
-                ui.set_recording(false);
-                Message::StopRecording
-            } else {
-                // Sends start message and updates UI
-                ui.set_recording(true);
-                Message::StartRecording
-            }) {
-                Ok(_) => (),
-                Err(_) => {
-                    Tracker::write(error_handle.clone(), Some(Error::MessageError));
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let current = settings
+                    .recordings
+                    .get(ui.get_current_recording() as usize)
+                    .map(|recording| recording.name.clone());
+                if let Some(name) = current {
+                    settings.queue.push(name);
                 }
+                ui.set_queue_summary(settings.queue_summary());
             }
-            if !ui.get_recording() {
-                // If UI not recording then save and shuffle songs
-                ui.invoke_save();
-                ui.invoke_gen_shuffle();
+
+            ui.invoke_save();
+        }
+    });
+
+    // Empties the queue
+    ui.on_clear_queue({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                settings.queue.clear();
+                ui.set_queue_summary(settings.queue_summary());
             }
+
+            ui.invoke_save();
         }
     });
 
-    // Deletes recordings
-    ui.on_delete_recordings({
+    // Converts recordings found in the folder to the library's channel count, or leaves them as they are
+    ui.on_convert_imports({
         let ui_handle = ui.as_weak();
 
-        move || {
+        let notifier = notifier.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move |convert| {
             let ui = ui_handle.unwrap();
 
-            match File::delete(String::from(ui.get_deleted_recording_name())) {
-                // Deletes recordings
-                Some(error) => {
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                for error in settings.convert_imports(convert) {
                     error.send(&ui);
                 }
-                None => {}
-            };
+                ui.set_unconverted_summary(settings.unconverted_summary());
+                if let Some(recording) =
+                    settings.recordings.get(ui.get_current_recording() as usize)
+                {
+                    recording.send_current(&ui);
+                }
+            }
 
-            ui.invoke_save(); // Saves changes
+            ui.invoke_save();
         }
     });
 
-    // Skips song
-    ui.on_skip_audio({
+    // Joins the queued recordings in order into a new recording added after the first of them
+    ui.on_merge_queue({
         let ui_handle = ui.as_weak();
 
-        let error_handle = errors.clone();
-
-        let sender_handle = audio_sender.clone();
+        let notifier = notifier.clone();
 
         let settings_handle = tracker.settings.clone();
 
-        let preloaded_handle = tracker.preloaded.clone();
-
         move || {
             let ui = ui_handle.unwrap();
 
-            let settings = settings_handle.read().unwrap();
-
-            Tracker::write(preloaded_handle.clone(), false); // Tells thread that nothing has been preloaded
-
-            let file = if settings.recordings.len() > 0 {
-                // Gets the name of the recording that should be played
-                settings.recordings[ui.get_current_recording() as usize]
-                    .name
-                    .clone()
-            } else {
-                String::new()
-            };
-
-            let path = match File::get_directory() {
-                Ok(value) => value,
-                Err(error) => {
-                    error.send(&ui);
-                    String::new()
-                }
-            };
-
-            let snapshot_data = if settings.recordings.len() > 0 {
-                // Loads the snapshot data of that recording
-                match load(
-                    &settings.recordings[ui.get_current_recording() as usize].name,
-                    LoadType::Snapshot,
-                ) {
-                    Ok(DataType::SnapShot(data)) => data,
-                    _ => {
-                        Error::LoadError.send(&ui);
-                        SnapShot::new()
-                    }
-                }
-            } else {
-                SnapShot::new()
-            };
-
-            if settings.recordings.len() > 0 {
-                for _ in 0..if ui.get_starting_threads() {
-                    // If threads are starting for the first time only send load messgae once, otherwise twice
-                    ui.set_starting_threads(false);
-                    1
-                } else {
-                    2
-                } {
-                    match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
-                        // Sends load message and file path
-                        Ok(_) => (),
-                        Err(_) => {
-                            Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
-                        }
-                    }
-                }
-                if ui.get_audio_playback() {
-                    // If already generic playing
-                    match sender_handle.send(Message::PlayAudio((
-                        // Sends message to play new recording as a generic playback along with snapshot data
-                        Playback::Generic(snapshot_data),
-                        ui.get_current_recording() as usize,
-                    ))) {
-                        Ok(_) => (),
-                        Err(_) => {
-                            Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
-                        }
-                    }
-                } else if ui.get_input_playback() {
-                    // If already input playback
-                    match sender_handle.send(Message::PlayAudio((
-                        // Sends message to play new recordings input data along with its snapshot data
-                        Playback::Input(snapshot_data),
-                        ui.get_current_recording() as usize,
-                    ))) {
-                        Ok(_) => (),
-                        Err(_) => {
-                            Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let names: Vec<String> = settings
+                    .queue
+                    .iter()
+                    .filter(|queued| File::exists((*queued).clone(), &settings.recordings))
+                    .cloned()
+                    .collect();
+                match merge(&names, &settings.recordings) {
+                    Ok(name) => {
+                        let first = settings
+                            .recordings
+                            .iter()
+                            .position(|recording| recording.name == names[0]);
+                        if let Some(index) = first {
+                            // Takes the dial settings of the first recording as its automation starts the new one
+                            let merged = settings.recordings[index].part(&name);
+                            settings.recordings.insert(index + 1, merged);
                         }
+                        Warning::Merged.send(&ui);
                     }
-                } else if ui.get_input_recording() {
-                    // If recording inputs
-                    for _ in 0..2 {
-                        let snapshot_data = SnapShot::new(); // Send message to record inputs twice
-                        match sender_handle.send(Message::PlayAudio((
-                            Playback::Capture(snapshot_data),
-                            ui.get_current_recording() as usize,
-                        ))) {
-                            Ok(_) => (),
-                            Err(_) => {
-                                Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
-                            }
-                        }
+                    Err(error) => {
+                        error.send(&ui);
+                        return;
                     }
                 }
             }
+
+            ui.invoke_save();
+            ui.invoke_update();
         }
     });
 
-    // On generic playback
-    ui.on_play_generic({
+    // Sets or clears the current recording's own playback type
+    ui.on_set_playback_override({
         let ui_handle = ui.as_weak();
 
-        let error_handle = errors.clone();
-
-        let sender_handle = audio_sender.clone();
+        let notifier = notifier.clone();
 
         let settings_handle = tracker.settings.clone();
 
-        let preloaded_handle = tracker.preloaded.clone();
-
-        move || {
+        move |set, playback| {
             let ui = ui_handle.unwrap();
 
-            let settings = settings_handle.read().unwrap();
-
-            let snapshot_data = match load(
-                // Load snapshot data
-                &settings.recordings[ui.get_current_recording() as usize].name,
-                LoadType::Snapshot,
-            ) {
-                Ok(DataType::SnapShot(data)) => data,
-                _ => {
-                    Error::LoadError.send(&ui);
-                    return;
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let position = ui.get_current_recording() as usize;
+                if position < settings.recordings.len() {
+                    settings.recordings[position].playback_override = if set {
+                        Some(PlaybackMode::from_ui(playback))
+                    } else {
+                        None
+                    };
                 }
-            };
+            }
 
-            if Tracker::read(preloaded_handle.clone()) {
-                () // Do nothing if data has been preloaded
-            } else {
-                // Load new data
-                let file = if settings.recordings.len() > 0 {
-                    settings.recordings[ui.get_current_recording() as usize]
-                        .name
-                        .clone()
-                } else {
-                    String::new()
-                };
+            ui.invoke_save();
+        }
+    });
 
-                let path = match File::get_directory() {
-                    Ok(value) => value,
-                    Err(error) => {
-                        error.send(&ui);
-                        String::new()
-                    }
-                };
+    // Sets the start or end of the current recording's loop region to where playback is now
+    ui.on_set_loop_point({
+        let ui_handle = ui.as_weak();
 
-                match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
-                    Ok(_) => (),
-                    Err(_) => {
-                        Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
+        let notifier = notifier.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let position_handle = tracker.position.clone();
+
+        move |start| {
+            let ui = ui_handle.unwrap();
+
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let position = ui.get_current_recording() as usize;
+                if let Some(recording) = settings.recordings.get_mut(position) {
+                    let point = Some(Tracker::read(position_handle.clone()).as_secs_f32());
+                    if start {
+                        recording.loop_start = point;
+                    } else {
+                        recording.loop_end = point;
                     }
+                    ui.set_current_loop(recording.loop_summary());
                 }
             }
 
-            match sender_handle.send(if ui.get_audio_playback() {
-                // Send message to start and stop playback and update UI accordingly
-                ui.set_audio_playback(false);
-                ui.set_input_playback(false);
-                ui.set_input_recording(false);
-                Message::StopAudio
-            } else {
-                ui.set_audio_playback(true);
-                ui.set_input_playback(false);
-                ui.set_input_recording(false);
-                Message::PlayAudio((
-                    Playback::Generic(snapshot_data),
-                    ui.get_current_recording() as usize,
-                ))
-            }) {
-                Ok(_) => (),
-                Err(_) => {
-                    Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
+            ui.invoke_save();
+        }
+    });
+
+    // Sets where the current recording starts playing from to where playback is now, or back to the beginning
+    ui.on_set_start_offset({
+        let ui_handle = ui.as_weak();
+
+        let notifier = notifier.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        let position_handle = tracker.position.clone();
+
+        move |here| {
+            let ui = ui_handle.unwrap();
+
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let position = ui.get_current_recording() as usize;
+                if let Some(recording) = settings.recordings.get_mut(position) {
+                    recording.start_offset = if here {
+                        Tracker::read(position_handle.clone()).as_secs_f32()
+                    } else {
+                        0.0
+                    };
+                    ui.set_current_start(recording.start_summary());
                 }
             }
 
-            if settings.recordings.len() > 0 {
-                ui.set_current_dial_values(ModelRc::new(VecModel::from(
-                    settings.recordings[ui.get_current_recording() as usize]
-                        .parse_vec_from_recording(),
-                )));
-            }
+            ui.invoke_save();
         }
     });
 
-    // Input playback
-    ui.on_play_captured_inputs({
+    // Skips the silence at the top of the current recording by finding where its sound starts
+    ui.on_trim_start_silence({
         let ui_handle = ui.as_weak();
 
+        let notifier = notifier.clone();
+
         let settings_handle = tracker.settings.clone();
 
-        let dials = tracker.snapshot_frame_values.clone();
+        move || {
+            let ui = ui_handle.unwrap();
 
-        let error_handle = errors.clone();
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let position = ui.get_current_recording() as usize;
+                if let Some(recording) = settings.recordings.get_mut(position) {
+                    match detect_start_offset(&recording.name) {
+                        Ok(value) => {
+                            recording.start_offset = value;
+                            ui.set_current_start(recording.start_summary()); // Shown so it can be moved with Set start here
+                        }
+                        Err(error) => {
+                            error.send(&ui);
+                            return;
+                        }
+                    }
+                }
+            }
 
-        let sender_handle = audio_sender.clone();
+            ui.invoke_save();
+        }
+    });
+
+    // Hides recordings whose names don't match the search
+    ui.on_filter_recordings({
+        let ui_handle = ui.as_weak();
 
-        let preloaded_handle = tracker.preloaded.clone();
+        let settings_handle = tracker.settings.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            let settings = settings_handle.read().unwrap();
-
-            let snapshot_data = match load(
-                // Load snapshot data
-                &settings.recordings[ui.get_current_recording() as usize].name,
-                LoadType::Snapshot,
-            ) {
-                Ok(DataType::SnapShot(data)) => data,
-                _ => {
-                    Error::LoadError.send(&ui);
-                    return;
-                }
-            };
+            ui.set_recording_hidden(Recording::send_hidden(
+                &settings_handle.read().unwrap().recordings,
+                &ui.get_recording_search(),
+            ));
+        }
+    });
 
-            Tracker::write(
-                dials.clone(),
-                Recording::parse(&settings.recordings[ui.get_current_recording() as usize]),
-            );
+    // Moves the current recording on to the next colour label
+    ui.on_cycle_colour({
+        let ui_handle = ui.as_weak();
 
-            if Tracker::read(preloaded_handle.clone()) {
-                ()
-            } else {
-                let file = if settings.recordings.len() > 0 {
-                    settings.recordings[ui.get_current_recording() as usize]
-                        .name
-                        .clone()
-                } else {
-                    String::new()
-                };
+        let notifier = notifier.clone();
 
-                let path = match File::get_directory() {
-                    Ok(value) => value,
-                    Err(error) => {
-                        error.send(&ui);
-                        String::new()
-                    }
-                };
+        let settings_handle = tracker.settings.clone();
 
-                match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
-                    Ok(_) => (),
-                    Err(_) => {
-                        Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
-                    }
-                }
-            }
+        move || {
+            let ui = ui_handle.unwrap();
 
-            match sender_handle.send(if ui.get_input_playback() {
-                ui.set_audio_playback(false);
-                ui.set_input_playback(false);
-                ui.set_input_recording(false);
-                Message::StopAudio
-            } else {
-                ui.set_input_playback(true);
-                ui.set_audio_playback(false);
-                ui.set_input_recording(false);
-                Message::PlayAudio((
-                    Playback::Input(snapshot_data),
-                    ui.get_current_recording() as usize,
-                ))
-            }) {
-                Ok(_) => (),
-                Err(_) => {
-                    Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let position = ui.get_current_recording() as usize;
+                if let Some(recording) = settings.recordings.get_mut(position) {
+                    recording.colour = recording.colour.next();
                 }
             }
+
+            ui.invoke_save();
+            ui.invoke_update(); // Updates UI
         }
     });
 
-    // Record inputs
-    ui.on_capture_inputs({
+    // Goes back to looping the whole of the current recording
+    ui.on_clear_loop_points({
         let ui_handle = ui.as_weak();
 
-        let error_handle = errors.clone();
-
-        let sender_handle = audio_sender.clone();
+        let notifier = notifier.clone();
 
         let settings_handle = tracker.settings.clone();
 
-        let preloaded_handle = tracker.preloaded.clone();
-
         move || {
             let ui = ui_handle.unwrap();
 
-            let snapshot_data = SnapShot::new();
+            {
+                let mut settings = notifier.edit(&settings_handle);
+                let position = ui.get_current_recording() as usize;
+                if let Some(recording) = settings.recordings.get_mut(position) {
+                    recording.loop_start = None;
+                    recording.loop_end = None;
+                    ui.set_current_loop(recording.loop_summary());
+                }
+            }
 
-            let settings = settings_handle.read().unwrap();
+            ui.invoke_save();
+        }
+    });
 
-            if Tracker::read(preloaded_handle.clone()) {
-                ()
-            } else {
-                let file = if settings.recordings.len() > 0 {
-                    settings.recordings[ui.get_current_recording() as usize]
-                        .name
-                        .clone()
-                } else {
-                    String::new()
-                };
+    // Plays the current recording again from the beginning, along with its automation
+    ui.on_restart_audio({
+        let ui_handle = ui.as_weak();
 
-                let path = match File::get_directory() {
-                    Ok(value) => value,
-                    Err(error) => {
-                        error.send(&ui);
-                        String::new()
-                    }
-                };
+        let notifier = notifier.clone();
 
-                match sender_handle.send(Message::File(format!("{}/{}.wav", path, file))) {
+        let sender_handle = audio_sender.clone();
+
+        let error_handle = errors.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            if ui.get_audio_playback() || ui.get_input_playback() {
+                match sender_handle.send(Message::Seek(Duration::ZERO)) {
                     Ok(_) => (),
-                    Err(_) => {
-                        Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
+                    Err(error) => {
+                        notifier.write(
+                            error_handle.clone(),
+                            Some(Error::PlaybackError(error.to_string())),
+                        );
                     }
                 }
             }
-
-            match sender_handle.send(if ui.get_input_playback() {
-                ui.set_input_recording(false);
-                ui.set_audio_playback(false);
-                ui.set_input_playback(false);
-                ui.set_locked(false);
-                Message::StopAudio
-            } else {
-                ui.set_input_recording(true);
-                ui.set_audio_playback(false);
-                ui.set_input_playback(false);
-                Message::PlayAudio((
-                    Playback::Capture(snapshot_data),
-                    ui.get_current_recording() as usize,
-                ))
-            }) {
-                Ok(_) => (),
-                Err(_) => {
-                    Tracker::write(error_handle.clone(), Some(Error::PlaybackError));
-                }
-            }
         }
     });
 
-    // Update UI when playing is finished
-    ui.on_sync_playing_with_backend({
+    // Shows whether anything hasn't been written to disk yet
+    ui.on_sync_unsaved({
         let ui_handle = ui.as_weak();
 
-        let finished = tracker.playing.clone();
+        let settings_handle = tracker.settings.clone();
+
+        let saved_handle = tracker.saved.clone();
 
-        let sender_handle = audio_sender.clone();
+        move || {
+            let ui = ui_handle.unwrap();
+
+            // Comparing every setting is only worth it when something has been changed or saved since the last check
+            if SETTINGS_CHANGED.swap(false, AtomicOrdering::Relaxed) {
+                ui.set_unsaved_changes(Tracker::unsaved(&settings_handle, &saved_handle));
+            }
+        }
+    });
+
+    // Writes unsaved changes to disk, on the auto save interval or when asked
+    ui.on_save_unsaved({
+        let ui_handle = ui.as_weak();
 
         let settings_handle = tracker.settings.clone();
 
-        let error_handle = errors.clone();
+        let saved_handle = tracker.saved.clone();
+
+        let save_requested = tracker.save_requested.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            if Tracker::read(finished.clone()) {
-                // If finished playing
-                let settings = settings_handle.read().unwrap();
+            // Held values and captures in progress aren't meant to be saved yet
+            if ui.get_locked()
+                || ui.get_input_recording()
+                || !Tracker::unsaved(&settings_handle, &saved_handle)
+            {
+                return;
+            }
 
-                if ui.get_playback() == PlaybackType::None {
-                    // If playback type is set to stop playing at the end of the song
-                    // Update UI and do nothing
-                    if ui.get_input_playback() || ui.get_input_recording() {
-                        drop(settings);
-                        ui.invoke_sync_with_locked_values();
-                        ui.invoke_save();
-                    }
-                    ui.set_input_recording(false);
-                    ui.set_audio_playback(false);
-                    ui.set_input_playback(false);
-                } else if ui.get_playback() == PlaybackType::Loop
-                    || ui.get_playback() == PlaybackType::AutoNext
-                // If looping or auto skippng to next song
-                {
-                    match sender_handle.send(if ui.get_input_recording() {
-                        // Stop audio if recording inputs
-                        ui.set_input_recording(false);
-                        ui.set_audio_playback(false);
-                        ui.set_input_playback(false);
-                        drop(settings);
-                        ui.invoke_sync_with_locked_values();
-                        ui.invoke_save();
-                        Message::StopAudio
-                    } else {
-                        if ui.get_playback() == PlaybackType::AutoNext {
-                            // If auto skipping
-                            let settings = settings_handle.read().unwrap();
-                            // Skips to first recording if on last recording, otherwise skips to next recording
-                            // Also handles shuffle logic
-                            if ui.get_shuffle() && settings.get_index_data().recording_length > 2 {
-                                if ui.get_current_shuffle_index()
-                                    == (ui.get_shuffle_order().row_count() - 1) as i32
-                                {
-                                    // If on last index in shuffle list, reshuffle and set index to 0
-                                    ui.invoke_gen_shuffle();
-                                    ui.set_current_shuffle_index(0);
-                                } else {
-                                    ui.set_current_shuffle_index(
-                                        ui.get_current_shuffle_index() + 1,
-                                    ); // Otherwise increase shuffle index by one
-                                }
-                                ui.set_current_recording(
-                                    ui.get_shuffle_order()
-                                        .row_data(ui.get_current_shuffle_index() as usize)
-                                        .unwrap(),
-                                ); // Set current recording to shuffle index
-                            } else {
-                                if ui.get_current_recording()
-                                    == (settings.recordings.len() - 1) as i32
-                                {
-                                    ui.set_current_recording(0);
-                                } else {
-                                    ui.set_current_recording(ui.get_current_recording() + 1);
-                                }
-                            }
-                            // drop(settings);
-                            // ui.invoke_sync_with_locked_values();
-                            // ui.invoke_save();
-                            ui.invoke_skip_audio(); // Invokes skip callback
-                        }
-                        let snapshot_data = match load(
-                            // Load snapshot data
-                            &settings.recordings[ui.get_current_recording() as usize].name,
-                            LoadType::Snapshot,
-                        ) {
-                            Ok(DataType::SnapShot(data)) => data,
-                            _ => {
-                                Error::LoadError.send(&ui);
-                                SnapShot::new()
-                            }
-                        };
-                        Message::PlayAudio((
-                            // Send the correct play message to UI depending on what button has been pressed
-                            if ui.get_audio_playback() {
-                                Playback::Generic(snapshot_data)
-                            } else if ui.get_input_playback() {
-                                Playback::Input(snapshot_data)
-                            } else {
-                                Playback::Generic(snapshot_data)
-                            },
-                            ui.get_current_recording() as usize,
-                        ))
-                    }) {
-                        Ok(_) => (),
-                        Err(_) => {
-                            Tracker::write(error_handle.clone(), Some(Error::MessageError));
-                        }
-                    }
+            Tracker::write(save_requested.clone(), None); // Covers any pending dial edits
+            ui.set_save_pending(false);
+
+            let settings = settings_handle.read().unwrap();
+            match Tracker::save_settings(&settings, saved_handle.clone()) {
+                Some(error) => {
+                    error.send(&ui);
                 }
-                Tracker::write(finished.clone(), false);
+                None => {}
             }
+            drop(settings);
+
+            ui.set_unsaved_changes(Tracker::unsaved(&settings_handle, &saved_handle));
         }
     });
 
-    // Update dial values when playing back inputs
-    ui.on_snapshot_dial_update({
+    // Shows the playback output levels on the meter
+    ui.on_sync_levels({
         let ui_handle = ui.as_weak();
 
-        let dials = tracker.snapshot_frame_values.clone();
+        let levels_handle = tracker.levels.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            let dial_values = dials.read().unwrap();
-
-            ui.set_current_dial_values(ModelRc::new(VecModel::from(
-                Recording::parse_vec_from_list(*dial_values),
+            let levels = {
+                let mut shared = levels_handle.write().unwrap();
+                let levels = *shared;
+                shared.peak = [0.0, 0.0]; // Starts collecting the next peak
+                levels
+            };
+            ui.set_output_rms(ModelRc::new(VecModel::from(
+                levels.rms.map(meter_fill).to_vec(),
+            )));
+            ui.set_output_peak(ModelRc::new(VecModel::from(
+                levels.peak.map(meter_fill).to_vec(),
+            )));
+            ui.set_output_held(ModelRc::new(VecModel::from(
+                levels.held.map(meter_fill).to_vec(),
             )));
+            ui.set_output_clipped(levels.clipped());
         }
     });
 
-    // Check for any errors and update UI
-    ui.on_check_for_errors({
+    // Lets go of the highest level the meter has held on to
+    ui.on_reset_peak_hold({
         let ui_handle = ui.as_weak();
 
-        let error_handle = errors.clone();
-
-        let sender = audio_sender.clone();
-
-        let settings_handle = tracker.settings.clone();
+        let levels_handle = tracker.levels.clone();
 
         move || {
             let ui = ui_handle.unwrap();
 
-            let occured = Tracker::read(error_handle.clone());
-            match occured {
-                Some(error) => {
-                    match error {
-                        Error::MessageError => {
-                            // Reload audio if incorrect mesaage sent to thread
-                            // This ensures that it won't keep failing
-                            if ui.get_audio_or_input_playback() || ui.get_input_recording() {
-                                let settings = settings_handle.read().unwrap();
+            levels_handle.write().unwrap().held = [0.0, 0.0];
+            ui.set_output_held(ModelRc::new(VecModel::from(vec![0.0, 0.0])));
+            ui.set_output_clipped(false);
+        }
+    });
 
-                                let file = if settings.recordings.len() > 0 {
-                                    settings.recordings[ui.get_current_recording() as usize]
-                                        .name
-                                        .clone()
-                                } else {
-                                    String::new()
-                                };
+    // Flattens or restores every effect without changing any saved values
+    ui.on_set_bypass({
+        let bypass_handle = tracker.bypass.clone();
 
-                                let path = match File::get_directory() {
-                                    Ok(value) => value,
-                                    Err(error) => {
-                                        error.send(&ui);
-                                        String::new()
-                                    }
-                                };
-                                match sender.send(Message::File(format!("{}/{}.wav", path, file))) {
-                                    Ok(_) => (),
-                                    Err(_) => (),
-                                }
-                            }
-                        }
-                        Error::ReadError => {
-                            // Load new data
-                            let settings = settings_handle.read().unwrap();
-                            let file = if settings.recordings.len() > 0 {
-                                settings.recordings[ui.get_current_recording() as usize]
-                                    .name
-                                    .clone()
-                            } else {
-                                String::new()
-                            };
+        move |bypass| {
+            Tracker::write(bypass_handle.clone(), bypass);
+        }
+    });
 
-                            let path = match File::get_directory() {
-                                Ok(value) => value,
-                                Err(error) => {
-                                    error.send(&ui);
-                                    String::new()
-                                }
-                            };
+    // Shows how long the current recording has been going for
+    ui.on_sync_recording_elapsed({
+        let ui_handle = ui.as_weak();
 
-                            for _ in 0..2 {
-                                match sender.send(Message::File(format!("{}/{}.wav", path, file))) {
-                                    Ok(_) => (),
-                                    Err(_) => {
-                                        Tracker::write(
-                                            error_handle.clone(),
-                                            Some(Error::PlaybackError),
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        _ => (),
-                    }
-                    // Sets all playback UI variables to false and sends error to UI
-                    ui.set_recording(false);
-                    ui.set_audio_playback(false);
-                    ui.set_input_playback(false);
-                    ui.set_input_recording(false);
-                    error.send(&ui);
-                    Tracker::write(error_handle.clone(), None);
-                }
-                None => (),
-            }
+        let frames_handle = tracker.recorded_frames.clone();
+
+        let dropouts_handle = tracker.dropouts.clone();
+
+        let empty_handle = tracker.empty_recording.clone();
+
+        let settings_handle = tracker.settings.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            let seconds = Tracker::read(frames_handle.clone()) / RECORDING_SAMPLE_RATE as u64;
+            ui.set_recording_elapsed(SharedString::from(format!(
+                "{}:{:02}",
+                seconds / 60,
+                seconds % 60
+            )));
+            ui.set_recording_dropouts(Tracker::read(dropouts_handle.clone()) as i32);
+            // The recorder marks the recording as not empty once it hears sound, before that it only writes if trimming is off
+            ui.set_recording_writing(
+                !Tracker::read(empty_handle.clone())
+                    || !settings_handle.read().unwrap().trim_leading_silence,
+            );
         }
     });
 
@@ -2449,7 +2713,68 @@ fn main() -> Result<(), Box<dyn STDError>> {
         }
     });
 
-    ui.run()?; // Runs UI
+    // Remembers where the window was when it's closed
+    let closing_geometry = Arc::new(RwLock::new(None));
+    ui.window().on_close_requested({
+        let ui_handle = ui.as_weak();
+
+        let geometry_handle = closing_geometry.clone();
+
+        move || {
+            let ui = ui_handle.unwrap();
+
+            Tracker::write(
+                geometry_handle.clone(),
+                Some(WindowGeometry::of(ui.window())),
+            );
+            CloseRequestResponse::HideWindow
+        }
+    });
+
+    // Puts the window back where it was, moving it onto the primary display if the one it was on is gone
+    let restored = tracker.settings.read().unwrap().window;
+    if let Some(geometry) = restored {
+        geometry.apply_size(ui.window());
+    }
+    ui.show()?;
+    if let Some(geometry) = restored {
+        let display = |monitor: MonitorHandle| WindowGeometry {
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        };
+        match ui.window().with_winit_window(|window| {
+            (
+                window
+                    .available_monitors()
+                    .map(display)
+                    .collect::<Vec<WindowGeometry>>(),
+                window.primary_monitor().map(display),
+            )
+        }) {
+            Some((displays, primary)) => geometry
+                .clamped(&displays, primary)
+                .apply_position(ui.window()),
+            None => geometry.apply_position(ui.window()), // Displays can't be listed so it's trusted to still be on one
+        }
+    }
+    slint::run_event_loop()?; // Runs UI
+    ui.hide()?;
+
+    // Saves where the window was without writing anything else that was left unsaved
+    if let Some(geometry) = Tracker::read(closing_geometry.clone()) {
+        Tracker::edit(&tracker.settings).window = Some(geometry);
+        let mut saved = tracker.saved.read().unwrap().clone();
+        saved.window = Some(geometry);
+        let _ = Tracker::save_settings(&saved, tracker.saved.clone()); // The window is gone so there's nowhere to show an error
+    }
+
+    // Flushes any dial edits that were still waiting on the debounce when the window closed
+    if Tracker::read(tracker.save_requested.clone()).is_some() && !ui.get_locked() {
+        let settings = tracker.settings.read().unwrap();
+        let _ = Tracker::save_settings(&settings, tracker.saved.clone()); // The window is gone so there's nowhere to show an error
+    }
 
     Ok(()) // Returns Ok if Ok
 }
@@ -0,0 +1,2208 @@
+// Settings, presets, and recordings along with the options and defaults they're saved with
+
+// -------- Imports --------
+use crate::*; // Constants, errors, and the other modules through the crate root
+
+// -------- Enums --------
+// Filter shapes that a band can use, saved in place of EqFilterKind which can't be saved
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum FilterKind {
+    Bell,
+    LowShelf,
+    HighShelf,
+}
+
+impl FilterKind {
+    pub fn to_kira(self) -> EqFilterKind {
+        // Converts to the filter kind used for playback
+        match self {
+            FilterKind::Bell => EqFilterKind::Bell,
+            FilterKind::LowShelf => EqFilterKind::LowShelf,
+            FilterKind::HighShelf => EqFilterKind::HighShelf,
+        }
+    }
+
+    pub fn from_index(index: i32) -> FilterKind {
+        // Converts from the index the UI uses
+        match index {
+            1 => FilterKind::LowShelf,
+            2 => FilterKind::HighShelf,
+            _ => FilterKind::Bell,
+        }
+    }
+
+    pub fn to_index(self) -> i32 {
+        // Converts to the index the UI uses
+        match self {
+            FilterKind::Bell => 0,
+            FilterKind::LowShelf => 1,
+            FilterKind::HighShelf => 2,
+        }
+    }
+
+    pub fn send(list: [FilterKind; 5]) -> ModelRc<i32> {
+        // Sends filter kinds to the UI
+        ModelRc::new(VecModel::from(
+            list.iter()
+                .map(|kind| kind.to_index())
+                .collect::<Vec<i32>>(),
+        ))
+    }
+}
+
+pub fn default_filter_kinds() -> [FilterKind; 5] {
+    // The band shapes used before they could be changed
+    [
+        FilterKind::LowShelf,
+        FilterKind::Bell,
+        FilterKind::Bell,
+        FilterKind::Bell,
+        FilterKind::HighShelf,
+    ]
+}
+
+// What happens when a recording finishes, saved in place of PlaybackType which can't be saved
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum PlaybackMode {
+    AutoNext,
+    Loop,
+    None,
+}
+
+// Colour shown next to a recording's name to make it easier to find
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum ColourLabel {
+    None,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl ColourLabel {
+    pub fn next(self) -> ColourLabel {
+        // Steps through the colours, going back to none after the last one
+        match self {
+            ColourLabel::None => ColourLabel::Red,
+            ColourLabel::Red => ColourLabel::Orange,
+            ColourLabel::Orange => ColourLabel::Yellow,
+            ColourLabel::Yellow => ColourLabel::Green,
+            ColourLabel::Green => ColourLabel::Blue,
+            ColourLabel::Blue => ColourLabel::Purple,
+            ColourLabel::Purple => ColourLabel::None,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        // Index into the UI's list of label colours, 0 is no label
+        match self {
+            ColourLabel::None => 0,
+            ColourLabel::Red => 1,
+            ColourLabel::Orange => 2,
+            ColourLabel::Yellow => 3,
+            ColourLabel::Green => 4,
+            ColourLabel::Blue => 5,
+            ColourLabel::Purple => 6,
+        }
+    }
+}
+
+// How new recordings are named
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum NamingScheme {
+    Sequential, // 'Recording N'
+    Timestamp,  // The date and time the recording started, like '2024-06-01 14-30-05'
+    Prefix,     // A custom name followed by a number
+}
+
+impl NamingScheme {
+    pub fn from_ui(index: i32) -> NamingScheme {
+        // Converts from the option picked in the UI
+        match index {
+            1 => NamingScheme::Timestamp,
+            2 => NamingScheme::Prefix,
+            _ => NamingScheme::Sequential,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            NamingScheme::Sequential => 0,
+            NamingScheme::Timestamp => 1,
+            NamingScheme::Prefix => 2,
+        }
+    }
+
+    pub fn name(self, prefix: &str, taken_names: &[String], now: SystemTime) -> Option<String> {
+        // Picks a name for a new recording, or none for sequential names which the recorder works out itself
+        match self {
+            NamingScheme::Sequential => None,
+            NamingScheme::Timestamp => {
+                let seconds = match now.duration_since(UNIX_EPOCH) {
+                    Ok(value) => value.as_secs(),
+                    Err(_) => 0,
+                };
+                Some(unique_name(timestamp_name(seconds), taken_names))
+            }
+            NamingScheme::Prefix => {
+                let prefix = clean_prefix(prefix);
+                let count = taken_names
+                    .iter()
+                    .filter(|name| name.starts_with(&format!("{} ", prefix)))
+                    .count();
+                Some(unique_name(
+                    format!("{} {}", prefix, count + 1),
+                    taken_names,
+                ))
+            }
+        }
+    }
+}
+
+// Sample format recordings are written in when they're exported
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Float32, // The same as the recorder writes
+    Int24,
+    Int16, // Plays almost anywhere
+}
+
+impl ExportFormat {
+    pub fn from_ui(index: i32) -> ExportFormat {
+        // Converts from the option picked in the UI
+        match index {
+            1 => ExportFormat::Int24,
+            2 => ExportFormat::Int16,
+            _ => ExportFormat::Float32,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            ExportFormat::Float32 => 0,
+            ExportFormat::Int24 => 1,
+            ExportFormat::Int16 => 2,
+        }
+    }
+
+    pub fn spec(self, source: WavSpec) -> WavSpec {
+        // The format to export a recording in, keeping its channels and sample rate
+        let (bits_per_sample, sample_format) = match self {
+            ExportFormat::Float32 => (32, SampleFormat::Float),
+            ExportFormat::Int24 => (24, SampleFormat::Int),
+            ExportFormat::Int16 => (16, SampleFormat::Int),
+        };
+        WavSpec {
+            bits_per_sample,
+            sample_format,
+            ..source
+        }
+    }
+}
+
+// Colours the app is shown in
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub fn from_ui(index: i32) -> Theme {
+        // Converts from the option picked in the UI
+        match index {
+            1 => Theme::Light,
+            _ => Theme::Dark,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            Theme::Dark => 0,
+            Theme::Light => 1,
+        }
+    }
+}
+
+// When recordings at another sample rate are brought to the library's rate
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum ResampleMode {
+    Off,      // Left to the audio library when it plays
+    OnPlay,   // Resampled in memory each time it plays, the file stays as it was
+    OnImport, // Offered with the channel conversion when the file is found, replacing it
+}
+
+impl ResampleMode {
+    pub fn from_ui(index: i32) -> ResampleMode {
+        // Converts from the option picked in the UI
+        match index {
+            1 => ResampleMode::OnPlay,
+            2 => ResampleMode::OnImport,
+            _ => ResampleMode::Off,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            ResampleMode::Off => 0,
+            ResampleMode::OnPlay => 1,
+            ResampleMode::OnImport => 2,
+        }
+    }
+}
+
+// Where recordings named 'Default taken... N' go in the list
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum FallbackGrouping {
+    Mixed, // Sorted in with the rest by name
+    First, // Together at the top
+    Last,  // Together at the bottom
+}
+
+impl FallbackGrouping {
+    pub fn from_ui(index: i32) -> FallbackGrouping {
+        // Converts from the option picked in the UI
+        match index {
+            1 => FallbackGrouping::First,
+            2 => FallbackGrouping::Last,
+            _ => FallbackGrouping::Mixed,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            FallbackGrouping::Mixed => 0,
+            FallbackGrouping::First => 1,
+            FallbackGrouping::Last => 2,
+        }
+    }
+
+    pub fn group(self, names: &mut Vec<String>) {
+        // Moves fallback names to one end of an already sorted list, keeping both groups in their order
+        let fallback = |name: &String| name.starts_with(FALLBACK_NAME);
+        match self {
+            FallbackGrouping::Mixed => (),
+            FallbackGrouping::First => names.sort_by_key(|name| !fallback(name)),
+            FallbackGrouping::Last => names.sort_by_key(fallback),
+        }
+    }
+}
+
+// Dial values a new preset is made with
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum NewPresetStart {
+    Current,  // The current recording's dials
+    Flat,     // Every dial at zero
+    Template, // The dials new recordings start with
+}
+
+impl NewPresetStart {
+    pub fn from_ui(index: i32) -> NewPresetStart {
+        // Converts from the option picked in the UI
+        match index {
+            1 => NewPresetStart::Flat,
+            2 => NewPresetStart::Template,
+            _ => NewPresetStart::Current,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            NewPresetStart::Current => 0,
+            NewPresetStart::Flat => 1,
+            NewPresetStart::Template => 2,
+        }
+    }
+}
+
+// Sound the metronome clicks with
+#[derive(Savefile, Clone, PartialEq)]
+pub enum ClickSound {
+    Beep,           // Short sine tones
+    WoodBlock,      // Quickly fading tones that knock like a wood block
+    Tick,           // Short bursts of noise
+    Custom(String), // Path to a sound file, used for every beat
+}
+
+impl ClickSound {
+    pub fn from_ui(index: i32) -> ClickSound {
+        // Converts from the built in sound picked in the UI, a custom file is chosen on its own
+        match index {
+            1 => ClickSound::WoodBlock,
+            2 => ClickSound::Tick,
+            _ => ClickSound::Beep,
+        }
+    }
+
+    pub fn to_ui(&self) -> i32 {
+        match self {
+            ClickSound::Beep => 0,
+            ClickSound::WoodBlock => 1,
+            ClickSound::Tick => 2,
+            ClickSound::Custom(_) => 3,
+        }
+    }
+
+    pub fn sounds(&self) -> Result<(StaticSoundData, StaticSoundData), Error> {
+        // Makes or loads the normal click and the one for the first beat of each bar
+        match self {
+            ClickSound::Beep => Ok((click_sound(1000.0), click_sound(1500.0))),
+            ClickSound::WoodBlock => Ok((wood_block_sound(800.0), wood_block_sound(1200.0))),
+            ClickSound::Tick => Ok((tick_sound(0.3), tick_sound(0.6))),
+            ClickSound::Custom(path) => match StaticSoundData::from_file(path) {
+                Ok(value) if value.num_frames() > 0 => Ok((value.clone(), value)),
+                _ => Err(Error::ClickLoadError(path.clone())),
+            },
+        }
+    }
+}
+
+// What happens when the next 'Recording N' is already taken
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum NameCollision {
+    Fallback,  // 'Default taken... N'
+    Suffix,    // 'Recording N (2)'
+    Timestamp, // 'Recording N' followed by the date and time
+    Refuse,    // Doesn't record until the name is free
+}
+
+impl NameCollision {
+    pub fn from_ui(index: i32) -> NameCollision {
+        // Converts from the option picked in the UI
+        match index {
+            1 => NameCollision::Suffix,
+            2 => NameCollision::Timestamp,
+            3 => NameCollision::Refuse,
+            _ => NameCollision::Fallback,
+        }
+    }
+
+    pub fn to_ui(self) -> i32 {
+        match self {
+            NameCollision::Fallback => 0,
+            NameCollision::Suffix => 1,
+            NameCollision::Timestamp => 2,
+            NameCollision::Refuse => 3,
+        }
+    }
+
+    pub fn resolve(
+        self,
+        name: &str,
+        taken_names: &[String],
+        fallbacks: usize,
+        now: SystemTime,
+    ) -> Option<String> {
+        // Picks a free name to use instead of a taken one, or none if the recording should be refused
+        // Every name is checked against the taken ones so none of them can overwrite a file
+        match self {
+            NameCollision::Fallback => {
+                let mut count = fallbacks + 1;
+                while taken_names.contains(&format!("{} {}", FALLBACK_NAME, count)) {
+                    count += 1;
+                }
+                Some(format!("{} {}", FALLBACK_NAME, count))
+            }
+            NameCollision::Suffix => {
+                let mut count = 2;
+                while taken_names.contains(&format!("{} ({})", name, count)) {
+                    count += 1;
+                }
+                Some(format!("{} ({})", name, count))
+            }
+            NameCollision::Timestamp => {
+                let seconds = match now.duration_since(UNIX_EPOCH) {
+                    Ok(value) => value.as_secs(),
+                    Err(_) => 0,
+                };
+                Some(unique_name(
+                    format!("{} {}", name, timestamp_name(seconds)),
+                    taken_names,
+                ))
+            }
+            NameCollision::Refuse => None,
+        }
+    }
+}
+
+// Steps that run on a new recording once it's finished, in the order they're saved in
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub enum PostRecordAction {
+    Normalise, // Turns the recording up or down so its loudest sample peaks just under full scale
+    Export, // Writes a copy to the export folder in the export format, the same as exporting it by hand
+}
+
+impl PostRecordAction {
+    pub fn run(
+        self,
+        name: &str,
+        export_directory: &Option<String>,
+        export_format: ExportFormat,
+    ) -> Result<Option<Warning>, Error> {
+        // Runs the action on a recording and returns its own error if it fails
+        // A warning is returned when it worked but not quite as asked
+        match self {
+            PostRecordAction::Normalise => match normalise(name) {
+                Some(_) => Err(Error::NormaliseError),
+                None => Ok(None),
+            },
+            PostRecordAction::Export => {
+                let (directory, fell_back) = File::export_directory(export_directory)?;
+                export(name, &directory, export_format)?;
+                Ok(fell_back.then_some(Warning::ExportFallback))
+            }
+        }
+    }
+}
+
+pub fn no_post_record_actions() -> Vec<PostRecordAction> {
+    // Settings saved before post-record actions existed don't run any
+    vec![]
+}
+
+pub fn no_colour_label() -> ColourLabel {
+    ColourLabel::None
+}
+
+pub fn no_loop_point() -> Option<f32> {
+    None
+}
+
+pub fn default_band_labels() -> Vec<String> {
+    // The names the bands had before they could be renamed
+    ["Sub Bass", "Bass", "Low Mids", "High Mids", "Treble"]
+        .iter()
+        .map(|label| label.to_string())
+        .collect()
+}
+
+pub fn default_input_channels() -> [u32; 2] {
+    // Settings saved before channels could be picked record the first two
+    [0, 1]
+}
+
+pub fn no_window_geometry() -> Option<WindowGeometry> {
+    // Lets the system place and size the window the first time
+    None
+}
+
+pub fn default_theme() -> Theme {
+    Theme::Dark // The only theme before it could be picked
+}
+
+pub fn default_output_device() -> String {
+    // Blank means whatever the system's default output is
+    String::new()
+}
+
+pub fn default_library_channels() -> u16 {
+    2 // The recorder has always written stereo
+}
+
+pub fn no_original_channels() -> Option<u16> {
+    None // Recordings from before this was kept
+}
+
+pub fn no_original_sample_rate() -> Option<u32> {
+    None // Recordings from before this was kept
+}
+
+pub fn default_resample_mode() -> ResampleMode {
+    ResampleMode::Off // What older versions always did
+}
+
+pub fn nothing_unconverted() -> Vec<String> {
+    vec![]
+}
+
+pub fn empty_queue() -> Vec<String> {
+    vec![]
+}
+
+pub fn default_naming_scheme() -> NamingScheme {
+    NamingScheme::Sequential
+}
+
+pub fn default_name_collision() -> NameCollision {
+    NameCollision::Fallback // What older versions always did
+}
+
+pub fn default_export_format() -> ExportFormat {
+    ExportFormat::Float32
+}
+
+pub fn default_fallback_grouping() -> FallbackGrouping {
+    FallbackGrouping::Mixed // What older versions always did
+}
+
+pub fn default_new_preset_start() -> NewPresetStart {
+    NewPresetStart::Current // What older versions always did
+}
+
+pub fn default_click_sound() -> ClickSound {
+    ClickSound::Beep // What older versions always did
+}
+
+pub fn no_export_directory() -> Option<String> {
+    None // Exports go to the data folder until another is chosen
+}
+
+pub fn no_default_preset() -> Option<String> {
+    None
+}
+
+pub fn default_name_prefix() -> String {
+    String::from("Take")
+}
+
+pub fn default_playback() -> PlaybackMode {
+    PlaybackMode::AutoNext
+}
+
+pub fn no_playback_override() -> Option<PlaybackMode> {
+    // Recordings saved before overrides existed use the global playback type
+    None
+}
+
+// -------- Structs --------
+// Edits made in the UI that need applying to the settings data, kept apart from the UI so syncing can run without a window
+pub struct Changes {
+    pub dials: [i32; 6],
+    pub filter_kinds: [FilterKind; 5],
+    pub high_pass: f32,
+    pub low_pass: f32,
+    pub invert_left: bool,
+    pub invert_right: bool,
+    pub auto_makeup: bool,
+    pub current_recording: usize,
+    pub dials_edited: bool, // Whether the current recording should take the values above
+    pub new_preset: bool,   // Whether a preset should be made from the values above
+    pub deleted_preset: Option<usize>, // Index of a deleted preset
+    pub preset_names: Option<Vec<String>>, // New names for every preset if they were renamed
+    pub deleted_recording: Option<usize>, // Index of a deleted recording
+    pub recording_names: Option<Vec<String>>, // New names for every recording if they were renamed
+}
+
+impl Changes {
+    pub fn none() -> Changes {
+        // No edits, used to only sync with the files on disk
+        Changes {
+            dials: [0, 0, 0, 0, 0, 0],
+            filter_kinds: default_filter_kinds(),
+            high_pass: HIGH_PASS_OPEN,
+            low_pass: LOW_PASS_OPEN,
+            invert_left: false,
+            invert_right: false,
+            auto_makeup: false,
+            current_recording: 0,
+            dials_edited: false,
+            new_preset: false,
+            deleted_preset: None,
+            preset_names: None,
+            deleted_recording: None,
+            recording_names: None,
+        }
+    }
+}
+
+// Index data for Settings struct
+pub struct IndexData {
+    pub preset_length: usize,
+    pub recording_length: usize,
+}
+
+// Preset data
+#[derive(Savefile, Clone, PartialEq)]
+pub struct Preset {
+    pub name: String,
+    pub sub_bass: i32,
+    pub bass: i32,
+    pub low_mids: i32,
+    pub high_mids: i32,
+    pub treble: i32,
+    pub pan: i32,
+    #[savefile_versions = "5.."]
+    #[savefile_default_fn = "default_filter_kinds"]
+    pub filter_kinds: [FilterKind; 5], // Shape of each band's filter
+}
+
+impl Preset {
+    pub fn clamp(&mut self) -> bool {
+        // Pulls dial values back into range and returns whether any were out of it
+        let mut clamped = false;
+        for value in [
+            &mut self.sub_bass,
+            &mut self.bass,
+            &mut self.low_mids,
+            &mut self.high_mids,
+            &mut self.treble,
+            &mut self.pan,
+        ] {
+            clamped |= clamp_dial(value);
+        }
+        clamped
+    }
+
+    pub fn parse(&self) -> [i32; 6] {
+        // Parses preset data into dial values
+        [
+            self.sub_bass,
+            self.bass,
+            self.low_mids,
+            self.high_mids,
+            self.treble,
+            self.pan,
+        ]
+    }
+
+    pub fn gains(&self) -> [f32; 5] {
+        // Gets the band gains in decibels so the preset means the same thing outside the app
+        band_gains(self.parse())
+    }
+
+    pub fn from_gains(
+        name: &str,
+        gains: [f32; 5],
+        pan: i32,
+        filter_kinds: [FilterKind; 5],
+    ) -> Preset {
+        // Creates a preset from band gains in decibels, rounding each to the nearest dial step
+        let mut values = [0, 0, 0, 0, 0, pan.clamp(MIN_DIAL, MAX_DIAL)];
+        for band in 0..5 {
+            values[band] = gain_to_dial(gains[band]);
+        }
+        Preset {
+            name: name.to_string(),
+            ..Preset::from(values, filter_kinds)
+        }
+    }
+
+    pub fn to_shared(&self) -> String {
+        // One line of a shared presets file, the name then each band in decibels then the pan dial
+        let gains = self.gains();
+        format!(
+            "{}, {}, {}, {}, {}, {}, {}",
+            self.name, gains[0], gains[1], gains[2], gains[3], gains[4], self.pan
+        )
+    }
+
+    pub fn from_shared(line: &str, path: &str) -> Result<Preset, Error> {
+        // Reads a line of a shared presets file, fields are split from the end so names can have commas in them
+        let mut fields: Vec<&str> = line.rsplitn(7, ',').map(|field| field.trim()).collect();
+        fields.reverse();
+        let unreadable = || Error::LoadError(path.to_string());
+        if fields.len() != 7 || fields[0].is_empty() {
+            return Err(unreadable());
+        }
+        let mut gains = [0.0; 5];
+        for band in 0..5 {
+            gains[band] = fields[band + 1].parse().map_err(|_| unreadable())?;
+        }
+        let pan: i32 = fields[6].parse().map_err(|_| unreadable())?;
+
+        // Anything quieter than the muted gain still mutes, only gains that round past the top step are out of range
+        let loudest = MAX_DIAL as f32 * GAIN_PER_STEP + GAIN_PER_STEP / 2.0;
+        if gains.iter().any(|gain| gain.is_nan() || *gain >= loudest)
+            || !(MIN_DIAL..=MAX_DIAL).contains(&pan)
+        {
+            return Err(Error::PresetRangeError(fields[0].to_string()));
+        }
+        Ok(Preset::from_gains(
+            fields[0],
+            gains,
+            pan,
+            default_filter_kinds(),
+        ))
+    }
+
+    pub fn from(values: [i32; 6], filter_kinds: [FilterKind; 5]) -> Preset {
+        // Creates a preset from dial values and filter kinds
+        Preset {
+            name: String::from("New Preset"),
+            sub_bass: values[0],
+            bass: values[1],
+            low_mids: values[2],
+            high_mids: values[3],
+            treble: values[4],
+            pan: values[5],
+            filter_kinds,
+        }
+    }
+
+    pub fn next_name(taken_names: &[String]) -> String {
+        // Picks the first 'Preset N' that isn't in use, counting on from the ones already made
+        let mut counter = taken_names
+            .iter()
+            .filter(|name| name.starts_with("Preset "))
+            .count()
+            + 1;
+        while taken_names.contains(&format!("Preset {}", counter)) {
+            counter += 1;
+        }
+        format!("Preset {}", counter)
+    }
+
+    pub fn send_names(list: &Vec<Preset>, length: &usize) -> ModelRc<SharedString> {
+        // Sends preset names to UI
+        let mut preset_names = vec![];
+        for preset in 0..*length {
+            preset_names.push(list[preset].name.to_shared_string());
+        }
+
+        // ModelRc is the type of list that the UI uses
+        ModelRc::new(VecModel::from(preset_names)) // Creates new ModelRc from the names list
+    }
+
+    pub fn send_values(list: &Vec<Preset>, length: &usize) -> ModelRc<ModelRc<i32>> {
+        // Sends preset dial values to the UI
+        let mut all_preset_values = vec![];
+        for values in 0..*length {
+            let mut preset_values = vec![];
+
+            preset_values.push(list[values].sub_bass);
+            preset_values.push(list[values].bass);
+            preset_values.push(list[values].low_mids);
+            preset_values.push(list[values].high_mids);
+            preset_values.push(list[values].treble);
+            preset_values.push(list[values].pan);
+
+            all_preset_values.push(ModelRc::new(VecModel::from(preset_values)));
+        }
+        ModelRc::new(VecModel::from(all_preset_values))
+    }
+
+    pub fn send_filter_kinds(list: &Vec<Preset>) -> ModelRc<ModelRc<i32>> {
+        // Sends preset filter kinds to the UI
+        let mut all_kinds = vec![];
+        for preset in list {
+            all_kinds.push(FilterKind::send(preset.filter_kinds));
+        }
+        ModelRc::new(VecModel::from(all_kinds))
+    }
+}
+
+// Recording data
+#[derive(Savefile, Clone, PartialEq)]
+pub struct Recording {
+    pub name: String,
+    pub sub_bass: i32,
+    pub bass: i32,
+    pub low_mids: i32,
+    pub high_mids: i32,
+    pub treble: i32,
+    pub pan: i32,
+    #[savefile_versions = "4.."]
+    #[savefile_default_val = "false"]
+    pub had_dropouts: bool, // Whether audio was lost while this was being recorded
+    #[savefile_versions = "5.."]
+    #[savefile_default_fn = "default_filter_kinds"]
+    pub filter_kinds: [FilterKind; 5], // Shape of each band's filter
+    #[savefile_versions = "6.."]
+    #[savefile_default_val = "20.0"]
+    pub high_pass: f32, // Cutoff in hertz that lower frequencies are rolled off below
+    #[savefile_versions = "6.."]
+    #[savefile_default_val = "20000.0"]
+    pub low_pass: f32, // Cutoff in hertz that higher frequencies are rolled off above
+    #[savefile_versions = "7.."]
+    #[savefile_default_val = "false"]
+    pub invert_left: bool, // Whether the left channel's polarity is flipped
+    #[savefile_versions = "7.."]
+    #[savefile_default_val = "false"]
+    pub invert_right: bool, // Whether the right channel's polarity is flipped
+    #[savefile_versions = "8.."]
+    #[savefile_default_fn = "no_playback_override"]
+    pub playback_override: Option<PlaybackMode>, // Used instead of the global playback type when set
+    #[savefile_versions = "9.."]
+    #[savefile_default_val = "false"]
+    pub auto_makeup: bool, // Whether boosted bands are balanced out by turning the whole track down
+    #[savefile_versions = "12.."]
+    #[savefile_default_fn = "no_loop_point"]
+    pub loop_start: Option<f32>, // Seconds in that looping jumps back to, the beginning when not set
+    #[savefile_versions = "12.."]
+    #[savefile_default_fn = "no_loop_point"]
+    pub loop_end: Option<f32>, // Seconds in that looping jumps back from, the end when not set
+    #[savefile_versions = "25.."]
+    #[savefile_default_fn = "no_colour_label"]
+    pub colour: ColourLabel, // Shown next to the name in the recordings list
+    #[savefile_versions = "32.."]
+    #[savefile_default_val = "0.0"]
+    pub start_offset: f32, // Seconds in that playback starts from, used to skip silence at the top without changing the file
+    #[savefile_versions = "35.."]
+    #[savefile_default_fn = "no_original_channels"]
+    pub original_channels: Option<u16>, // Channels the file had when it was found in the folder, before any conversion
+    #[savefile_versions = "37.."]
+    #[savefile_default_fn = "no_original_sample_rate"]
+    pub original_sample_rate: Option<u32>, // Sample rate the file had when it was found in the folder, before any conversion
+}
+
+impl Recording {
+    pub fn clamp(&mut self) -> bool {
+        // Pulls dial values and cutoffs back into range and returns whether any were out of it
+        let mut clamped = false;
+        for value in [
+            &mut self.sub_bass,
+            &mut self.bass,
+            &mut self.low_mids,
+            &mut self.high_mids,
+            &mut self.treble,
+            &mut self.pan,
+        ] {
+            clamped |= clamp_dial(value);
+        }
+        for (cutoff, open) in [
+            (&mut self.high_pass, HIGH_PASS_OPEN),
+            (&mut self.low_pass, LOW_PASS_OPEN),
+        ] {
+            if cutoff.is_nan() {
+                *cutoff = open;
+                clamped = true;
+            } else if *cutoff < HIGH_PASS_OPEN || *cutoff > LOW_PASS_OPEN {
+                *cutoff = cutoff.clamp(HIGH_PASS_OPEN, LOW_PASS_OPEN);
+                clamped = true;
+            }
+        }
+        clamped
+    }
+
+    pub fn new(name: &String) -> Recording {
+        // Creates a new recording
+        Recording {
+            name: name.to_string(),
+            sub_bass: 0,
+            bass: 0,
+            low_mids: 0,
+            high_mids: 0,
+            treble: 0,
+            pan: 0,
+            had_dropouts: false,
+            filter_kinds: default_filter_kinds(),
+            high_pass: HIGH_PASS_OPEN,
+            low_pass: LOW_PASS_OPEN,
+            invert_left: false,
+            invert_right: false,
+            playback_override: None,
+            auto_makeup: false,
+            loop_start: None,
+            loop_end: None,
+            colour: ColourLabel::None,
+            start_offset: 0.0,
+            original_channels: None,
+            original_sample_rate: None,
+        }
+    }
+
+    pub fn from(name: &String, values: [i32; 6]) -> Recording {
+        // Creates a new recording from a name and dial values
+        Recording {
+            name: name.to_string(),
+            sub_bass: values[0],
+            bass: values[1],
+            low_mids: values[2],
+            high_mids: values[3],
+            treble: values[4],
+            pan: values[5],
+            had_dropouts: false,
+            filter_kinds: default_filter_kinds(),
+            high_pass: HIGH_PASS_OPEN,
+            low_pass: LOW_PASS_OPEN,
+            invert_left: false,
+            invert_right: false,
+            playback_override: None,
+            auto_makeup: false,
+            loop_start: None,
+            loop_end: None,
+            colour: ColourLabel::None,
+            start_offset: 0.0,
+            original_channels: None,
+            original_sample_rate: None,
+        }
+    }
+
+    pub fn parse(&self) -> [i32; 6] {
+        // Parses recording data into dial values
+        let mut list: [i32; 6] = [0, 0, 0, 0, 0, 0];
+
+        list[0] = self.sub_bass;
+        list[1] = self.bass;
+        list[2] = self.low_mids;
+        list[3] = self.high_mids;
+        list[4] = self.treble;
+        list[5] = self.pan;
+
+        list
+    }
+
+    pub fn gains(&self) -> [f32; 5] {
+        // Gets the band gains in decibels, the same values the player uses
+        band_gains(self.parse())
+    }
+
+    pub fn parse_vec_from_recording(&self) -> Vec<i32> {
+        // Parses recording data into a vector
+        let mut list = vec![];
+
+        list.push(self.sub_bass);
+        list.push(self.bass);
+        list.push(self.low_mids);
+        list.push(self.high_mids);
+        list.push(self.treble);
+        list.push(self.pan);
+
+        list
+    }
+
+    pub fn parse_vec_from_list(list: [i32; 6]) -> Vec<i32> {
+        // Parses a vector from dial values
+        let mut new = vec![];
+
+        new.push(list[0]);
+        new.push(list[1]);
+        new.push(list[2]);
+        new.push(list[3]);
+        new.push(list[4]);
+        new.push(list[5]);
+
+        new
+    }
+
+    pub fn send_names(list: &Vec<Recording>) -> ModelRc<SharedString> {
+        // Sends recording names to UI
+        let mut new_list = vec![];
+
+        for recording in 0..list.len() {
+            new_list.push(list[recording].name.to_shared_string());
+        }
+
+        ModelRc::new(VecModel::from(new_list))
+    }
+
+    pub fn loop_region(&self, length: Duration) -> Option<(Duration, Duration)> {
+        // Part of the recording that plays while looping, None when the whole recording loops
+        if self.loop_start.is_none() && self.loop_end.is_none() {
+            return None;
+        }
+        let limit = length.as_secs_f32();
+        let start = Duration::from_secs_f32(self.loop_start.unwrap_or(0.0).max(0.0).min(limit));
+        let end = match self.loop_end {
+            Some(value) => Duration::from_secs_f32(value.max(0.0).min(limit)),
+            None => length,
+        };
+        if start < end {
+            Some((start, end))
+        } else {
+            None // A start after the end would never play anything
+        }
+    }
+
+    pub fn start_position(&self, length: Duration) -> Duration {
+        // Where playback starts, the beginning if the offset is past the end so there's always something to play
+        let offset = Duration::from_secs_f32(self.start_offset.max(0.0));
+        if offset < length {
+            offset
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    pub fn start_summary(&self) -> SharedString {
+        // Describes the start offset for the UI
+        // Shown to a tenth of a second since detected offsets rarely land on a whole second
+        if self.start_offset > 0.0 {
+            SharedString::from(format!(
+                "{}:{:04.1}",
+                (self.start_offset / 60.0) as u64,
+                self.start_offset % 60.0
+            ))
+        } else {
+            SharedString::from("Beginning")
+        }
+    }
+
+    pub fn format_summary(&self) -> SharedString {
+        // Says what the recording was converted from when it was found, nothing if it's as it was
+        // Sample rates are only named when they changed
+        let (Some(channels), Some(current)) = (self.original_channels, wav_spec(&self.name)) else {
+            return SharedString::new();
+        };
+        let sample_rate = self.original_sample_rate.unwrap_or(current.sample_rate);
+        if channels == current.channels && sample_rate == current.sample_rate {
+            return SharedString::new();
+        }
+        let describe = |channels: u16, rate: u32| {
+            if sample_rate == current.sample_rate {
+                channel_label(channels)
+            } else {
+                format!("{} at {} Hz", channel_label(channels), rate)
+            }
+        };
+        SharedString::from(format!(
+            "Imported in {}, converted to {}",
+            describe(channels, sample_rate),
+            describe(current.channels, current.sample_rate)
+        ))
+    }
+
+    pub fn crop(&self, recordings: &Vec<Recording>, keep_original: bool) -> Result<String, Error> {
+        // Cuts the recording down to its loop region along with its automation and returns the name it was saved as
+        // Keeping the original writes a copy, otherwise the recording is replaced
+        let (spec, channels) = read_channels(&self.name)?;
+        let frames = channels.first().map_or(0, |channel| channel.len());
+        let length = Duration::from_secs_f64(frames as f64 / spec.sample_rate as f64);
+        let region = match self.loop_region(length) {
+            Some(value) => value,
+            None => return Err(Error::NoSelectionError),
+        };
+        let first = (region.0.as_secs_f64() * spec.sample_rate as f64) as usize;
+        let last = ((region.1.as_secs_f64() * spec.sample_rate as f64) as usize).min(frames);
+        let cropped: Vec<Vec<f32>> = channels
+            .iter()
+            .map(|channel| channel[first.min(last)..last].to_vec())
+            .collect();
+
+        let original = SnapShot::load_or_new(&self.name)?;
+        let snapshot = original.clone().unwrap_or(SnapShot::new()); // Nothing captured to keep
+        let tick = |time: Duration| (time.as_millis() / PLAYER_TICK.as_millis()) as i32;
+        let section = snapshot.section(tick(region.0), Some(tick(region.1)));
+
+        let name = if keep_original {
+            unused_name(&Disk, format!("{} cropped", self.name), recordings)
+        } else {
+            self.name.clone()
+        };
+        // The automation is written before the audio, and put back if either fails, so the two never go out of step
+        let restore = || match (&original, keep_original) {
+            (Some(snapshot), false) => {
+                let _ = snapshot.clone().save(&name);
+            }
+            _ => {
+                if let Ok(snapshots) = File::snapshot_directory() {
+                    let _ = remove_file(format!("{}/{}.bin", snapshots, name));
+                }
+            }
+        };
+        if let Some(error) = section.save(&name) {
+            restore();
+            return Err(error);
+        }
+        if let Some(error) = write_channels(&name, spec, &cropped) {
+            restore();
+            return Err(error);
+        }
+        Ok(name)
+    }
+
+    pub fn split(&self, at: Duration, recordings: &Vec<Recording>) -> Result<[String; 2], Error> {
+        // Writes the parts before and after a point to two new recordings along with their automation, keeping the original
+        let (spec, channels) = read_channels(&self.name)?;
+        let frames = channels.first().map_or(0, |channel| channel.len());
+        let split = (at.as_secs_f64() * spec.sample_rate as f64) as usize;
+        if split == 0 || split >= frames {
+            return Err(Error::SplitEdgeError);
+        }
+
+        let snapshot = match load(&self.name, LoadType::Snapshot) {
+            Ok(DataType::SnapShot(value)) => value,
+            _ => SnapShot::new(),
+        };
+        let tick = (at.as_millis() / PLAYER_TICK.as_millis()) as i32;
+
+        let mut taken = recordings.clone();
+        let mut parts = [String::new(), String::new()];
+        for (part, (range, automation)) in [
+            (0..split, snapshot.section(0, Some(tick))),
+            (split..frames, snapshot.section(tick, None)), // The second part starts from zero
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let name = unused_name(&Disk, format!("{} part {}", self.name, part + 1), &taken);
+            let samples: Vec<Vec<f32>> = channels
+                .iter()
+                .map(|channel| channel[range.clone()].to_vec())
+                .collect();
+            if let Some(error) = write_channels(&name, spec, &samples) {
+                return Err(error);
+            }
+            if let Some(error) = automation.save(&name) {
+                return Err(error);
+            }
+            taken.push(Recording::new(&name)); // So the second part can't take the first part's name
+            parts[part] = name;
+        }
+
+        Ok(parts)
+    }
+
+    pub fn part(&self, name: &str) -> Recording {
+        // A new recording cut from this one, sounding the same but with the points on the old timeline left behind
+        Recording {
+            name: name.to_string(),
+            loop_start: None,
+            loop_end: None,
+            start_offset: 0.0,
+            ..self.clone()
+        }
+    }
+
+    pub fn loop_summary(&self) -> SharedString {
+        // Describes the loop region for the UI
+        let format = |point: Option<f32>, unset: &str| match point {
+            Some(value) => {
+                let seconds = value.max(0.0) as u64;
+                format!("{}:{:02}", seconds / 60, seconds % 60)
+            }
+            None => String::from(unset),
+        };
+        if self.loop_start.is_none() && self.loop_end.is_none() {
+            SharedString::from("Whole recording")
+        } else {
+            SharedString::from(format!(
+                "{} - {}",
+                format(self.loop_start, "Start"),
+                format(self.loop_end, "End")
+            ))
+        }
+    }
+
+    pub fn send_dropouts(list: &Vec<Recording>) -> ModelRc<bool> {
+        // Sends which recordings lost audio to UI
+        let mut new_list = vec![];
+
+        for recording in 0..list.len() {
+            new_list.push(list[recording].had_dropouts);
+        }
+
+        ModelRc::new(VecModel::from(new_list))
+    }
+
+    pub fn matches(&self, search: &str) -> bool {
+        // Whether the name contains the search text, ignoring case
+        self.name
+            .to_lowercase()
+            .contains(&search.trim().to_lowercase())
+    }
+
+    pub fn send_hidden(list: &Vec<Recording>, search: &str) -> ModelRc<bool> {
+        // Sends which recordings don't match the search to UI, the list keeps every recording so indexes stay the same
+        ModelRc::new(VecModel::from(
+            list.iter()
+                .map(|recording| !recording.matches(search))
+                .collect::<Vec<bool>>(),
+        ))
+    }
+
+    pub fn send_colours(list: &Vec<Recording>) -> ModelRc<i32> {
+        // Sends each recording's colour label to UI
+        ModelRc::new(VecModel::from(
+            list.iter()
+                .map(|recording| recording.colour.to_ui())
+                .collect::<Vec<i32>>(),
+        ))
+    }
+
+    pub fn send_values(list: &Vec<Recording>, length: &usize) -> ModelRc<ModelRc<i32>> {
+        // Sends recording dial values to UI
+        let mut all_recording_values = vec![];
+        for values in 0..*length {
+            let mut recording_values = vec![];
+
+            recording_values.push(list[values].sub_bass);
+            recording_values.push(list[values].bass);
+            recording_values.push(list[values].low_mids);
+            recording_values.push(list[values].high_mids);
+            recording_values.push(list[values].treble);
+            recording_values.push(list[values].pan);
+
+            all_recording_values.push(ModelRc::new(VecModel::from(recording_values)));
+        }
+        ModelRc::new(VecModel::from(all_recording_values))
+    }
+
+    pub fn rename(
+        // Renames recordings
+        storage: &dyn Storage,
+        old: &Vec<Recording>,
+        new: &Vec<String>,
+    ) -> Result<Vec<Recording>, (Vec<Recording>, Error)> {
+        // Returns either a vector of the new names or if there was an error, a vector of new and old names plus an error value
+        let mut recording_list = vec![];
+        let mut error = None; // First problem found, the rest of the names are still checked so no recordings are dropped
+        let problems = Recording::check_renames(storage, old, new);
+
+        for name in 0..old.len() {
+            // Loops through all the old names
+            let new_name = match new.get(name) {
+                Some(value) => value.clone(),
+                None => old[name].name.clone(), // Missing names are left as they were
+            };
+            if new_name == old[name].name {
+                recording_list.push(old[name].clone()); // Skips recordings that were unchanged
+                continue;
+            }
+
+            let problem = match problems[name].clone() {
+                Some(value) => Some(value),
+                None => File::rename(storage, &old[name].name, new_name.clone()), // Renames file if all the checks pass
+            };
+            match problem {
+                Some(value) => {
+                    recording_list.push(old[name].clone()); // Keeps the old name
+                    if error.is_none() {
+                        error = Some(value);
+                    }
+                }
+                None => {
+                    recording_list.push(Recording {
+                        name: new_name,
+                        ..old[name].clone()
+                    }); // Pushes new name to list
+                }
+            }
+        }
+
+        match error {
+            Some(value) => Err((recording_list, value)),
+            None => Ok(recording_list),
+        }
+    }
+
+    pub fn check_renames(
+        storage: &dyn Storage,
+        old: &Vec<Recording>,
+        new: &Vec<String>,
+    ) -> Vec<Option<Error>> {
+        // Runs the same checks as rename without moving any files, giving what would go wrong with each recording
+        // Names taken by earlier recordings in the list count as used since renaming would have moved a file there
+        let mut taken = old.clone();
+        (0..old.len())
+            .map(|index| {
+                let new_name = match new.get(index) {
+                    Some(value) if *value != old[index].name => value,
+                    _ => return None, // Unchanged names are never renamed
+                };
+                let problem = Recording::check_name(storage, new_name, &taken);
+                if problem.is_none() {
+                    taken.push(Recording::new(new_name));
+                }
+                problem
+            })
+            .collect()
+    }
+
+    pub fn rename_one(
+        storage: &dyn Storage,
+        recordings: &mut Vec<Recording>,
+        index: usize,
+        new_name: &String,
+    ) -> Option<Error> {
+        // Renames a single recording in place, leaving the rest of the list untouched
+        let old_name = match recordings.get(index) {
+            Some(value) => value.name.clone(),
+            None => return None,
+        };
+        if *new_name == old_name {
+            return None;
+        }
+
+        if let Some(error) = Recording::check_name(storage, new_name, recordings) {
+            return Some(error);
+        }
+        if let Some(error) = File::rename(storage, &old_name, new_name.clone()) {
+            return Some(error);
+        }
+
+        recordings[index].name = new_name.clone();
+        None
+    }
+
+    pub fn check_name(
+        storage: &dyn Storage,
+        new_name: &String,
+        recordings: &Vec<Recording>,
+    ) -> Option<Error> {
+        // Checks whether a recording can be renamed to a name, and returns why not if it can't
+        if new_name.contains(FALLBACK_NAME) {
+            // Checks if the new name contains the fallback name
+            Some(Error::FallbackError)
+        } else if File::reserved(new_name) {
+            // Checks if the new name is used by the app's own files
+            Some(Error::SaveFileRenameError)
+        } else if new_name.is_empty() {
+            // Checks if the new name doesn't exist or equals ''
+            Some(Error::EmptyError)
+        } else if File::exists(new_name.clone(), recordings)
+            || File::present(storage, new_name, "wav")
+        {
+            // Checks if the new name is already used by a recording or by a file that hasn't been synced yet
+            Some(Error::ExistsError)
+        } else {
+            None
+        }
+    }
+
+    pub fn renumber_fallbacks(
+        storage: &dyn Storage,
+        recordings: &mut Vec<Recording>,
+    ) -> (usize, Option<Error>) {
+        // Gives every 'Default taken...' recording the lowest 'Recording N' name that's free
+        // Returns how many were renamed and the first error
+        let mut renamed = 0;
+        let mut error = None;
+        let mut number = 1;
+        for index in 0..recordings.len() {
+            if !recordings[index].name.contains(FALLBACK_NAME) {
+                continue;
+            }
+            while Recording::check_name(storage, &format!("Recording {}", number), recordings)
+                .is_some()
+            {
+                number += 1; // Skips names used in the list or on disk
+            }
+            match Recording::rename_one(
+                storage,
+                recordings,
+                index,
+                &format!("Recording {}", number),
+            ) {
+                Some(value) => {
+                    if error.is_none() {
+                        error = Some(value);
+                    }
+                }
+                None => renamed += 1,
+            }
+            number += 1;
+        }
+
+        (renamed, error)
+    }
+
+    pub fn shuffle(length: usize) -> Vec<i32> {
+        // Shuffles recordings
+        let mut new = vec![];
+        let mut avaliable = vec![];
+
+        for number in 0..length {
+            // Creates a list of numbers 0 to list length -1
+            avaliable.push(number);
+        }
+
+        for _ in 0..length {
+            let random = random_range(0..avaliable.len()); // Creates a random number between 0 and the length of the avaliable numbers list
+            new.push(avaliable[random] as i32); // Pushes the value at the index to the shuffle list
+            avaliable.remove(random); // Removes the used number from the avaliable list
+        }
+
+        new
+    }
+}
+
+// Where the window was and how big it was when the app last closed
+#[derive(Savefile, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowGeometry {
+    pub fn of(window: &Window) -> WindowGeometry {
+        // Reads the window's position and size in physical pixels
+        let position = window.position();
+        let size = window.size();
+        WindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    pub fn apply_size(&self, window: &Window) {
+        window.set_size(PhysicalSize::new(self.width, self.height));
+    }
+
+    pub fn apply_position(&self, window: &Window) {
+        window.set_position(PhysicalPosition::new(self.x, self.y));
+    }
+
+    pub fn on_display(&self, displays: &[WindowGeometry]) -> bool {
+        // Whether enough of the top of the window is on a display for it to be dragged around
+        displays.iter().any(|display| {
+            let right = (self.x + self.width as i32).min(display.x + display.width as i32);
+            let bottom = (self.y + MIN_VISIBLE_WINDOW).min(display.y + display.height as i32);
+            right - self.x.max(display.x) >= MIN_VISIBLE_WINDOW && bottom > self.y.max(display.y)
+        })
+    }
+
+    pub fn clamped(
+        self,
+        displays: &[WindowGeometry],
+        primary: Option<WindowGeometry>,
+    ) -> WindowGeometry {
+        // Moves a window that's no longer on any display, like after a monitor was removed, onto the primary display
+        // It's shrunk to fit if the primary display is smaller than it
+        if self.on_display(displays) {
+            return self;
+        }
+        match primary.or(displays.first().copied()) {
+            Some(display) => WindowGeometry {
+                x: display.x,
+                y: display.y,
+                width: self.width.min(display.width),
+                height: self.height.min(display.height),
+            },
+            None => self, // Nothing to check against so it's left where it was
+        }
+    }
+}
+
+// All settings data
+#[derive(Savefile, Clone, PartialEq)]
+pub struct Settings {
+    pub presets: Vec<Preset>,
+    pub recordings: Vec<Recording>,
+    #[savefile_versions = "1.."]
+    #[savefile_default_val = "600"]
+    pub recording_warn_after: u32, // Seconds before a long recording warning is shown, 0 turns it off
+    #[savefile_versions = "2.."]
+    #[savefile_default_val = "false"]
+    pub metronome: bool, // Whether a click plays while recording
+    #[savefile_versions = "2.."]
+    #[savefile_default_val = "120"]
+    pub metronome_bpm: u32,
+    #[savefile_versions = "2.."]
+    #[savefile_default_val = "4"]
+    pub beats_per_bar: u32,
+    #[savefile_versions = "3.."]
+    #[savefile_default_val = "false"]
+    pub monitor_input: bool, // Whether the recorded input can be heard while recording
+    #[savefile_versions = "3.."]
+    #[savefile_default_val = "true"]
+    pub monitor_eq: bool, // Whether the current recording's dials are applied to the monitor
+    #[savefile_versions = "10.."]
+    #[savefile_default_fn = "flat_dials"]
+    pub default_dials: [i32; 6], // Dial values that newly found recordings start with
+    #[savefile_versions = "11.."]
+    #[savefile_default_fn = "no_post_record_actions"]
+    pub post_record_actions: Vec<PostRecordAction>, // Run in order on every new recording
+    #[savefile_versions = "13.."]
+    #[savefile_default_fn = "default_playback"]
+    pub playback: PlaybackMode, // Playback type used by recordings without their own, restored on launch
+    #[savefile_versions = "13.."]
+    #[savefile_default_val = "false"]
+    pub shuffle: bool, // Whether recordings were being shuffled when the app last saved
+    #[savefile_versions = "14.."]
+    #[savefile_default_fn = "default_band_labels"]
+    pub band_labels: Vec<String>, // Names shown above the five band dials
+    #[savefile_versions = "15.."]
+    #[savefile_default_val = "10"]
+    pub fallback_limit: u32, // 'Default taken...' recordings allowed before new recordings are refused, 0 is no limit
+    #[savefile_versions = "16.."]
+    #[savefile_default_val = "0"]
+    pub auto_save_minutes: u32, // How often unsaved changes are written to disk, 0 is off
+    #[savefile_versions = "17.."]
+    #[savefile_default_fn = "empty_queue"]
+    pub queue: Vec<String>, // Names of recordings to play in order, separate from the library order and shuffle
+    #[savefile_versions = "17.."]
+    #[savefile_default_val = "false"]
+    pub follow_queue: bool, // Whether auto next plays through the queue instead of the library
+    #[savefile_versions = "18.."]
+    #[savefile_default_val = "120"]
+    pub pan_glide_ms: u32, // How long captured pan moves take to glide to their new position during input playback
+    #[savefile_versions = "19.."]
+    #[savefile_default_val = "true"]
+    pub delete_empty_recordings: bool, // Whether recordings without any sound are thrown away when they finish
+    #[savefile_versions = "20.."]
+    #[savefile_default_fn = "default_input_channels"]
+    pub input_channels: [u32; 2], // Which of the input's channels are recorded as left and right, counting from 0
+    #[savefile_versions = "21.."]
+    #[savefile_default_fn = "default_naming_scheme"]
+    pub naming_scheme: NamingScheme, // How new recordings are named
+    #[savefile_versions = "21.."]
+    #[savefile_default_fn = "default_name_prefix"]
+    pub name_prefix: String, // Start of new recordings' names when they're named with a prefix
+    #[savefile_versions = "22.."]
+    #[savefile_default_val = "10"]
+    pub parameter_glide_ms: u32, // How long the player takes to move to new dial values
+    #[savefile_versions = "23.."]
+    #[savefile_default_val = "false"]
+    pub raise_thread_priority: bool, // Whether the player and recorder ask to run ahead of other threads, used from the next launch
+    #[savefile_versions = "24.."]
+    #[savefile_default_val = "true"]
+    pub trim_leading_silence: bool, // Whether new recordings start at the first sound instead of when record was pressed
+    #[savefile_versions = "26.."]
+    #[savefile_default_val = "5"]
+    pub capture_autosave_seconds: u32, // How often captured inputs are saved while capturing, 0 only saves when capturing stops
+    #[savefile_versions = "27.."]
+    #[savefile_default_fn = "default_output_device"]
+    pub output_device: String, // Name of the device that playback goes to, blank for the system default
+    #[savefile_versions = "28.."]
+    #[savefile_default_val = "50"]
+    pub min_sound_ms: u32, // How long a recording has to be above the sound floor before it isn't treated as empty
+    #[savefile_versions = "29.."]
+    #[savefile_default_fn = "no_window_geometry"]
+    pub window: Option<WindowGeometry>, // Where the window was when the app last closed
+    #[savefile_versions = "44.."]
+    #[savefile_default_fn = "default_theme"]
+    pub theme: Theme, // Colours the app is shown in
+    #[savefile_versions = "30.."]
+    #[savefile_default_val = "0"]
+    pub auto_next_gap_seconds: u32, // Silence left between tracks when auto next moves on, 0 goes straight to the next one
+    #[savefile_versions = "31.."]
+    #[savefile_default_val = "true"]
+    pub snapshot_folder: bool, // Whether snapshots are kept in their own folder instead of next to the recordings
+    #[savefile_versions = "33.."]
+    #[savefile_default_fn = "default_name_collision"]
+    pub name_collision: NameCollision, // What new recordings are named when the next 'Recording N' is taken
+    #[savefile_versions = "34.."]
+    #[savefile_default_fn = "no_default_preset"]
+    pub default_preset: Option<String>, // Name of the preset new recordings start with instead of the default dials
+    #[savefile_versions = "35.."]
+    #[savefile_default_fn = "default_library_channels"]
+    pub library_channels: u16, // Channels recordings found in the folder can be converted to so they play and pan like the rest
+    #[savefile_versions = "36.."]
+    #[savefile_default_fn = "default_export_format"]
+    pub export_format: ExportFormat, // Sample format recordings are exported in
+    #[savefile_versions = "36.."]
+    #[savefile_default_fn = "no_export_directory"]
+    pub export_directory: Option<String>, // Folder recordings were last exported to, the data folder when not set
+    #[savefile_versions = "37.."]
+    #[savefile_default_fn = "default_resample_mode"]
+    pub resample: ResampleMode, // When recordings at another sample rate are brought to the rate new recordings are made at
+    #[savefile_versions = "39.."]
+    #[savefile_default_fn = "default_click_sound"]
+    pub click_sound: ClickSound, // Sound the metronome clicks with
+    #[savefile_versions = "43.."]
+    #[savefile_default_val = "true"]
+    pub push_updates: bool, // Whether threads wake the UI as soon as something changes instead of it only finding out on its next check
+    #[savefile_versions = "42.."]
+    #[savefile_default_fn = "default_fallback_grouping"]
+    pub fallback_grouping: FallbackGrouping, // Where recordings named 'Default taken... N' go in the list
+    #[savefile_versions = "41.."]
+    #[savefile_default_fn = "default_new_preset_start"]
+    pub new_preset_start: NewPresetStart, // Dial values a new preset is made with
+    #[savefile_versions = "40.."]
+    #[savefile_default_val = "true"]
+    pub capture_everything: bool, // Whether every dial change is captured, otherwise the steps and window below leave out small and rapid ones
+    #[savefile_versions = "40.."]
+    #[savefile_default_val = "2"]
+    pub capture_band_step: u32, // Smallest change to an equaliser dial that's captured
+    #[savefile_versions = "40.."]
+    #[savefile_default_val = "2"]
+    pub capture_pan_step: u32, // Smallest change to the pan dial that's captured
+    #[savefile_versions = "40.."]
+    #[savefile_default_val = "100"]
+    pub capture_window_ms: u32, // Changes this soon after a captured one replace it instead of adding another
+    #[savefile_versions = "38.."]
+    #[savefile_default_val = "true"]
+    pub skip_missing: bool, // Whether playback moves on from a recording whose file has gone, dropping it from the list
+    #[savefile_versions = "35.."]
+    #[savefile_default_fn = "nothing_unconverted"]
+    pub unconverted: Vec<String>, // Names of found recordings with a different channel count, waiting on the user to choose whether to convert them
+}
+
+impl Settings {
+    pub fn import_presets(&mut self, path: &str) -> Result<usize, Error> {
+        // Adds the presets from another settings file or a shared presets file to the end of the list, leaving any recordings behind
+        // Every preset is checked first so a bad file doesn't add some of them
+        let presets = if Self::shared_presets(path) {
+            let text = match fs::read_to_string(path) {
+                Ok(value) => value,
+                Err(_) => return Err(Error::ReadError(path.to_string())),
+            };
+            text.lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| Preset::from_shared(line, path))
+                .collect::<Result<Vec<Preset>, Error>>()?
+        } else {
+            let other: Settings = match load_file(path, SAVE_VERSION) {
+                Ok(value) => value,
+                Err(error) => return Err(load_error(error, path)),
+            };
+            if let Some(preset) = other.presets.iter().find(|preset| preset.clone().clamp()) {
+                return Err(Error::PresetRangeError(preset.name.clone()));
+            }
+            other.presets
+        };
+        if presets.is_empty() {
+            return Err(Error::NoPresetsError);
+        }
+        let count = presets.len();
+        for mut preset in presets {
+            let taken: Vec<String> = self
+                .presets
+                .iter()
+                .map(|preset| preset.name.clone())
+                .collect();
+            preset.name = unique_name(preset.name, &taken);
+            self.presets.push(preset);
+        }
+        Ok(count)
+    }
+
+    pub fn export_presets(&self, path: &str) -> Result<usize, Error> {
+        // Writes every preset to a shared presets file with the bands in decibels so they mean the same thing outside the app
+        let path = if Self::shared_presets(path) {
+            path.to_string()
+        } else {
+            format!("{}.{}", path, SHARED_PRESETS_EXTENSION)
+        };
+        let mut text = String::from(
+            "# Name, sub bass dB, bass dB, low mids dB, high mids dB, treble dB, pan dial\n",
+        );
+        for preset in &self.presets {
+            text.push_str(&preset.to_shared());
+            text.push('\n');
+        }
+        match fs::write(&path, text) {
+            Ok(_) => Ok(self.presets.len()),
+            Err(_) => Err(Error::SaveError),
+        }
+    }
+
+    fn shared_presets(path: &str) -> bool {
+        // Whether a path is a shared presets file instead of a settings file
+        Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(SHARED_PRESETS_EXTENSION))
+    }
+
+    pub fn reset_automation(&self) -> (usize, Option<Error>) {
+        // Replaces every recording's snapshot with an empty one, returning how many were reset and the first thing that failed
+        // Only recordings are gone through so the settings file is never touched
+        let mut reset = 0;
+        let mut failed = None;
+        for recording in &self.recordings {
+            if File::reserved(&recording.name) {
+                continue;
+            }
+            match SnapShot::create(&recording.name) {
+                Some(error) => {
+                    failed.get_or_insert(error);
+                }
+                None => reset += 1,
+            }
+        }
+        (reset, failed)
+    }
+
+    pub fn move_preset(&mut self, from: usize, to: usize) -> bool {
+        // Moves a preset to another place in the list, stopping at either end, and returns whether it moved
+        if from >= self.presets.len() {
+            return false;
+        }
+        let to = to.min(self.presets.len() - 1);
+        if to == from {
+            return false;
+        }
+        let preset = self.presets.remove(from);
+        self.presets.insert(to, preset);
+        true
+    }
+
+    pub fn capture_filter(&self) -> CaptureFilter {
+        // Which dial changes are kept while capturing inputs
+        if self.capture_everything {
+            return CaptureFilter::everything();
+        }
+        let band = self.capture_band_step.max(1) as i32;
+        CaptureFilter {
+            steps: [
+                band,
+                band,
+                band,
+                band,
+                band,
+                self.capture_pan_step.max(1) as i32,
+            ],
+            window: (self.capture_window_ms as u128 / PLAYER_TICK.as_millis()) as i32,
+        }
+    }
+
+    pub fn clamp(&mut self) -> bool {
+        // Pulls every preset and recording back into range and returns whether any were out of it
+        let mut clamped = false;
+        for preset in self.presets.iter_mut() {
+            clamped |= preset.clamp();
+        }
+        for recording in self.recordings.iter_mut() {
+            clamped |= recording.clamp();
+        }
+        for value in self.default_dials.iter_mut() {
+            clamped |= clamp_dial(value);
+        }
+        clamped
+    }
+
+    pub fn new() -> Settings {
+        // Creates empty settings data
+        Settings {
+            presets: vec![],
+            recordings: vec![],
+            recording_warn_after: DEFAULT_RECORDING_WARN_AFTER,
+            metronome: false,
+            metronome_bpm: DEFAULT_METRONOME_BPM,
+            beats_per_bar: DEFAULT_BEATS_PER_BAR,
+            monitor_input: false,
+            monitor_eq: true,
+            default_dials: flat_dials(),
+            post_record_actions: no_post_record_actions(),
+            playback: default_playback(),
+            shuffle: false,
+            band_labels: default_band_labels(),
+            fallback_limit: DEFAULT_FALLBACK_LIMIT,
+            auto_save_minutes: 0,
+            queue: empty_queue(),
+            follow_queue: false,
+            pan_glide_ms: DEFAULT_PAN_GLIDE_MS,
+            delete_empty_recordings: true,
+            input_channels: default_input_channels(),
+            naming_scheme: default_naming_scheme(),
+            name_prefix: default_name_prefix(),
+            parameter_glide_ms: DEFAULT_PARAMETER_GLIDE_MS,
+            raise_thread_priority: false,
+            trim_leading_silence: true,
+            capture_autosave_seconds: DEFAULT_CAPTURE_AUTOSAVE_SECONDS,
+            output_device: default_output_device(),
+            min_sound_ms: DEFAULT_MIN_SOUND_MS,
+            window: no_window_geometry(),
+            theme: default_theme(),
+            auto_next_gap_seconds: 0,
+            snapshot_folder: true,
+            name_collision: default_name_collision(),
+            default_preset: no_default_preset(),
+            library_channels: default_library_channels(),
+            unconverted: nothing_unconverted(),
+            resample: default_resample_mode(),
+            skip_missing: true,
+            click_sound: default_click_sound(),
+            capture_everything: true,
+            new_preset_start: default_new_preset_start(),
+            fallback_grouping: default_fallback_grouping(),
+            push_updates: true,
+            capture_band_step: 2,
+            capture_pan_step: 2,
+            capture_window_ms: 100,
+            export_format: default_export_format(),
+            export_directory: no_export_directory(),
+        }
+    }
+
+    pub fn new_recording_dials(&self) -> [i32; 6] {
+        // Dial values newly found recordings start with, a chosen preset is used instead of the default dials
+        match &self.default_preset {
+            Some(name) => match self.presets.iter().find(|preset| preset.name == *name) {
+                Some(preset) => preset.parse(),
+                None => flat_dials(), // The preset has been deleted
+            },
+            None => self.default_dials,
+        }
+    }
+
+    pub fn next_in_queue(&self, current: usize) -> (Option<usize>, bool) {
+        // Finds the recording queued after the current one, passing over names that no longer have a recording
+        // Returns its index and whether anything was passed over
+        let start = match self.recordings.get(current).and_then(|recording| {
+            self.queue
+                .iter()
+                .position(|queued| *queued == recording.name)
+        }) {
+            Some(position) => position + 1,
+            None => 0, // Starts the queue from the top if the current recording isn't in it
+        };
+        let mut skipped = false;
+        for offset in 0..self.queue.len() {
+            let queued = &self.queue[(start + offset) % self.queue.len()];
+            match self
+                .recordings
+                .iter()
+                .position(|recording| recording.name == *queued)
+            {
+                Some(index) => return (Some(index), skipped),
+                None => skipped = true,
+            }
+        }
+
+        (None, skipped)
+    }
+
+    pub fn follow_renames(&mut self, before: &[String]) {
+        // Points queued names at the new names of recordings that were renamed
+        // before holds every recording's name from before the rename, in the same order
+        for queued in self.queue.iter_mut() {
+            if let Some(index) = before.iter().position(|old| old == queued) {
+                if let Some(recording) = self.recordings.get(index) {
+                    *queued = recording.name.clone();
+                }
+            }
+        }
+        for waiting in self.unconverted.iter_mut() {
+            if let Some(index) = before.iter().position(|old| old == waiting) {
+                if let Some(recording) = self.recordings.get(index) {
+                    *waiting = recording.name.clone();
+                }
+            }
+        }
+    }
+
+    pub fn imported(&mut self, name: &String) -> Recording {
+        // Adds a recording found in the folder, noting its format and asking about converting it if it doesn't match the library
+        let spec = wav_spec(name);
+        if let Some(spec) = spec {
+            if self.needs_conversion(spec) && !self.unconverted.contains(name) {
+                self.unconverted.push(name.clone());
+            }
+        }
+        Recording {
+            original_channels: spec.map(|spec| spec.channels),
+            original_sample_rate: spec.map(|spec| spec.sample_rate),
+            ..Recording::from(name, self.new_recording_dials())
+        }
+    }
+
+    pub fn needs_conversion(&self, spec: WavSpec) -> bool {
+        // Checks if a found recording differs from the library in a way that's converted when it's found
+        spec.channels != self.library_channels
+            || (self.resample == ResampleMode::OnImport
+                && spec.sample_rate != RECORDING_SAMPLE_RATE)
+    }
+
+    pub fn library_format(&self) -> String {
+        // Names what found recordings are converted to for messages
+        if self.resample == ResampleMode::OnImport {
+            format!(
+                "{} at {} Hz",
+                channel_label(self.library_channels),
+                RECORDING_SAMPLE_RATE
+            )
+        } else {
+            channel_label(self.library_channels)
+        }
+    }
+
+    pub fn convert_imports(&mut self, convert: bool) -> Vec<Error> {
+        // Converts the recordings waiting on a choice to the library's format, or leaves them as they are
+        let mut errors = vec![];
+        if convert {
+            for name in &self.unconverted {
+                if File::exists(name.clone(), &self.recordings) {
+                    let sample_rate = match self.resample {
+                        ResampleMode::OnImport => Some(RECORDING_SAMPLE_RATE),
+                        _ => None,
+                    };
+                    if let Some(error) = convert_recording(name, self.library_channels, sample_rate)
+                    {
+                        errors.push(error);
+                    }
+                }
+            }
+        }
+        self.unconverted.clear();
+        errors
+    }
+
+    pub fn unconverted_summary(&self) -> SharedString {
+        // Asks about converting found recordings, nothing when none are waiting
+        let waiting = self
+            .unconverted
+            .iter()
+            .filter(|name| File::exists((*name).clone(), &self.recordings))
+            .count();
+        match waiting {
+            0 => SharedString::new(),
+            1 => SharedString::from(format!(
+                "A recording found in the folder isn't {} ... Convert it?",
+                self.library_format()
+            )),
+            count => SharedString::from(format!(
+                "{} recordings found in the folder aren't {} ... Convert them?",
+                count,
+                self.library_format()
+            )),
+        }
+    }
+
+    pub fn recording_names(&self) -> Vec<String> {
+        // Gets the name of every recording in order
+        self.recordings
+            .iter()
+            .map(|recording| recording.name.clone())
+            .collect()
+    }
+
+    pub fn queue_summary(&self) -> SharedString {
+        // Lists the queue for the UI, marking recordings that have gone missing
+        if self.queue.is_empty() {
+            return SharedString::from("Empty");
+        }
+        SharedString::from(
+            self.queue
+                .iter()
+                .map(|queued| {
+                    if File::exists(queued.clone(), &self.recordings) {
+                        queued.clone()
+                    } else {
+                        format!("{} (missing)", queued)
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    }
+
+    pub fn band_label(&self, band: usize) -> String {
+        // Gets a band's name, going back to the original one if it was left blank
+        match self.band_labels.get(band) {
+            Some(label) if !label.trim().is_empty() => label.clone(),
+            _ => default_band_labels().get(band).cloned().unwrap_or_default(),
+        }
+    }
+
+    pub fn rebuild() -> Result<Settings, Error> {
+        // Creates settings with default dial values for every recording on disk
+        let path = File::get_directory()?;
+        let File::Names(names) = File::search(&Disk, &path, "wav", true)?;
+
+        let mut settings = Settings::new();
+        for name in &names {
+            settings
+                .recordings
+                .push(Recording::from(name, settings.default_dials));
+        }
+
+        Ok(settings)
+    }
+
+    pub fn get_index_data(&self) -> IndexData {
+        // Gets the length of each list in the settings struct
+        IndexData {
+            preset_length: self.presets.len(),
+            recording_length: self.recordings.len(),
+        }
+    }
+
+    pub fn apply(&mut self, storage: &dyn Storage, changes: &Changes) -> (Vec<Error>, bool) {
+        // Applies edits made in the UI and syncs with the files on disk, returning any errors and whether something was deleted
+        let index_data = self.get_index_data();
+        let mut errors = vec![];
+        let mut deleted = false;
+
+        // Check for new preset creation
+        if changes.new_preset {
+            // Update the settings data with a new preset made from the chosen starting values
+            let mut preset = match self.new_preset_start {
+                NewPresetStart::Current => Preset::from(changes.dials, changes.filter_kinds),
+                NewPresetStart::Flat => Preset::from(flat_dials(), default_filter_kinds()),
+                NewPresetStart::Template => {
+                    Preset::from(self.new_recording_dials(), default_filter_kinds())
+                }
+            };
+            let taken: Vec<String> = self
+                .presets
+                .iter()
+                .map(|preset| preset.name.clone())
+                .collect();
+            preset.name = Preset::next_name(&taken);
+            self.presets.push(preset);
+        }
+
+        // Check for preset deletion
+        if let Some(index) = changes.deleted_preset {
+            if self.presets.len() > index {
+                self.presets.remove(index); // Deletes deleted preset from settings data
+                deleted = true;
+            }
+        }
+
+        // Check for preset rename
+        if let Some(names) = &changes.preset_names {
+            // The preset new recordings start with is kept through a rename
+            let default = self
+                .default_preset
+                .as_ref()
+                .and_then(|name| self.presets.iter().position(|preset| preset.name == *name));
+            // Only presets whose name was edited are renamed, so a list that's out of step can't overwrite the others
+            for (preset, name) in self.presets.iter_mut().zip(names) {
+                let name = name.trim();
+                if !name.is_empty() && preset.name != name {
+                    preset.name = name.to_string();
+                }
+            }
+            if let Some(index) = default {
+                self.default_preset = Some(self.presets[index].name.clone());
+            }
+        }
+
+        // Check for recording edits
+        if changes.dials_edited && changes.current_recording < index_data.recording_length {
+            let position = changes.current_recording;
+            self.recordings[position] = Recording {
+                had_dropouts: self.recordings[position].had_dropouts,
+                playback_override: self.recordings[position].playback_override,
+                loop_start: self.recordings[position].loop_start,
+                loop_end: self.recordings[position].loop_end,
+                colour: self.recordings[position].colour,
+                start_offset: self.recordings[position].start_offset,
+                original_channels: self.recordings[position].original_channels,
+                original_sample_rate: self.recordings[position].original_sample_rate,
+                filter_kinds: changes.filter_kinds,
+                high_pass: changes.high_pass.clamp(HIGH_PASS_OPEN, LOW_PASS_OPEN),
+                low_pass: changes.low_pass.clamp(HIGH_PASS_OPEN, LOW_PASS_OPEN),
+                invert_left: changes.invert_left,
+                invert_right: changes.invert_right,
+                auto_makeup: changes.auto_makeup,
+                ..Recording::from(&self.recordings[position].name, changes.dials)
+            };
+            // Updates settings data with edited values
+        }
+
+        // Check for recording deletion
+        if let Some(index) = changes.deleted_recording {
+            if index < self.recordings.len() {
+                self.recordings.remove(index); // Removes recording data from settings
+            }
+            deleted = true;
+        }
+
+        // Check for recording renaming
+        if let Some(names) = &changes.recording_names {
+            let before = self.recording_names();
+            self.recordings = match Recording::rename(storage, &self.recordings, names) {
+                // Renames recording
+                Ok(value) => value,
+                Err(error) => {
+                    errors.push(error.1);
+                    error.0
+                }
+            };
+            self.follow_renames(&before);
+        }
+
+        // Sync recording data with any changes that might have been made to the application files
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(error) => {
+                errors.push(error);
+                String::new()
+            }
+        };
+        let mut file_names = match File::search(storage, &path, "wav", true) {
+            // Gets wav file names
+            Ok(File::Names(value)) => value,
+            Err(error) => {
+                errors.push(error);
+                vec![String::from("Couldn't read files")]
+            }
+        };
+
+        // Moves recordings with reserved names out of the way so they never get a snapshot that overwrites app data
+        for name in file_names.iter_mut() {
+            if File::reserved(name) {
+                match File::rename_reserved(storage, name) {
+                    Ok(value) => {
+                        *name = value;
+                        errors.push(Error::ReservedNameError);
+                    }
+                    Err(error) => errors.push(error),
+                }
+            }
+        }
+        file_names.retain(|name| !File::reserved(name)); // Leaves out any that couldn't be renamed
+        self.fallback_grouping.group(&mut file_names);
+
+        let mut snapshot_names = match File::snapshot_directory()
+            .and_then(|snapshots| File::search(storage, &snapshots, "bin", true))
+        {
+            // Gets binary file names
+            Ok(File::Names(value)) => value,
+            Err(error) => {
+                errors.push(error);
+                vec![String::from("Couldn't read files")]
+            }
+        };
+
+        snapshot_names.retain(|name| !File::reserved(name)); // Removes the settings file from the list of binary files
+
+        let mut updated_recordings = vec![];
+
+        if file_names.len() > 0 {
+            for name in 0..file_names.len() {
+                // Loops over all the names
+                if self.recordings.len() > 0 {
+                    for recording in 0..self.recordings.len() {
+                        if self.recordings[recording].name == file_names[name] {
+                            // If the recording is known, then add the old recording to the list
+                            updated_recordings.push(self.recordings[recording].clone());
+                            break;
+                        }
+                        if recording == self.recordings.len() - 1 {
+                            updated_recordings.push(self.imported(&file_names[name]));
+                            // If it's unknown then create a new recording
+                        }
+                    }
+                } else {
+                    updated_recordings.push(self.imported(&file_names[name]));
+                    // Adds new recording to settings data
+                }
+
+                // Syncs snapshots
+                if !snapshot_names.contains(&file_names[name]) {
+                    // Only creates a snapshot when the recording doesn't have one so existing ones are never written over
+                    match SnapShot::create(&file_names[name]) {
+                        Some(error) => {
+                            errors.push(error);
+                        }
+                        None => (),
+                    }
+                }
+            }
+        }
+
+        self.recordings = updated_recordings; // Updates the settings data with the updated data
+
+        (errors, deleted)
+    }
+}
+
+// -------- Functions --------
+pub fn flat_dials() -> [i32; 6] {
+    // Settings saved before default dials existed start new recordings flat
+    [0, 0, 0, 0, 0, 0]
+}
+
+pub fn clamp_dial(value: &mut i32) -> bool {
+    // Pulls a dial value back into range and returns whether it was out of it
+    if *value < MIN_DIAL || *value > MAX_DIAL {
+        *value = (*value).clamp(MIN_DIAL, MAX_DIAL);
+        true
+    } else {
+        false
+    }
+}
+
+pub fn dial_readout(index: usize, value: i32) -> SharedString {
+    // Describes what a dial value does using the same conversions as playback, index 5 is pan and the rest are bands
+    if index == 5 {
+        let panning = dial_to_panning(value);
+        if panning == 0.0 {
+            SharedString::from("C")
+        } else {
+            SharedString::from(format!(
+                "{} {:.0}%",
+                if panning < 0.0 { "L" } else { "R" },
+                (panning.abs() * 100.0).min(100.0) // Kira stops panning at fully left or right
+            ))
+        }
+    } else if value == SILENT_DIAL {
+        SharedString::from("Muted")
+    } else if value == 0 {
+        SharedString::from("0 dB")
+    } else {
+        SharedString::from(format!("{:+.0} dB", dial_to_gain(value)))
+    }
+}
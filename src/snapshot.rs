@@ -0,0 +1,246 @@
+// Captured inputs that are played back alongside a recording
+
+// -------- Imports --------
+use crate::*; // Constants, errors, and the other modules through the crate root
+
+// -------- Structs --------
+// Which dial changes are kept while capturing inputs
+#[derive(Clone, Copy, PartialEq)]
+pub struct CaptureFilter {
+    pub steps: [i32; 6], // Smallest change to each dial that's captured
+    pub window: i32, // Ticks after a captured frame in which later changes replace it instead of adding another
+}
+
+impl CaptureFilter {
+    pub fn everything() -> CaptureFilter {
+        // Captures every change as soon as it's made
+        CaptureFilter {
+            steps: [1; 6],
+            window: 0,
+        }
+    }
+}
+
+// Recorded input data
+#[derive(Savefile, Clone, PartialEq)]
+pub struct SnapShot {
+    pub frames: Vec<([i32; 6], i32)>, // Dial values, frame
+}
+
+impl SnapShot {
+    pub fn clamp(&mut self) -> bool {
+        // Pulls every frame's dial values back into range and returns whether any were out of it
+        let mut clamped = false;
+        for (values, _) in self.frames.iter_mut() {
+            for value in values.iter_mut() {
+                clamped |= clamp_dial(value);
+            }
+        }
+        clamped
+    }
+
+    pub fn drop_invalid(&mut self, limit: Option<i32>) -> bool {
+        // Leaves out frames before the start or, if the recording's length in ticks is known, after its end
+        // The player counts ticks up from zero so these would never be reached, and a negative one would wrap when cast
+        let before = self.frames.len();
+        self.frames
+            .retain(|(_, tick)| *tick >= 0 && limit.map_or(true, |limit| *tick <= limit));
+        let dropped = self.frames.len() != before;
+        if self.frames.is_empty() {
+            self.frames.push(([0, 0, 0, 0, 0, 0], 0)); // The player always starts from a first frame
+        }
+        dropped
+    }
+
+    pub fn load_or_new(name: &str) -> Result<Option<SnapShot>, Error> {
+        // Loads a recording's snapshot, or nothing if it doesn't have one on disk
+        // Any other failure is returned so automation that can't be read isn't mistaken for none at all
+        let snapshots = File::snapshot_directory()?;
+        if !Path::new(&format!("{}/{}.bin", snapshots, name)).is_file() {
+            return Ok(None);
+        }
+        match load(name, LoadType::Snapshot)? {
+            DataType::SnapShot(value) => Ok(Some(value)),
+            _ => Err(Error::LoadError(name.to_string())),
+        }
+    }
+
+    pub fn create(name: &str) -> Option<Error> {
+        // Saves an empty snapshot to disk or returns an error
+        match SnapShot::new().save(name) {
+            Some(error) => {
+                return Some(error);
+            }
+            None => {}
+        };
+
+        None
+    }
+
+    pub fn new() -> SnapShot {
+        // New snapshot in memory
+        SnapShot {
+            frames: vec![([0, 0, 0, 0, 0, 0], 0)],
+        }
+    }
+
+    pub fn none() -> SnapShot {
+        // Snapshot without any frames, used when captured inputs replace the existing ones
+        SnapShot { frames: vec![] }
+    }
+
+    pub fn merged(self, base: &SnapShot) -> SnapShot {
+        // Adds captured frames onto an existing snapshot, keeping the newer frame when both land on the same tick
+        let mut merged = base.clone();
+        for frame in self.frames {
+            match merged.frames.iter().position(|(_, tick)| *tick == frame.1) {
+                Some(index) => merged.frames[index] = frame,
+                None => merged.frames.push(frame),
+            }
+        }
+        merged.frames.sort_by_key(|(_, tick)| *tick);
+        merged
+    }
+
+    pub fn section(&self, start: i32, end: Option<i32>) -> SnapShot {
+        // Gets the frames from the start tick up to the end tick, moved back so the start tick is the beginning
+        // The values active at the start are put at the beginning so the section sounds the same as it did
+        let mut frames = match self
+            .frames
+            .iter()
+            .take_while(|(_, tick)| *tick <= start)
+            .last()
+        {
+            Some((values, _)) => vec![(*values, 0)],
+            None => vec![([0, 0, 0, 0, 0, 0], 0)],
+        };
+        for (values, tick) in &self.frames {
+            if *tick > start && end.map_or(true, |end| *tick < end) {
+                frames.push((*values, tick - start));
+            }
+        }
+        SnapShot { frames }
+    }
+
+    pub fn changed_by(previous: [i32; 6], next: [i32; 6], steps: [i32; 6]) -> bool {
+        // Checks if any dial value has moved at least as far as its step
+        (0..6).any(|number| (previous[number] - next[number]).abs() >= steps[number].max(1))
+    }
+
+    pub fn capture(&mut self, values: [i32; 6], tick: i32, filter: CaptureFilter) -> bool {
+        // Adds dial values if they've moved far enough from the last captured ones, returns whether a frame was added
+        // The first frame only holds the starting values so it's never replaced
+        let Some(&(last, last_tick)) = self.frames.last() else {
+            self.frames.push((values, tick));
+            return true;
+        };
+        if !SnapShot::changed_by(last, values, filter.steps) {
+            return false;
+        }
+        if self.frames.len() > 1 && tick - last_tick < filter.window {
+            // Quick changes in a row collapse into the frame they started at
+            if let Some(frame) = self.frames.last_mut() {
+                frame.0 = values;
+            }
+            return false;
+        }
+        self.frames.push((values, tick));
+        true
+    }
+
+    pub fn edited(previous: [i32; 6], next: [i32; 6]) -> bool {
+        // Checks if the dial values have changed
+        for number in 0..6 {
+            if previous[number] == next[number] {
+                continue;
+            } else {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn is_empty(&self) -> bool {
+        // Checks if nothing has been captured, captured frames only get saved when the dials move away from flat
+        self.frames
+            .iter()
+            .all(|(values, _)| *values == [0, 0, 0, 0, 0, 0])
+    }
+
+    pub fn frame_at(&self, tick: usize) -> usize {
+        // Gets the index of the first frame at or after a player tick, used when playback doesn't start from the beginning
+        self.frames
+            .iter()
+            .take_while(|(_, frame)| (*frame as usize) < tick)
+            .count()
+    }
+
+    pub fn save(self, name: &str) -> Option<Error> {
+        // Saves a snapshot to disk that doesn't have to be empty - Used when a snapshot already exists
+        save(DataType::SnapShot(self), name)
+    }
+
+    pub fn save_capture(&self, file: &str) -> Option<Error> {
+        // Saves the inputs captured so far next to the recording so they aren't lost if the app closes while capturing
+        // Takes the recording's path without its extension, the same as the player uses
+        match save_file(format!("{}.capture", file), SAVE_VERSION, self) {
+            Ok(_) => None,
+            Err(_) => Some(Error::SaveError),
+        }
+    }
+
+    pub fn discard_capture(file: &str) {
+        // Removes the autosaved capture once the real snapshot has been written, there may not be one
+        let _ = remove_file(format!("{}.capture", file));
+    }
+
+    pub fn leftover_captures(storage: &dyn Storage) -> Result<Vec<String>, Error> {
+        // Finds recordings that were still capturing inputs when the app last closed
+        let path = File::get_directory()?;
+        let File::Names(names) = File::search(storage, &path, "capture", false)?;
+        Ok(names)
+    }
+
+    pub fn recover_capture(name: &str, keep: bool) -> Option<Error> {
+        // Makes an autosaved capture the recording's snapshot, or throws it away
+        let path = match File::get_directory() {
+            Ok(value) => value,
+            Err(error) => return Some(error),
+        };
+        let capture = format!("{}/{}.capture", path, name);
+        if keep {
+            let snapshot: SnapShot = match load_file(&capture, SAVE_VERSION) {
+                Ok(value) => value,
+                Err(error) => return Some(load_error(error, name)),
+            };
+            if let Some(error) = snapshot.save(name) {
+                return Some(error); // Keeps the capture so it can be tried again
+            }
+        }
+
+        match remove_file(&capture) {
+            Ok(_) => None,
+            Err(_) => Some(Error::DeleteError),
+        }
+    }
+}
+
+pub fn with_overrides(values: [i32; 6], overrides: [Option<i32>; 6]) -> [i32; 6] {
+    // Puts the values of dials the user has taken over in place of the captured ones
+    let mut values = values;
+    for dial in 0..6 {
+        if let Some(value) = overrides[dial] {
+            values[dial] = value;
+        }
+    }
+    values
+}
+
+pub fn pan_glide(glide: Duration, ticks_to_next: Option<usize>) -> Duration {
+    // Shortens the pan glide so it finishes before the next captured frame, keeping quick moves in time
+    match ticks_to_next {
+        Some(ticks) => glide.min(PLAYER_TICK * ticks as u32),
+        None => glide,
+    }
+}
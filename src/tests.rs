@@ -0,0 +1,2205 @@
+// Integration tests that run the file handling and syncing against a temporary data folder
+use super::*;
+use slint::Model; // Reading back the lists that are sent to the UI
+use std::{cell::RefCell, sync::MutexGuard};
+
+// The data folder is set through the environment so only one test can use it at a time
+static DATA_DIR_LOCK: Mutex<()> = Mutex::new(());
+
+// A temporary data folder that's removed when the test finishes
+struct TestDir {
+    path: String,
+    _lock: MutexGuard<'static, ()>, // Held until the folder has been removed
+}
+
+impl TestDir {
+    fn new(name: &str) -> TestDir {
+        // Creates an empty folder and points the app at it
+        let lock = match DATA_DIR_LOCK.lock() {
+            Ok(value) => value,
+            Err(poisoned) => poisoned.into_inner(), // A failed test shouldn't stop the rest from running
+        };
+        let path = env::temp_dir().join(format!("audio-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        let path = path.to_string_lossy().to_string();
+        env::set_var(DATA_DIR_VARIABLE, &path);
+        SNAPSHOTS_IN_FOLDER.store(false, AtomicOrdering::Relaxed); // Tests that use the snapshot folder turn it on themselves
+
+        TestDir { path, _lock: lock }
+    }
+
+    fn write_wav(&self, name: &str, sample_rate: u32, seconds: f32) {
+        // Writes a stereo sine wave in the same format the recorder uses
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let mut writer = WavWriter::create(format!("{}/{}.wav", self.path, name), spec).unwrap();
+        for frame in 0..(seconds * sample_rate as f32) as u32 {
+            let sample = (frame as f32 * TAU * 440.0 / sample_rate as f32).sin() * 0.5;
+            writer.write_sample(sample).unwrap();
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    fn has(&self, name: &str, extension: &str) -> bool {
+        // Checks if a file is in the folder
+        Path::new(&format!("{}/{}.{}", self.path, name, extension)).is_file()
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        env::remove_var(DATA_DIR_VARIABLE);
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+// Storage that keeps file names in memory and can be told to fail, so error handling can be tested without real I/O
+struct MockStorage {
+    files: RefCell<Vec<String>>,
+    fail_read: bool,
+    fail_rename: bool,
+    fail_delete: bool,
+}
+
+impl MockStorage {
+    fn new(files: &[&str]) -> MockStorage {
+        MockStorage {
+            files: RefCell::new(files.iter().map(|file| file.to_string()).collect()),
+            fail_read: false,
+            fail_rename: false,
+            fail_delete: false,
+        }
+    }
+
+    fn has(&self, file: &str) -> bool {
+        self.files.borrow().contains(&file.to_string())
+    }
+
+    fn file_name(path: &str) -> String {
+        // Files are only tracked by name so the data folder doesn't matter
+        Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Storage for MockStorage {
+    fn read_dir(&self, path: &str) -> Result<Vec<PathBuf>, Error> {
+        if self.fail_read {
+            return Err(Error::ReadError(path.to_string()));
+        }
+        Ok(self
+            .files
+            .borrow()
+            .iter()
+            .map(|file| Path::new(path).join(file))
+            .collect())
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.has(&MockStorage::file_name(&path.to_string_lossy()))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), Error> {
+        let from = MockStorage::file_name(from);
+        let mut files = self.files.borrow_mut();
+        match files.iter().position(|file| *file == from) {
+            Some(index) if !self.fail_rename => {
+                files[index] = MockStorage::file_name(to);
+                Ok(())
+            }
+            _ => Err(Error::RenameError),
+        }
+    }
+
+    fn delete(&self, path: &str) -> Result<(), Error> {
+        let path = MockStorage::file_name(path);
+        let mut files = self.files.borrow_mut();
+        match files.iter().position(|file| *file == path) {
+            Some(index) if !self.fail_delete => {
+                files.remove(index);
+                Ok(())
+            }
+            _ => Err(Error::DeleteError),
+        }
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<(), Error> {
+        if !self.has(&MockStorage::file_name(from)) {
+            return Err(Error::SaveError);
+        }
+        self.files.borrow_mut().push(MockStorage::file_name(to));
+        Ok(())
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        self.has(&MockStorage::file_name(path)).then_some(0) // Only names are kept, so every file is empty
+    }
+
+    // Folders are always there since files are only tracked by name
+    fn is_dir(&self, _path: &str) -> bool {
+        true
+    }
+
+    fn create_dir(&self, _path: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn remove_dir(&self, _path: &str) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn names(settings: &Settings) -> Vec<String> {
+    // Gets the names of every recording in order
+    settings
+        .recordings
+        .iter()
+        .map(|recording| recording.name.clone())
+        .collect()
+}
+
+#[test]
+fn search_orders_numbers_by_value() {
+    let dir = TestDir::new("search");
+    for name in ["Recording 10", "Recording 2", "Recording 1"] {
+        dir.write_wav(name, RECORDING_SAMPLE_RATE, 0.1);
+    }
+
+    let Ok(File::Names(found)) = File::search(&Disk, &dir.path, "wav", true) else {
+        panic!("Couldn't search the test folder");
+    };
+    assert_eq!(found, vec!["Recording 1", "Recording 2", "Recording 10"]);
+}
+
+#[test]
+fn sync_adds_new_recordings_and_snapshots() {
+    let dir = TestDir::new("sync_new");
+    dir.write_wav("Recording 1", RECORDING_SAMPLE_RATE, 0.1);
+    dir.write_wav("Recording 2", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    let (errors, _) = settings.apply(&Disk, &Changes::none());
+
+    assert!(errors.is_empty());
+    assert_eq!(names(&settings), vec!["Recording 1", "Recording 2"]);
+    assert!(dir.has("Recording 1", "bin"));
+    assert!(dir.has("Recording 2", "bin"));
+}
+
+#[test]
+fn sync_keeps_known_recordings_and_drops_missing_ones() {
+    let dir = TestDir::new("sync_missing");
+    dir.write_wav("Kept", RECORDING_SAMPLE_RATE, 0.1);
+    dir.write_wav("Removed", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    settings.recordings[0].bass = 3;
+    fs::remove_file(format!("{}/Removed.wav", dir.path)).unwrap();
+    settings.apply(&Disk, &Changes::none());
+
+    assert_eq!(names(&settings), vec!["Kept"]);
+    assert_eq!(settings.recordings[0].bass, 3);
+}
+
+#[test]
+fn sync_applies_dial_edits_to_the_current_recording() {
+    let dir = TestDir::new("sync_edits");
+    dir.write_wav("First", RECORDING_SAMPLE_RATE, 0.1);
+    dir.write_wav("Second", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    settings.apply(
+        &Disk,
+        &Changes {
+            dials: [1, 2, 3, 4, 5, 6],
+            dials_edited: true,
+            current_recording: 1,
+            ..Changes::none()
+        },
+    );
+
+    assert_eq!(settings.recordings[0].parse(), [0, 0, 0, 0, 0, 0]);
+    assert_eq!(settings.recordings[1].parse(), [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn rename_moves_files_on_disk() {
+    let dir = TestDir::new("rename");
+    dir.write_wav("Old", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    let (errors, _) = settings.apply(
+        &Disk,
+        &Changes {
+            recording_names: Some(vec![String::from("New")]),
+            ..Changes::none()
+        },
+    );
+
+    assert!(errors.is_empty());
+    assert_eq!(names(&settings), vec!["New"]);
+    assert!(dir.has("New", "wav"));
+    assert!(dir.has("New", "bin"));
+    assert!(!dir.has("Old", "wav"));
+}
+
+#[test]
+fn rename_rejects_reserved_and_taken_names() {
+    let dir = TestDir::new("rename_rejected");
+    dir.write_wav("First", RECORDING_SAMPLE_RATE, 0.1);
+    dir.write_wav("Second", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+
+    let (errors, _) = settings.apply(
+        &Disk,
+        &Changes {
+            recording_names: Some(vec![String::from("settings"), String::from("Second")]),
+            ..Changes::none()
+        },
+    );
+    assert!(errors.contains(&Error::SaveFileRenameError));
+
+    let (errors, _) = settings.apply(
+        &Disk,
+        &Changes {
+            recording_names: Some(vec![String::from("Second"), String::from("Second")]),
+            ..Changes::none()
+        },
+    );
+    assert!(errors.contains(&Error::ExistsError));
+
+    assert_eq!(names(&settings), vec!["First", "Second"]);
+    assert!(dir.has("First", "wav"));
+}
+
+#[test]
+fn delete_removes_recording_and_snapshot() {
+    let dir = TestDir::new("delete");
+    dir.write_wav("Gone", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    assert!(File::delete(&Disk, String::from("Gone")).is_none());
+    let (errors, deleted) = settings.apply(
+        &Disk,
+        &Changes {
+            deleted_recording: Some(0),
+            ..Changes::none()
+        },
+    );
+
+    assert!(errors.is_empty());
+    assert!(deleted);
+    assert!(settings.recordings.is_empty());
+    assert!(!dir.has("Gone", "wav"));
+    assert!(!dir.has("Gone", "bin"));
+}
+
+#[test]
+fn search_skips_other_extensions() {
+    let storage = MockStorage::new(&["b.wav", "a.wav", "a.bin", "notes.txt"]);
+
+    let Ok(File::Names(found)) = File::search(&storage, "data", "wav", true) else {
+        panic!("Couldn't search the mock storage");
+    };
+    assert_eq!(found, vec!["a", "b"]);
+}
+
+#[test]
+fn search_reports_read_errors() {
+    let storage = MockStorage {
+        fail_read: true,
+        ..MockStorage::new(&["a.wav"])
+    };
+
+    assert!(
+        File::search(&storage, "data", "wav", true) == Err(Error::ReadError("data".to_string()))
+    );
+}
+
+#[test]
+fn rename_moves_recording_and_snapshot() {
+    let storage = MockStorage::new(&["Old.wav", "Old.bin"]);
+
+    assert!(File::rename(&storage, &String::from("Old"), String::from("New")).is_none());
+    assert!(storage.has("New.wav"));
+    assert!(storage.has("New.bin"));
+    assert!(!storage.has("Old.wav"));
+}
+
+#[test]
+fn rename_reports_failed_renames() {
+    let storage = MockStorage {
+        fail_rename: true,
+        ..MockStorage::new(&["Old.wav", "Old.bin"])
+    };
+
+    assert!(
+        File::rename(&storage, &String::from("Old"), String::from("New"))
+            == Some(Error::RenameError)
+    );
+    assert!(storage.has("Old.wav"));
+
+    let recordings = vec![Recording::new(&String::from("Old"))];
+    match Recording::rename(&storage, &recordings, &vec![String::from("New")]) {
+        Ok(_) => panic!("Rename should have failed"),
+        Err((_, error)) => assert!(error == Error::RenameError),
+    }
+}
+
+#[test]
+fn delete_reports_failed_deletes() {
+    let storage = MockStorage {
+        fail_delete: true,
+        ..MockStorage::new(&["Gone.wav", "Gone.bin"])
+    };
+    assert!(File::delete(&storage, String::from("Gone")) == Some(Error::DeleteError));
+    assert!(storage.has("Gone.wav"));
+
+    let storage = MockStorage::new(&["Gone.wav"]);
+    assert!(File::delete(&storage, String::from("Gone")).is_none()); // A missing snapshot isn't an error
+    assert!(!storage.has("Gone.wav"));
+}
+
+#[test]
+fn orphans_are_repaired_through_storage() {
+    let _dir = TestDir::new("orphans");
+    let storage = MockStorage::new(&["Kept.wav", "Kept.bin", "Gone.bin"]);
+    let orphans = Orphans {
+        snapshots: vec![String::from("Gone")],
+        corrupt: vec![],
+    };
+    assert!(orphans.repair(&storage).is_none());
+    assert!(!storage.has("Gone.bin"));
+    assert!(storage.has("Kept.bin"));
+
+    let storage = MockStorage {
+        fail_delete: true,
+        ..MockStorage::new(&["Gone.bin"])
+    };
+    assert!(orphans.repair(&storage) == Some(Error::DeleteError));
+    assert!(storage.has("Gone.bin"));
+
+    // Backups are copies, so the original stays where it is
+    let storage = MockStorage::new(&["settings.bin"]);
+    assert!(File::backup(&storage, "settings", "bin").is_none());
+    assert!(storage.has("settings.bin") && storage.has("settings.bin.bak"));
+    assert!(File::backup(&storage, "missing", "bin") == Some(Error::SaveError));
+}
+
+#[test]
+fn new_recordings_start_with_default_dials() {
+    let dir = TestDir::new("default_dials");
+    dir.write_wav("Existing", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    settings.default_dials = [-2, 0, 0, 1, 3, 0];
+    dir.write_wav("Found later", RECORDING_SAMPLE_RATE, 0.1);
+    settings.apply(&Disk, &Changes::none());
+
+    assert_eq!(names(&settings), vec!["Existing", "Found later"]);
+    assert_eq!(settings.recordings[0].parse(), [0, 0, 0, 0, 0, 0]);
+    assert_eq!(settings.recordings[1].parse(), [-2, 0, 0, 1, 3, 0]);
+}
+
+#[test]
+fn quick_rename_only_changes_one_recording() {
+    let storage = MockStorage::new(&["First.wav", "First.bin", "Second.wav", "Second.bin"]);
+    let mut recordings = vec![
+        Recording::from(&String::from("First"), [1, 1, 1, 1, 1, 1]),
+        Recording::from(&String::from("Second"), [2, 2, 2, 2, 2, 2]),
+    ];
+    recordings[1].had_dropouts = true;
+
+    assert!(
+        Recording::rename_one(&storage, &mut recordings, 1, &String::from("Renamed")).is_none()
+    );
+    assert_eq!(recordings[0].name, "First");
+    assert_eq!(recordings[1].name, "Renamed");
+    assert_eq!(recordings[1].parse(), [2, 2, 2, 2, 2, 2]);
+    assert!(recordings[1].had_dropouts);
+    assert!(storage.has("Renamed.wav"));
+    assert!(storage.has("Renamed.bin"));
+}
+
+#[test]
+fn quick_rename_rejects_names_in_use_on_disk() {
+    // 'Unsynced' hasn't been picked up by a sync yet but still can't be overwritten
+    let storage = MockStorage::new(&["First.wav", "First.bin", "Unsynced.wav"]);
+    let mut recordings = vec![Recording::new(&String::from("First"))];
+
+    assert!(
+        Recording::rename_one(&storage, &mut recordings, 0, &String::from("Unsynced"))
+            == Some(Error::ExistsError)
+    );
+    assert!(
+        Recording::rename_one(&storage, &mut recordings, 0, &String::new())
+            == Some(Error::EmptyError)
+    );
+    assert_eq!(recordings[0].name, "First");
+    assert!(storage.has("First.wav"));
+}
+
+#[test]
+fn failed_renames_keep_every_recording() {
+    let storage = MockStorage::new(&["A.wav", "A.bin", "B.wav", "B.bin", "C.wav", "C.bin"]);
+    let recordings = vec![
+        Recording::new(&String::from("A")),
+        Recording::new(&String::from("B")),
+        Recording::new(&String::from("C")),
+    ];
+    let new = vec![
+        String::from("A"),
+        String::from("settings"),
+        String::from("D"),
+    ];
+
+    match Recording::rename(&storage, &recordings, &new) {
+        Ok(_) => panic!("Rename should have failed"),
+        Err((list, error)) => {
+            assert!(error == Error::SaveFileRenameError);
+            let names: Vec<String> = list
+                .iter()
+                .map(|recording| recording.name.clone())
+                .collect();
+            assert_eq!(names, vec!["A", "B", "D"]);
+        }
+    }
+}
+
+#[test]
+fn sync_renames_recordings_with_reserved_names() {
+    let dir = TestDir::new("reserved");
+    dir.write_wav("settings", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    let (errors, _) = settings.apply(&Disk, &Changes::none());
+
+    assert!(errors.contains(&Error::ReservedNameError));
+    assert_eq!(names(&settings), vec!["settings recording"]);
+    assert!(dir.has("settings recording", "wav"));
+    assert!(!dir.has("settings", "wav"));
+    assert!(!dir.has("settings", "bin")); // No snapshot was written over the settings file
+}
+
+#[test]
+fn reserved_names_ignore_case() {
+    let storage = MockStorage::new(&["First.wav", "First.bin"]);
+    let recordings = vec![Recording::new(&String::from("First"))];
+
+    for name in ["settings", "Settings", "SETTINGS"] {
+        assert!(
+            Recording::check_name(&storage, &String::from(name), &recordings)
+                == Some(Error::SaveFileRenameError)
+        );
+    }
+}
+
+#[test]
+fn snapshot_frames_line_up_with_a_resumed_tick() {
+    let snapshot = SnapShot {
+        frames: vec![
+            ([0, 0, 0, 0, 0, 0], 0),
+            ([1, 0, 0, 0, 0, 0], 10),
+            ([2, 0, 0, 0, 0, 0], 25),
+        ],
+    };
+
+    assert_eq!(snapshot.frame_at(0), 0);
+    assert_eq!(snapshot.frame_at(10), 1);
+    assert_eq!(snapshot.frame_at(11), 2);
+    assert_eq!(snapshot.frame_at(100), 3);
+}
+
+#[test]
+fn snapshots_without_dial_changes_are_empty() {
+    assert!(SnapShot::new().is_empty());
+    assert!(SnapShot { frames: vec![] }.is_empty());
+    assert!(!SnapShot {
+        frames: vec![([0, 2, 0, 0, 0, 0], 4)]
+    }
+    .is_empty());
+}
+
+#[test]
+fn small_and_rapid_dial_changes_can_be_left_out_of_captures() {
+    let mut everything = SnapShot::new();
+    assert!(everything.capture([1, 0, 0, 0, 0, 0], 1, CaptureFilter::everything()));
+    assert!(everything.capture([2, 0, 0, 0, 0, 0], 2, CaptureFilter::everything()));
+    assert!(!everything.capture([2, 0, 0, 0, 0, 0], 3, CaptureFilter::everything()));
+    assert_eq!(everything.frames.len(), 3);
+
+    let mut settings = Settings::new();
+    settings.capture_everything = false;
+    settings.capture_band_step = 2;
+    settings.capture_window_ms = 100; // Five ticks
+    let filter = settings.capture_filter();
+    let mut filtered = SnapShot::new();
+    assert!(!filtered.capture([1, 0, 0, 0, 0, 0], 1, filter)); // A one step wobble
+    assert!(filtered.capture([2, 0, 0, 0, 0, 0], 2, filter));
+    assert!(!filtered.capture([4, 0, 0, 0, 0, 0], 4, filter)); // Joins the frame before
+    assert!(filtered.capture([6, 0, 0, 0, 0, 0], 7, filter));
+    assert_eq!(
+        filtered.frames,
+        vec![
+            ([0, 0, 0, 0, 0, 0], 0),
+            ([4, 0, 0, 0, 0, 0], 2),
+            ([6, 0, 0, 0, 0, 0], 7)
+        ]
+    );
+}
+
+#[test]
+fn captured_frames_merge_onto_existing_ones() {
+    let base = SnapShot {
+        frames: vec![([1, 0, 0, 0, 0, 0], 0), ([2, 0, 0, 0, 0, 0], 20)],
+    };
+    let captured = SnapShot {
+        frames: vec![([0, 3, 0, 0, 0, 0], 10), ([0, 4, 0, 0, 0, 0], 20)],
+    };
+
+    let merged = captured.merged(&base);
+    assert_eq!(
+        merged.frames,
+        vec![
+            ([1, 0, 0, 0, 0, 0], 0),
+            ([0, 3, 0, 0, 0, 0], 10),
+            ([0, 4, 0, 0, 0, 0], 20), // The newer frame wins on the same tick
+        ]
+    );
+
+    let replaced = SnapShot {
+        frames: vec![([5, 0, 0, 0, 0, 0], 3)],
+    }
+    .merged(&SnapShot::none());
+    assert_eq!(replaced.frames, vec![([5, 0, 0, 0, 0, 0], 3)]);
+}
+
+#[test]
+fn dial_readouts_match_playback_conversions() {
+    assert_eq!(dial_readout(0, 0), "0 dB");
+    assert_eq!(dial_readout(1, 2), "+8 dB");
+    assert_eq!(dial_readout(2, -3), "-12 dB");
+    assert_eq!(dial_readout(3, SILENT_DIAL), "Muted");
+    assert_eq!(dial_readout(5, 0), "C");
+    assert_eq!(dial_readout(5, -2), "L 30%");
+    assert_eq!(dial_readout(5, 7), "R 100%");
+}
+
+#[test]
+fn normalise_scales_the_loudest_sample_to_the_peak() {
+    let dir = TestDir::new("normalise");
+    dir.write_wav("Quiet", RECORDING_SAMPLE_RATE, 0.1);
+
+    assert!(PostRecordAction::Normalise.run("Quiet", &None, ExportFormat::Float32) == Ok(None));
+
+    let mut reader = WavReader::open(format!("{}/Quiet.wav", dir.path)).unwrap();
+    let peak = reader
+        .samples::<f32>()
+        .map(|sample| sample.unwrap().abs())
+        .fold(0.0_f32, f32::max);
+    assert!((peak - NORMALISE_PEAK).abs() < 0.001);
+    assert!(!dir.has("Quiet", "wav.tmp"));
+}
+
+#[test]
+fn normalise_reports_missing_files() {
+    let _dir = TestDir::new("normalise_missing");
+
+    assert!(
+        PostRecordAction::Normalise.run("Missing", &None, ExportFormat::Float32)
+            == Err(Error::NormaliseError)
+    );
+}
+
+#[test]
+fn export_action_uses_the_export_folder_and_format() {
+    let dir = TestDir::new("export_action");
+    dir.write_wav("Take", RECORDING_SAMPLE_RATE, 0.1);
+    let folder = format!("{}/exports", dir.path);
+    fs::create_dir_all(&folder).unwrap();
+
+    let action = PostRecordAction::Export;
+    assert!(action.run("Take", &Some(folder.clone()), ExportFormat::Int16) == Ok(None));
+    let Ok(names) = fs::read_dir(&folder) else {
+        panic!("Couldn't read the export folder");
+    };
+    let exported: Vec<PathBuf> = names.map(|entry| entry.unwrap().path()).collect();
+    assert_eq!(exported.len(), 1);
+    assert_eq!(
+        WavReader::open(&exported[0])
+            .unwrap()
+            .spec()
+            .bits_per_sample,
+        16
+    );
+
+    // A folder that can't be written to falls back to the data folder and says so
+    let missing = Some(format!("{}/missing", dir.path));
+    assert!(action.run("Take", &missing, ExportFormat::Int16) == Ok(Some(Warning::ExportFallback)));
+    assert!(action.run("Gone", &None, ExportFormat::Int16).is_err());
+}
+
+#[test]
+fn loop_regions_fit_inside_the_recording() {
+    let length = Duration::from_secs(10);
+    let mut recording = Recording::new(&String::from("Loop"));
+    assert!(recording.loop_region(length).is_none());
+
+    recording.loop_start = Some(2.0);
+    assert_eq!(
+        recording.loop_region(length),
+        Some((Duration::from_secs(2), length))
+    );
+
+    recording.loop_end = Some(30.0);
+    assert_eq!(
+        recording.loop_region(length),
+        Some((Duration::from_secs(2), length))
+    );
+
+    recording.loop_end = Some(1.0);
+    assert!(recording.loop_region(length).is_none());
+}
+
+#[test]
+fn loop_points_survive_a_rename() {
+    let storage = MockStorage::new(&["Old.wav", "Old.bin"]);
+    let mut recordings = vec![Recording::new(&String::from("Old"))];
+    recordings[0].loop_start = Some(1.5);
+    recordings[0].loop_end = Some(4.0);
+
+    assert!(Recording::rename_one(&storage, &mut recordings, 0, &String::from("New")).is_none());
+    assert_eq!(recordings[0].loop_start, Some(1.5));
+    assert_eq!(recordings[0].loop_end, Some(4.0));
+}
+
+#[test]
+fn blank_band_labels_fall_back_to_the_originals() {
+    let mut settings = Settings::new();
+    settings.band_labels = vec![String::from("60 Hz"), String::from("  ")];
+
+    assert_eq!(settings.band_label(0), "60 Hz");
+    assert_eq!(settings.band_label(1), "Bass");
+    assert_eq!(settings.band_label(4), "Treble");
+}
+
+#[test]
+fn fallback_recordings_take_the_free_numbers() {
+    let storage = MockStorage::new(&[
+        "Recording 1.wav",
+        "Default taken... 1.wav",
+        "Default taken... 1.bin",
+        "Recording 3.wav",
+        "Default taken... 2.wav",
+        "Default taken... 2.bin",
+    ]);
+    let mut recordings = vec![
+        Recording::new(&String::from("Recording 1")),
+        Recording::new(&String::from("Default taken... 1")),
+        Recording::new(&String::from("Recording 3")),
+        Recording::new(&String::from("Default taken... 2")),
+    ];
+
+    let (renamed, error) = Recording::renumber_fallbacks(&storage, &mut recordings);
+    assert_eq!(renamed, 2);
+    assert!(error.is_none());
+    assert_eq!(recordings[1].name, "Recording 2");
+    assert_eq!(recordings[3].name, "Recording 4");
+    assert!(storage.has("Recording 4.wav"));
+}
+
+#[test]
+fn levels_hold_the_loudest_sample_per_channel() {
+    let mut levels = Levels::new();
+    levels.measure(&[Frame::new(0.5, -0.25), Frame::new(-0.5, 0.25)]);
+    assert_eq!(levels.peak, [0.5, 0.25]);
+    assert_eq!(levels.rms, [0.5, 0.25]);
+    assert!(!levels.clipped());
+
+    levels.peak = [0.0, 0.0];
+    levels.measure(&[Frame::new(0.1, 1.2)]);
+    assert_eq!(levels.held, [0.5, 1.2]);
+    assert!(levels.clipped());
+}
+
+#[test]
+fn meter_fill_uses_a_decibel_scale() {
+    assert_eq!(meter_fill(0.0), 0.0);
+    assert_eq!(meter_fill(1.0), 1.0);
+    assert_eq!(meter_fill(2.0), 1.0);
+    assert!((meter_fill(0.001) - 0.0).abs() < 0.001); // -60 dB
+    assert!((meter_fill(0.0316) - 0.5).abs() < 0.01); // -30 dB
+}
+
+#[test]
+fn settings_only_count_as_saved_after_writing() {
+    let _dir = TestDir::new("unsaved");
+    let tracker = Tracker::new(Settings::new());
+    assert!(!Tracker::unsaved(&tracker.settings, &tracker.saved));
+
+    // Changing or saving the settings flags them to be compared again
+    SETTINGS_CHANGED.store(false, AtomicOrdering::Relaxed);
+    Tracker::edit(&tracker.settings).metronome = true;
+    assert!(SETTINGS_CHANGED.swap(false, AtomicOrdering::Relaxed));
+    assert!(Tracker::unsaved(&tracker.settings, &tracker.saved));
+
+    let settings = tracker.settings.read().unwrap().clone();
+    assert!(Tracker::save_settings(&settings, tracker.saved.clone()).is_none());
+    assert!(SETTINGS_CHANGED.load(AtomicOrdering::Relaxed));
+    assert!(!Tracker::unsaved(&tracker.settings, &tracker.saved));
+}
+
+#[test]
+fn queue_skips_missing_recordings_and_wraps() {
+    let mut settings = Settings::new();
+    for name in ["A", "B", "C"] {
+        settings
+            .recordings
+            .push(Recording::new(&String::from(name)));
+    }
+    settings.queue = vec![String::from("C"), String::from("Gone"), String::from("A")];
+
+    assert_eq!(settings.next_in_queue(2), (Some(0), true)); // C -> Gone -> A
+    assert_eq!(settings.next_in_queue(0), (Some(2), false)); // A wraps to C
+    assert_eq!(settings.next_in_queue(1), (Some(2), false)); // B isn't queued so the queue starts over
+
+    settings.queue = vec![String::from("Gone")];
+    assert_eq!(settings.next_in_queue(0), (None, true));
+}
+
+#[test]
+fn queue_follows_swapped_names() {
+    let mut settings = Settings::new();
+    for name in ["A", "B"] {
+        settings
+            .recordings
+            .push(Recording::new(&String::from(name)));
+    }
+    settings.queue = vec![String::from("A"), String::from("B"), String::from("A")];
+
+    let before = settings.recording_names();
+    settings.recordings[0].name = String::from("B");
+    settings.recordings[1].name = String::from("A");
+    settings.follow_renames(&before);
+
+    assert_eq!(settings.queue, vec!["B", "A", "B"]);
+}
+
+#[test]
+fn pan_glide_finishes_before_the_next_frame() {
+    let glide = Duration::from_millis(120);
+    assert_eq!(pan_glide(glide, None), glide);
+    assert_eq!(pan_glide(glide, Some(100)), glide);
+    assert_eq!(pan_glide(glide, Some(2)), PLAYER_TICK * 2);
+    assert_eq!(pan_glide(glide, Some(0)), Duration::ZERO);
+}
+
+#[test]
+fn noise_reduction_takes_out_profiled_noise() {
+    // Repeating noise that's the same in the profile and the recording
+    let noise: Vec<f32> = (0..NOISE_WINDOW * 8)
+        .map(|index| 0.1 * ((index * 7919 % 101) as f32 / 50.0 - 1.0))
+        .collect();
+    let Ok(profile) = NoiseProfile::from_channels("Room", &[noise.clone()]) else {
+        panic!("Profile should be taken from a long enough recording");
+    };
+    assert_eq!(profile.magnitudes[0].len(), NOISE_WINDOW / 2 + 1);
+
+    let cleaned = profile.reduce(&noise, 0);
+    assert_eq!(cleaned.len(), noise.len());
+    let energy = |samples: &[f32]| samples.iter().map(|sample| sample * sample).sum::<f32>();
+    assert!(energy(&cleaned) < energy(&noise) * 0.1);
+
+    assert!(matches!(
+        NoiseProfile::from_channels("Short", &[vec![0.0; NOISE_WINDOW - 1]]),
+        Err(Error::NoiseProfileError)
+    ));
+}
+
+#[test]
+fn noise_profiles_use_the_loop_region_and_copies_leave_unsynced_files_alone() {
+    let dir = TestDir::new("denoise");
+    dir.write_wav("Room", RECORDING_SAMPLE_RATE, 1.0);
+    dir.write_wav("Room denoised", RECORDING_SAMPLE_RATE, 0.5); // On disk but not in the list yet
+
+    let mut recording = Recording::new(&String::from("Room"));
+    let Ok(whole) = NoiseProfile::capture(&recording) else {
+        panic!("Couldn't profile the whole recording");
+    };
+    assert_eq!(whole.source, "Room");
+    recording.loop_end = Some(0.5);
+    let Ok(profile) = NoiseProfile::capture(&recording) else {
+        panic!("Couldn't profile the loop region");
+    };
+    assert_eq!(profile.source, "Room's loop region");
+
+    let Ok(copy) = profile.denoise("Room", &vec![recording]) else {
+        panic!("Couldn't write the cleaned copy");
+    };
+    assert_eq!(copy, "Room denoised 2");
+    let Ok((_, channels)) = read_channels("Room denoised") else {
+        panic!("The unsynced file should still be there");
+    };
+    assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize / 2);
+}
+
+#[test]
+fn input_channels_fall_back_when_missing() {
+    let data = vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0]];
+    assert_eq!(map_channels(&data, [2, 3]), (&[3.0][..], &[4.0][..], false));
+    assert_eq!(map_channels(&data, [3, 3]), (&[4.0][..], &[4.0][..], false));
+    assert_eq!(map_channels(&data, [0, 9]), (&[1.0][..], &[2.0][..], true));
+    assert_eq!(map_channels(&data, [9, 0]), (&[1.0][..], &[1.0][..], true));
+
+    // Mono input is copied to both sides without a warning
+    let mono = vec![vec![5.0]];
+    assert_eq!(map_channels(&mono, [0, 1]), (&[5.0][..], &[5.0][..], false));
+    assert_eq!(map_channels(&mono, [0, 2]), (&[5.0][..], &[5.0][..], true));
+    assert_eq!(map_channels(&[], [0, 1]), (&[][..], &[][..], false));
+}
+
+#[test]
+fn timestamp_names_avoid_collisions() {
+    assert_eq!(timestamp_name(0), "1970-01-01 00-00-00");
+    assert_eq!(timestamp_name(1709251199), "2024-02-29 23-59-59");
+
+    let now = UNIX_EPOCH + Duration::from_secs(1717252205);
+    let mut taken = vec![];
+    for expected in [
+        "2024-06-01 14-30-05",
+        "2024-06-01 14-30-05 2",
+        "2024-06-01 14-30-05 3",
+    ] {
+        let name = NamingScheme::Timestamp.name("", &taken, now);
+        assert_eq!(name.as_deref(), Some(expected));
+        taken.push(name.unwrap());
+    }
+
+    assert_eq!(NamingScheme::Sequential.name("", &taken, now), None);
+    assert_eq!(
+        NamingScheme::Prefix.name("Drums/", &taken, now).as_deref(),
+        Some("Drums 1")
+    );
+    assert_eq!(
+        NamingScheme::Prefix.name(" ", &taken, now).as_deref(),
+        Some("Take 1")
+    );
+}
+
+#[test]
+fn default_parameter_glide_matches_kira() {
+    assert_eq!(
+        parameter_tween(DEFAULT_PARAMETER_GLIDE_MS).duration,
+        Tween::default().duration
+    );
+    assert_eq!(parameter_tween(250).duration, Duration::from_millis(250));
+}
+
+#[test]
+fn colour_labels_survive_dial_edits_and_renames() {
+    let dir = TestDir::new("colour_labels");
+    dir.write_wav("Old", RECORDING_SAMPLE_RATE, 0.1);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    settings.recordings[0].colour = ColourLabel::Blue;
+    settings.apply(
+        &Disk,
+        &Changes {
+            dials: [1, 2, 3, 4, 5, 6],
+            dials_edited: true,
+            current_recording: 0,
+            ..Changes::none()
+        },
+    );
+    assert!(settings.recordings[0].colour == ColourLabel::Blue);
+
+    assert!(
+        Recording::rename_one(&Disk, &mut settings.recordings, 0, &String::from("New")).is_none()
+    );
+    assert!(settings.recordings[0].colour == ColourLabel::Blue);
+    assert!(ColourLabel::Purple.next() == ColourLabel::None);
+}
+
+#[test]
+fn search_ignores_case_and_keeps_indexes() {
+    let recordings: Vec<Recording> = ["Guitar Take", "Vocals", "guitar solo"]
+        .iter()
+        .map(|name| Recording::new(&String::from(*name)))
+        .collect();
+
+    let hidden: Vec<bool> = Recording::send_hidden(&recordings, " GUITAR ")
+        .iter()
+        .collect();
+    assert_eq!(hidden, vec![false, true, false]);
+
+    let hidden: Vec<bool> = Recording::send_hidden(&recordings, "").iter().collect();
+    assert_eq!(hidden, vec![false, false, false]);
+}
+
+#[test]
+fn leftover_captures_can_be_recovered_or_discarded() {
+    let dir = TestDir::new("capture_recovery");
+    let mut snapshot = SnapShot::new();
+    snapshot.frames.push(([1, 2, 3, 4, 5, 6], 10));
+    assert!(snapshot
+        .save_capture(&format!("{}/Take", dir.path))
+        .is_none());
+    assert!(snapshot
+        .save_capture(&format!("{}/Other", dir.path))
+        .is_none());
+
+    let Ok(names) = SnapShot::leftover_captures(&Disk) else {
+        panic!("Captures should be found");
+    };
+    assert_eq!(names, vec!["Other", "Take"]);
+
+    assert!(SnapShot::recover_capture("Take", true).is_none());
+    assert!(!dir.has("Take", "capture"));
+    match load("Take", LoadType::Snapshot) {
+        Ok(DataType::SnapShot(value)) => assert!(value == snapshot),
+        _ => panic!("Recovered capture should be the snapshot"),
+    }
+
+    assert!(SnapShot::recover_capture("Other", false).is_none());
+    assert!(!dir.has("Other", "capture"));
+    assert!(!dir.has("Other", "bin"));
+}
+
+#[test]
+fn consistency_check_adds_missing_entries_and_snapshots() {
+    let dir = TestDir::new("consistency_added");
+    dir.write_wav("Fresh", RECORDING_SAMPLE_RATE, 0.1); // File without a recording or snapshot
+
+    let mut settings = Settings::new();
+    let Ok(report) = settings.check_consistency(&Disk) else {
+        panic!("Check should be able to read the folder");
+    };
+
+    assert_eq!(report.added, vec!["Fresh"]);
+    assert_eq!(report.snapshots_created, vec!["Fresh"]);
+    assert!(report.repaired());
+    assert_eq!(names(&settings), vec!["Fresh"]);
+    assert!(dir.has("Fresh", "bin"));
+}
+
+#[test]
+fn consistency_check_removes_missing_and_duplicate_entries() {
+    let dir = TestDir::new("consistency_removed");
+    dir.write_wav("Known", RECORDING_SAMPLE_RATE, 0.1);
+    assert!(SnapShot::create("Known").is_none());
+
+    let mut settings = Settings::new();
+    let mut known = Recording::new(&String::from("Known"));
+    known.bass = 3;
+    settings.recordings = vec![
+        known,
+        Recording::new(&String::from("Gone")), // Recording without a file
+        Recording::new(&String::from("Known")),
+    ];
+    let Ok(report) = settings.check_consistency(&Disk) else {
+        panic!("Check should be able to read the folder");
+    };
+
+    assert_eq!(report.removed, vec!["Gone"]);
+    assert_eq!(report.duplicates, vec!["Known"]);
+    assert!(report.snapshots_created.is_empty());
+    assert_eq!(names(&settings), vec!["Known"]);
+    assert_eq!(settings.recordings[0].bass, 3); // The first entry is the one kept
+}
+
+#[test]
+fn consistency_check_leaves_orphaned_snapshots_alone() {
+    let dir = TestDir::new("consistency_orphans");
+    dir.write_wav("Known", RECORDING_SAMPLE_RATE, 0.1);
+    assert!(SnapShot::create("Known").is_none());
+    assert!(SnapShot::create("Ghost").is_none()); // Snapshot without a file
+
+    let mut settings = Settings::new();
+    settings
+        .recordings
+        .push(Recording::new(&String::from("Known")));
+    let Ok(report) = settings.check_consistency(&Disk) else {
+        panic!("Check should be able to read the folder");
+    };
+
+    assert_eq!(report.orphaned_snapshots, vec!["Ghost"]);
+    assert!(!report.repaired());
+    assert!(dir.has("Ghost", "bin"));
+}
+
+#[test]
+fn sync_keeps_snapshots_when_an_orphan_sorts_first() {
+    let dir = TestDir::new("sync_orphan_first");
+    dir.write_wav("B", RECORDING_SAMPLE_RATE, 0.1);
+    let mut snapshot = SnapShot::new();
+    snapshot.frames.push(([1, 1, 1, 1, 1, 1], 5));
+    assert!(snapshot.clone().save("B").is_none());
+    assert!(SnapShot::create("A").is_none()); // Orphan that used to make B's snapshot get written over
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+
+    match load("B", LoadType::Snapshot) {
+        Ok(DataType::SnapShot(value)) => assert!(value == snapshot),
+        _ => panic!("Snapshot should still load"),
+    }
+}
+
+#[test]
+fn output_choices_keep_the_default_first_and_a_missing_device() {
+    let available = vec![String::from("Headphones"), String::from("HDMI")];
+
+    assert_eq!(
+        output_choices("", &available),
+        vec!["", "Headphones", "HDMI"]
+    );
+    assert_eq!(
+        output_choices("HDMI", &available),
+        vec!["", "Headphones", "HDMI"]
+    );
+    assert_eq!(
+        output_choices("USB interface", &available),
+        vec!["", "Headphones", "HDMI", "USB interface"]
+    );
+
+    assert_eq!(output_label("", &available), "System default");
+    assert_eq!(output_label("HDMI", &available), "HDMI");
+    assert_eq!(
+        output_label("USB interface", &available),
+        "USB interface (missing)"
+    );
+}
+
+#[test]
+fn blank_output_uses_the_default_without_warning() {
+    assert!(output_settings("").1.is_none());
+}
+
+#[test]
+fn device_watch_pauses_on_unplug_and_resumes_when_back() {
+    let mut watch = DeviceWatch::new();
+    let playing = Some(Interrupted::Audio(2, Duration::from_secs(4)));
+    let headphones = || Some(String::from("Headphones"));
+    let speakers = || Some(String::from("Speakers"));
+
+    // Nothing is looked up while idle
+    let change = watch.check(None, || panic!("Shouldn't look up devices while idle"));
+    assert!(change == DeviceChange::Unchanged);
+
+    assert!(watch.check(playing, headphones) == DeviceChange::Unchanged);
+    assert!(watch.check(playing, speakers) == DeviceChange::Lost(playing.unwrap()));
+
+    // Stays paused while a different device is in use
+    assert!(watch.check(None, speakers) == DeviceChange::Unchanged);
+    assert!(watch.check(None, || None) == DeviceChange::Unchanged);
+    assert!(watch.check(None, headphones) == DeviceChange::Restored(playing.unwrap()));
+    assert!(watch.lost.is_none());
+}
+
+#[test]
+fn device_watch_forgets_a_pause_once_something_is_started() {
+    let mut watch = DeviceWatch::new();
+    let recording = Some(Interrupted::Recording);
+
+    watch.check(recording, || Some(String::from("Interface")));
+    watch.check(recording, || Some(String::from("Speakers")));
+    assert!(watch.lost.is_some());
+
+    // Recording was started again by hand so the interface coming back doesn't start another
+    assert!(watch.check(recording, || Some(String::from("Speakers"))) == DeviceChange::Unchanged);
+    assert!(watch.check(None, || Some(String::from("Interface"))) == DeviceChange::Unchanged);
+}
+
+#[test]
+fn a_new_default_output_isnt_counted_as_losing_the_device() {
+    let outputs = |connected: &[&str], default: &str| Outputs {
+        connected: connected.iter().map(|name| name.to_string()).collect(),
+        default: Some(default.to_string()),
+    };
+    let speakers = outputs(&["Speakers"], "Speakers");
+    let headphones = outputs(&["Speakers", "Headphones"], "Headphones");
+    let unplugged = outputs(&["Headphones"], "Headphones");
+
+    // Playback through the system default has no device of its own to lose
+    assert!(speakers.chosen("").is_none());
+    assert!(headphones.chosen("Speakers") == Some(String::from("Speakers")));
+    assert!(unplugged.chosen("Speakers").is_none());
+
+    // The recorder keeps its device when headphones become the default, and only loses it when it's unplugged
+    let mut watch = DeviceWatch::new();
+    let recording = Some(Interrupted::Recording);
+    for (outputs, expected) in [
+        (&speakers, DeviceChange::Unchanged),
+        (&headphones, DeviceChange::Unchanged),
+        (&unplugged, DeviceChange::Lost(Interrupted::Recording)),
+    ] {
+        let captured = outputs.captured(&watch);
+        assert!(watch.check(recording, || captured) == expected);
+    }
+    let captured = headphones.captured(&watch);
+    assert!(watch.check(None, || captured) == DeviceChange::Restored(Interrupted::Recording));
+}
+
+#[test]
+fn brief_clicks_and_dc_offset_dont_count_as_sound() {
+    let silent = vec![0.0; 100];
+    let mut click = silent.clone();
+    click[10] = 0.8;
+    let offset = vec![0.0002; 100];
+
+    assert_eq!(sound_frames(&click, &silent), 1);
+    assert_eq!(sound_frames(&offset, &offset), 0);
+    assert_eq!(sound_frames(&silent, &vec![-0.5; 100]), 100);
+
+    assert!(sound_frames(&click, &silent) < min_sound_frames(DEFAULT_MIN_SOUND_MS));
+    assert_eq!(min_sound_frames(0), 1); // Any sound keeps the recording
+    assert_eq!(min_sound_frames(1000), RECORDING_SAMPLE_RATE as u64);
+}
+
+#[test]
+fn padding_silence_only_fills_what_wasnt_written() {
+    let dir = TestDir::new("pad_partial");
+    dir.write_wav("Glitch", RECORDING_SAMPLE_RATE, 0.1);
+
+    assert!(pad_silence("Glitch", RECORDING_SAMPLE_RATE as u64).is_none());
+
+    let reader = WavReader::open(format!("{}/Glitch.wav", dir.path)).unwrap();
+    assert_eq!(reader.duration(), RECORDING_SAMPLE_RATE);
+}
+
+#[test]
+fn window_geometry_moves_onto_the_primary_display_when_its_display_is_gone() {
+    let laptop = WindowGeometry {
+        x: 0,
+        y: 0,
+        width: 1440,
+        height: 900,
+    };
+    let monitor = WindowGeometry {
+        x: 1440,
+        y: 0,
+        width: 2560,
+        height: 1440,
+    };
+    let window = WindowGeometry {
+        x: 2000,
+        y: 200,
+        width: 1600,
+        height: 1000,
+    };
+
+    // Still on the second monitor so it's left alone
+    assert!(window.clamped(&[laptop, monitor], Some(laptop)) == window);
+
+    // The monitor was unplugged so it goes to the laptop's screen and shrinks to fit
+    let moved = window.clamped(&[laptop], Some(laptop));
+    assert!(moved.x == 0 && moved.y == 0);
+    assert!(moved.width == 1440 && moved.height == 900);
+
+    // Only a sliver left on the screen counts as off it
+    let sliver = WindowGeometry { x: 1400, ..window };
+    assert!(!sliver.on_display(&[laptop]));
+}
+
+#[test]
+fn checking_renames_reports_each_problem_without_renaming() {
+    let storage = MockStorage::new(&[
+        "A.wav",
+        "A.bin",
+        "B.wav",
+        "B.bin",
+        "C.wav",
+        "C.bin",
+        "D.wav",
+        "D.bin",
+        "Loose.wav",
+    ]);
+    let recordings: Vec<Recording> = ["A", "B", "C", "D"]
+        .iter()
+        .map(|name| Recording::new(&name.to_string()))
+        .collect();
+    let new = vec![
+        String::from("A"),     // Unchanged
+        String::from("Loose"), // Only on disk
+        String::from("E"),
+        String::from("E"), // Already taken by the one above
+    ];
+
+    let problems = Recording::check_renames(&storage, &recordings, &new);
+    assert!(problems[0].is_none());
+    assert!(problems[1] == Some(Error::ExistsError));
+    assert!(problems[2].is_none());
+    assert!(problems[3] == Some(Error::ExistsError));
+    assert!(storage.has("C.wav") && !storage.has("E.wav"));
+
+    // The real rename agrees with the check
+    match Recording::rename(&storage, &recordings, &new) {
+        Ok(_) => panic!("Rename should have failed"),
+        Err((list, error)) => {
+            assert!(error == Error::ExistsError);
+            let names: Vec<String> = list
+                .iter()
+                .map(|recording| recording.name.clone())
+                .collect();
+            assert_eq!(names, vec!["A", "B", "E", "D"]);
+        }
+    }
+}
+
+#[test]
+fn root_is_two_folders_above_a_deep_executable() {
+    assert_eq!(
+        File::root_of(Path::new("/home/user/apps/audio/target/release/audio")),
+        Some("/home/user/apps/audio".to_string())
+    );
+    assert_eq!(
+        File::root_of(Path::new("/opt/audio/bin/audio")),
+        Some("/opt".to_string())
+    );
+}
+
+#[test]
+fn shallow_executables_use_their_own_folder() {
+    assert_eq!(
+        File::root_of(Path::new("/usr/bin/audio")),
+        Some("/usr/bin".to_string())
+    );
+    assert_eq!(
+        File::root_of(Path::new("/bin/audio")),
+        Some("/bin".to_string())
+    );
+    assert_eq!(File::root_of(Path::new("/audio")), Some("/".to_string()));
+    assert_eq!(File::root_of(Path::new("audio")), None); // No folder to work from at all
+}
+
+#[test]
+fn snapshots_move_into_their_folder_and_back() {
+    let dir = TestDir::new("snapshot_folder");
+    dir.write_wav("Recording 1", RECORDING_SAMPLE_RATE, 0.1);
+    assert!(SnapShot::create("Recording 1").is_none());
+    assert!(save(DataType::Settings(Settings::new()), SETTINGS_FILE).is_none());
+
+    // Snapshots saved by older versions are moved in, the settings stay where they are
+    assert!(File::arrange_snapshots(&Disk, true).is_none());
+    assert!(!dir.has("Recording 1", "bin"));
+    assert!(dir.has(&format!("{}/Recording 1", SNAPSHOT_FOLDER), "bin"));
+    assert!(dir.has(SETTINGS_FILE, "bin"));
+    assert!(load("Recording 1", LoadType::Snapshot).is_ok());
+
+    // New snapshots go straight into the folder
+    dir.write_wav("Recording 2", RECORDING_SAMPLE_RATE, 0.1);
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    assert!(dir.has(&format!("{}/Recording 2", SNAPSHOT_FOLDER), "bin"));
+
+    // Turning the option off puts everything back and removes the folder
+    assert!(File::arrange_snapshots(&Disk, false).is_none());
+    assert!(dir.has("Recording 1", "bin") && dir.has("Recording 2", "bin"));
+    assert!(!Path::new(&format!("{}/{}", dir.path, SNAPSHOT_FOLDER)).exists());
+}
+
+#[test]
+fn load_errors_name_the_file() {
+    let _dir = TestDir::new("load_error_name");
+    let Err(error) = load("Missing", LoadType::Snapshot) else {
+        panic!("A snapshot that isn't there shouldn't load");
+    };
+    assert!(error == Error::LoadError("Missing".to_string()));
+    assert!(error.get_text().contains("Missing"));
+}
+
+#[test]
+fn playback_errors_keep_the_reason() {
+    let error = Error::PlaybackError(String::from("No output device available"));
+    assert!(error.get_text().contains("No output device available"));
+    assert!(error.severity() == Severity::Error);
+}
+
+#[test]
+fn skipped_missing_files_are_named() {
+    let error = Error::MissingFileError(String::from("Gone"));
+    assert!(error.get_text().contains("Gone"));
+    assert!(error.severity() == Severity::Warning);
+    assert!(Settings::new().skip_missing);
+}
+
+#[test]
+fn start_offsets_stay_inside_the_recording_and_survive_a_rename() {
+    let storage = MockStorage::new(&["Intro.wav", "Intro.bin"]);
+    let mut recordings = vec![Recording::new(&String::from("Intro"))];
+    let length = Duration::from_secs(10);
+    assert_eq!(recordings[0].start_position(length), Duration::ZERO);
+
+    recordings[0].start_offset = 2.5;
+    assert_eq!(
+        recordings[0].start_position(length),
+        Duration::from_secs_f32(2.5)
+    );
+    assert!(Recording::rename_one(&storage, &mut recordings, 0, &String::from("Song")).is_none());
+    assert_eq!(recordings[0].start_offset, 2.5);
+
+    // An offset past the end would leave nothing to play
+    recordings[0].start_offset = 12.0;
+    assert_eq!(recordings[0].start_position(length), Duration::ZERO);
+}
+
+#[test]
+fn only_samples_above_the_sound_floor_are_audible() {
+    assert!(!audible(0.0));
+    assert!(!audible(SOUND_FLOOR / 2.0)); // Hiss the microphone picks up in a quiet room
+    assert!(audible(SOUND_FLOOR));
+    assert!(audible(-0.2));
+}
+
+#[test]
+fn leading_silence_is_found_on_either_channel() {
+    let left = vec![0.0, 0.0005, 0.0, 0.0, 0.2];
+    let right = vec![0.0, 0.0, 0.0, 0.01, 0.0];
+    assert_eq!(first_sound(&[left.clone(), right]), Some(3));
+    assert_eq!(first_sound(&[left, vec![0.0; 5]]), Some(4));
+    assert_eq!(first_sound(&[vec![0.0005; 5], vec![0.0; 5]]), None); // Under the floor the whole way through
+
+    let mut recording = Recording::new(&String::from("Intro"));
+    recording.start_offset = 2.5;
+    assert_eq!(recording.start_summary(), "0:02.5");
+}
+
+#[test]
+fn presets_round_trip_through_decibels() {
+    let preset = Preset::from([3, -2, 0, SILENT_DIAL, 7, -4], default_filter_kinds());
+    assert_eq!(preset.gains(), [12.0, -8.0, 0.0, SILENT_GAIN, 28.0]);
+
+    let shared = Preset::from_gains("Shared", preset.gains(), preset.pan, preset.filter_kinds);
+    assert_eq!(shared.parse(), preset.parse());
+    assert_eq!(shared.name, "Shared");
+
+    // Gains between steps round to the nearest one and stay in range, only the muted gain mutes
+    assert_eq!(gain_to_dial(5.0), 1);
+    assert_eq!(gain_to_dial(-6.5), -2);
+    assert_eq!(gain_to_dial(60.0), MAX_DIAL);
+    assert_eq!(gain_to_dial(-40.0), SILENT_DIAL + 1);
+    assert_eq!(gain_to_dial(-90.0), SILENT_DIAL);
+}
+
+#[test]
+fn taken_sequential_names_follow_the_chosen_strategy() {
+    let now = UNIX_EPOCH + Duration::from_secs(1_717_252_205); // 2024-06-01 14:30:05 UTC
+    let free = vec![String::from("Recording 1")];
+    assert_eq!(
+        sequential_name(&free, NameCollision::Refuse, 0, now),
+        Some(String::from("Recording 2"))
+    );
+
+    // 'Recording 4' is the next name and it's already taken
+    let taken = vec![
+        String::from("Recording 4"),
+        String::from("Recording 4 (2)"),
+        format!("{} 1", FALLBACK_NAME),
+    ];
+    assert_eq!(
+        sequential_name(&taken, NameCollision::Fallback, 0, now),
+        Some(format!("{} 2", FALLBACK_NAME))
+    );
+    assert_eq!(
+        sequential_name(&taken, NameCollision::Suffix, 0, now),
+        Some(String::from("Recording 4 (3)"))
+    );
+    assert_eq!(
+        sequential_name(&taken, NameCollision::Timestamp, 0, now),
+        Some(String::from("Recording 4 2024-06-01 14-30-05"))
+    );
+    assert_eq!(sequential_name(&taken, NameCollision::Refuse, 0, now), None);
+}
+
+#[test]
+fn new_recordings_start_with_the_chosen_preset() {
+    let _dir = TestDir::new("default_preset");
+    let mut settings = Settings::new();
+    settings.default_dials = [1, 1, 1, 1, 1, 0];
+    settings
+        .presets
+        .push(Preset::from([2, 0, -1, 0, 3, 1], default_filter_kinds()));
+    settings.default_preset = Some(String::from("New Preset"));
+    assert_eq!(settings.new_recording_dials(), [2, 0, -1, 0, 3, 1]);
+
+    // Renaming the preset keeps it chosen
+    settings.apply(
+        &Disk,
+        &Changes {
+            preset_names: Some(vec![String::from("Warm")]),
+            ..Changes::none()
+        },
+    );
+    assert_eq!(settings.default_preset, Some(String::from("Warm")));
+    assert_eq!(settings.new_recording_dials(), [2, 0, -1, 0, 3, 1]);
+
+    // A deleted preset falls back to flat rather than the default dials
+    settings.presets.clear();
+    assert_eq!(settings.new_recording_dials(), flat_dials());
+    settings.default_preset = None;
+    assert_eq!(settings.new_recording_dials(), [1, 1, 1, 1, 1, 0]);
+}
+
+#[test]
+fn cropping_keeps_the_loop_region_and_its_automation() {
+    let dir = TestDir::new("crop");
+    dir.write_wav("Long", RECORDING_SAMPLE_RATE, 2.0);
+    let mut snapshot = SnapShot::new();
+    snapshot.frames.push(([1, 0, 0, 0, 0, 0], 25)); // Half a second in
+    snapshot.frames.push(([2, 0, 0, 0, 0, 0], 75)); // A second and a half in
+    assert!(snapshot.save("Long").is_none());
+
+    let mut recording = Recording::new(&String::from("Long"));
+    let recordings = vec![recording.clone()];
+    assert!(recording.crop(&recordings, true) == Err(Error::NoSelectionError));
+
+    recording.loop_start = Some(1.0);
+    let Ok(name) = recording.crop(&recordings, true) else {
+        panic!("Couldn't crop the recording");
+    };
+    assert_eq!(name, "Long cropped");
+    assert!(dir.has("Long", "wav")); // The original is kept
+
+    let Ok((_, channels)) = read_channels(&name) else {
+        panic!("Couldn't read the cropped copy");
+    };
+    assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize);
+    let Ok(DataType::SnapShot(cropped)) = load(&name, LoadType::Snapshot) else {
+        panic!("Couldn't load the cropped automation");
+    };
+    assert_eq!(
+        cropped.frames,
+        vec![([1, 0, 0, 0, 0, 0], 0), ([2, 0, 0, 0, 0, 0], 25)]
+    );
+}
+
+#[test]
+fn splitting_writes_both_parts_and_rebases_the_second() {
+    let dir = TestDir::new("split");
+    dir.write_wav("Long", RECORDING_SAMPLE_RATE, 2.0);
+    let mut snapshot = SnapShot::new();
+    snapshot.frames.push(([1, 0, 0, 0, 0, 0], 25)); // Half a second in
+    snapshot.frames.push(([2, 0, 0, 0, 0, 0], 75)); // A second and a half in
+    assert!(snapshot.save("Long").is_none());
+
+    let recording = Recording::new(&String::from("Long"));
+    let recordings = vec![recording.clone()];
+    assert!(recording.split(Duration::ZERO, &recordings) == Err(Error::SplitEdgeError));
+    assert!(recording.split(Duration::from_secs(2), &recordings) == Err(Error::SplitEdgeError));
+
+    let Ok([first, second]) = recording.split(Duration::from_secs(1), &recordings) else {
+        panic!("Couldn't split the recording");
+    };
+    assert_eq!(first, "Long part 1");
+    assert_eq!(second, "Long part 2");
+    assert!(dir.has("Long", "wav")); // The original is kept
+
+    for name in [&first, &second] {
+        let Ok((_, channels)) = read_channels(name) else {
+            panic!("Couldn't read a part");
+        };
+        assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize);
+    }
+    let Ok(DataType::SnapShot(later)) = load(&second, LoadType::Snapshot) else {
+        panic!("Couldn't load the second part's automation");
+    };
+    assert_eq!(
+        later.frames,
+        vec![([1, 0, 0, 0, 0, 0], 0), ([2, 0, 0, 0, 0, 0], 25)]
+    );
+}
+
+#[test]
+fn merging_joins_audio_and_offsets_later_automation() {
+    let dir = TestDir::new("merge");
+    dir.write_wav("First", RECORDING_SAMPLE_RATE, 1.0);
+    dir.write_wav("Second", RECORDING_SAMPLE_RATE, 1.0);
+    dir.write_wav("Other rate", 22050, 1.0);
+    let mut snapshot = SnapShot::new();
+    snapshot.frames.push(([3, 0, 0, 0, 0, 0], 10));
+    assert!(snapshot.save("Second").is_none());
+
+    let names = vec![String::from("First"), String::from("Second")];
+    let recordings: Vec<Recording> = names.iter().map(Recording::new).collect();
+    assert!(merge(&names[..1], &recordings) == Err(Error::MergeCountError));
+    let mismatched = vec![String::from("First"), String::from("Other rate")];
+    assert!(matches!(
+        merge(&mismatched, &recordings),
+        Err(Error::FormatMismatchError(reason)) if reason.contains("Other rate")
+    ));
+
+    let Ok(name) = merge(&names, &recordings) else {
+        panic!("Couldn't merge the recordings");
+    };
+    assert_eq!(name, "First merged");
+    assert!(dir.has("First", "wav") && dir.has("Second", "wav")); // The originals are kept
+
+    let Ok((_, channels)) = read_channels(&name) else {
+        panic!("Couldn't read the merged recording");
+    };
+    assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize * 2);
+    let Ok(DataType::SnapShot(merged)) = load(&name, LoadType::Snapshot) else {
+        panic!("Couldn't load the merged automation");
+    };
+    assert_eq!(
+        merged.frames,
+        vec![
+            ([0, 0, 0, 0, 0, 0], 0),
+            ([0, 0, 0, 0, 0, 0], 50),
+            ([3, 0, 0, 0, 0, 0], 60)
+        ]
+    );
+}
+
+#[test]
+fn found_recordings_are_converted_only_when_asked() {
+    let dir = TestDir::new("convert");
+    dir.write_wav("Found", RECORDING_SAMPLE_RATE, 0.5);
+
+    let mut settings = Settings::new();
+    settings.library_channels = 1;
+    settings.apply(&Disk, &Changes::none());
+    assert_eq!(settings.recordings[0].original_channels, Some(2));
+    assert_eq!(settings.unconverted, vec!["Found"]);
+    assert!(settings.unconverted_summary().contains("mono"));
+
+    settings.apply(&Disk, &Changes::none()); // Isn't asked about twice
+    assert_eq!(settings.unconverted.len(), 1);
+
+    assert!(settings.convert_imports(true).is_empty());
+    assert!(settings.unconverted.is_empty());
+    assert_eq!(wav_channels("Found"), Some(1));
+    let Ok((_, channels)) = read_channels("Found") else {
+        panic!("Couldn't read the converted recording");
+    };
+    assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize / 2);
+    assert_eq!(
+        settings.recordings[0].format_summary(),
+        "Imported in stereo, converted to mono"
+    );
+}
+
+#[test]
+fn exports_go_to_the_chosen_folder_in_the_chosen_format() {
+    let dir = TestDir::new("export");
+    dir.write_wav("Song", RECORDING_SAMPLE_RATE, 0.5);
+    let folder = format!("{}/exports", dir.path);
+    fs::create_dir(&folder).unwrap();
+
+    let Ok((directory, fell_back)) = File::export_directory(&Some(folder.clone())) else {
+        panic!("Couldn't find the export folder");
+    };
+    assert!(directory == folder && !fell_back);
+    assert!(export("Song", &directory, ExportFormat::Int16) == Ok(String::from("Song")));
+    assert!(export("Song", &directory, ExportFormat::Int16) == Ok(String::from("Song 2"))); // Doesn't write over the first
+
+    let mut reader = WavReader::open(format!("{}/Song.wav", folder)).unwrap();
+    let spec = reader.spec();
+    assert_eq!(spec.bits_per_sample, 16);
+    assert!(spec.sample_format == SampleFormat::Int);
+    let Ok((_, channels)) = read_channels("Song") else {
+        panic!("Couldn't read the recording");
+    };
+    let written: Vec<i32> = reader
+        .samples::<i32>()
+        .take(20)
+        .map(|sample| sample.unwrap())
+        .collect();
+    let expected: Vec<i32> = (0..10)
+        .flat_map(|frame| [channels[0][frame], channels[1][frame]])
+        .map(|sample| int_sample(sample, spec))
+        .collect();
+    assert_eq!(written, expected); // Scaled the same way as every other integer write
+
+    let missing = Some(format!("{}/missing", dir.path));
+    assert!(matches!(
+        File::export_directory(&missing),
+        Ok((directory, true)) if directory == dir.path
+    ));
+}
+
+#[test]
+fn resampling_keeps_the_length_and_level() {
+    let tone: Vec<f32> = (0..44100)
+        .map(|frame| (frame as f32 * TAU * 1000.0 / 44100.0).sin() * 0.5)
+        .collect();
+    let resampled = resample(&tone, 44100, RECORDING_SAMPLE_RATE);
+    assert_eq!(resampled.len(), RECORDING_SAMPLE_RATE as usize);
+    let peak = resampled[1000..resampled.len() - 1000]
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    assert!((peak - 0.5).abs() < 0.01);
+
+    // Rates that don't share much use the nearest of the filter points worked out ahead of time
+    let resampled = resample(&tone, 44101, RECORDING_SAMPLE_RATE);
+    let peak = resampled[1000..resampled.len() - 1000]
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    assert!((peak - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn resampled_recordings_are_reused_until_the_file_changes() {
+    let dir = TestDir::new("resample_cache");
+    dir.write_wav("Found", 44100, 0.5);
+    let file = format!("{}/Found.wav", dir.path);
+
+    let mut cache = ResampleCache::new();
+    let Ok(first) = cache.load(&file, RECORDING_SAMPLE_RATE) else {
+        panic!("Couldn't load the recording");
+    };
+    assert_eq!(first.sample_rate, RECORDING_SAMPLE_RATE);
+    assert_eq!(first.num_frames(), RECORDING_SAMPLE_RATE as usize / 2);
+    let Ok(second) = cache.load(&file, RECORDING_SAMPLE_RATE) else {
+        panic!("Couldn't load the recording again");
+    };
+    assert!(Arc::ptr_eq(&first.frames, &second.frames));
+}
+
+#[test]
+fn found_recordings_are_resampled_when_chosen() {
+    let dir = TestDir::new("resample");
+    dir.write_wav("Found", 44100, 0.5);
+
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    assert!(settings.unconverted.is_empty()); // Only the channels are checked by default
+
+    dir.write_wav("Later", 44100, 0.5);
+    settings.resample = ResampleMode::OnImport;
+    settings.apply(&Disk, &Changes::none());
+    assert_eq!(settings.unconverted, vec!["Later"]);
+
+    assert!(settings.convert_imports(true).is_empty());
+    let Some(spec) = wav_spec("Later") else {
+        panic!("Couldn't read the resampled recording");
+    };
+    assert_eq!(spec.sample_rate, RECORDING_SAMPLE_RATE);
+    let later = settings
+        .recordings
+        .iter()
+        .find(|recording| recording.name == "Later")
+        .unwrap();
+    assert_eq!(
+        later.format_summary(),
+        "Imported in stereo at 44100 Hz, converted to stereo at 48000 Hz"
+    );
+}
+
+#[test]
+fn snapshots_leave_out_frames_outside_their_recording() {
+    let dir = TestDir::new("snapshot_ticks");
+    dir.write_wav("Short", RECORDING_SAMPLE_RATE, 1.0); // 50 ticks long
+    let snapshot = SnapShot {
+        frames: vec![
+            ([1, 0, 0, 0, 0, 0], -5),
+            ([2, 0, 0, 0, 0, 0], 10),
+            ([3, 0, 0, 0, 0, 0], 100000),
+        ],
+    };
+    assert!(snapshot.save("Short").is_none());
+    FRAMES_DROPPED.store(false, AtomicOrdering::Relaxed);
+
+    let Ok(DataType::SnapShot(loaded)) = load("Short", LoadType::Snapshot) else {
+        panic!("Couldn't load the snapshot");
+    };
+    assert_eq!(loaded.frames, vec![([2, 0, 0, 0, 0, 0], 10)]);
+    assert!(FRAMES_DROPPED.swap(false, AtomicOrdering::Relaxed));
+
+    let mut invalid = SnapShot {
+        frames: vec![([4, 0, 0, 0, 0, 0], -1)],
+    };
+    assert!(invalid.drop_invalid(None));
+    assert_eq!(invalid.frames, vec![([0, 0, 0, 0, 0, 0], 0)]); // Still has a frame to start from
+}
+
+#[test]
+fn space_left_is_given_in_recording_time() {
+    assert_eq!(recording_bytes(1), 384000);
+    let hour_and_a_half = DISK_SPACE_RESERVE + recording_bytes(5400);
+    assert_eq!(
+        space_summary(Some(hour_and_a_half)),
+        "About 1 h 30 min of recording space left"
+    );
+    assert_eq!(
+        space_summary(Some(DISK_SPACE_RESERVE / 2)),
+        "About 0 h 0 min of recording space left"
+    );
+    assert_eq!(space_summary(None), "");
+
+    // The file system is asked directly, so a folder that's there always has an answer and one that isn't has none
+    let dir = TestDir::new("free_space");
+    assert!(free_space(&dir.path).is_some_and(|free| free > 0));
+    assert!(free_space(&format!("{}/missing", dir.path)).is_none());
+}
+
+#[test]
+fn click_sounds_load_or_say_which_file_failed() {
+    for index in 0..3 {
+        let Ok((click, accent)) = ClickSound::from_ui(index).sounds() else {
+            panic!("Couldn't make a built in click");
+        };
+        assert!(click.num_frames() > 0 && accent.num_frames() > 0);
+    }
+    assert_eq!(ClickSound::from_ui(3).to_ui(), 0); // A custom file isn't picked from the list
+
+    let dir = TestDir::new("click_sounds");
+    dir.write_wav("Click", 44100, 0.05);
+    let path = format!("{}/Click.wav", dir.path);
+    assert!(ClickSound::Custom(path.clone()).sounds().is_ok());
+
+    let missing = format!("{}/Missing.wav", dir.path);
+    assert!(ClickSound::Custom(missing.clone()).sounds() == Err(Error::ClickLoadError(missing)));
+}
+
+#[test]
+fn presets_are_imported_from_another_settings_file() {
+    let dir = TestDir::new("import_presets");
+    let mut friend = Settings::new();
+    friend
+        .presets
+        .push(Preset::from([2, 0, 0, 0, 0, 0], default_filter_kinds()));
+    friend.presets[0].name = String::from("Warm");
+    friend
+        .recordings
+        .push(Recording::new(&String::from("Their song")));
+    let path = format!("{}/friend.bin", dir.path);
+    save_file(&path, SAVE_VERSION, &friend).unwrap();
+
+    let mut settings = Settings::new();
+    settings
+        .presets
+        .push(Preset::from(flat_dials(), default_filter_kinds()));
+    settings.presets[0].name = String::from("Warm");
+    assert!(settings.import_presets(&path) == Ok(1));
+    assert_eq!(settings.presets[1].name, "Warm 2");
+    assert!(settings.recordings.is_empty());
+
+    friend.presets[0].bass = 40;
+    save_file(&path, SAVE_VERSION, &friend).unwrap();
+    assert!(settings.import_presets(&path) == Err(Error::PresetRangeError(String::from("Warm"))));
+    assert_eq!(settings.presets.len(), 2);
+}
+
+#[test]
+fn presets_are_shared_in_decibels() {
+    let dir = TestDir::new("shared_presets");
+    let mut settings = Settings::new();
+    settings.presets.push(Preset::from(
+        [3, -2, 0, SILENT_DIAL, 7, -4],
+        default_filter_kinds(),
+    ));
+    settings.presets[0].name = String::from("Bright, thin");
+    let path = format!("{}/shared", dir.path);
+    assert!(settings.export_presets(&path) == Ok(1));
+
+    let path = format!("{}.{}", path, SHARED_PRESETS_EXTENSION);
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(text.contains("Bright, thin, 12, -8, 0, -60, 28, -4"));
+
+    let mut friend = Settings::new();
+    assert!(friend.import_presets(&path) == Ok(1));
+    assert_eq!(friend.presets[0].name, "Bright, thin");
+    assert_eq!(friend.presets[0].parse(), settings.presets[0].parse());
+
+    // Written by hand, gains between steps round and a band that's too loud stops the whole file
+    fs::write(&path, "# Comment\nWarm, 5, 0, 0, 0, -90, 1\n").unwrap();
+    assert!(friend.import_presets(&path) == Ok(1));
+    assert_eq!(friend.presets[1].parse(), [1, 0, 0, 0, SILENT_DIAL, 1]);
+    fs::write(&path, "Warm, 5, 0, 0, 0, 0, 1\nLoud, 40, 0, 0, 0, 0, 0\n").unwrap();
+    assert!(friend.import_presets(&path) == Err(Error::PresetRangeError(String::from("Loud"))));
+    fs::write(&path, "Warm, loud, 0, 0, 0, 0, 1\n").unwrap();
+    assert!(friend.import_presets(&path) == Err(Error::LoadError(path.clone())));
+    assert_eq!(friend.presets.len(), 2);
+}
+
+#[test]
+fn new_presets_get_their_own_names_and_chosen_start() {
+    let _dir = TestDir::new("new_presets");
+    let mut settings = Settings::new();
+    let new_preset = Changes {
+        dials: [3, 0, 0, 0, 0, 0],
+        new_preset: true,
+        ..Changes::none()
+    };
+    settings.apply(&Disk, &new_preset);
+    settings.new_preset_start = NewPresetStart::Flat;
+    settings.apply(&Disk, &new_preset);
+
+    assert_eq!(settings.presets[0].name, "Preset 1");
+    assert_eq!(settings.presets[0].parse(), [3, 0, 0, 0, 0, 0]);
+    assert_eq!(settings.presets[1].name, "Preset 2");
+    assert_eq!(settings.presets[1].parse(), flat_dials());
+
+    // Numbers carry on past ones that were renamed or taken by hand
+    assert_eq!(
+        Preset::next_name(&[String::from("Preset 3"), String::from("Preset 2")]),
+        "Preset 4"
+    );
+}
+
+#[test]
+fn presets_move_and_rename_by_their_place_in_the_list() {
+    let _dir = TestDir::new("preset_order");
+    let mut settings = Settings::new();
+    for name in ["Warm", "Bright", "Flat"] {
+        let mut preset = Preset::from(flat_dials(), default_filter_kinds());
+        preset.name = String::from(name);
+        settings.presets.push(preset);
+    }
+
+    assert!(settings.move_preset(2, 0));
+    assert!(settings.move_preset(0, 10)); // Stops at the end
+    assert!(!settings.move_preset(5, 0));
+    let names: Vec<&str> = settings
+        .presets
+        .iter()
+        .map(|preset| preset.name.as_str())
+        .collect();
+    assert_eq!(names, ["Warm", "Bright", "Flat"]);
+
+    // A names list that's out of step only renames what changed and never blanks a name
+    settings.apply(
+        &Disk,
+        &Changes {
+            preset_names: Some(vec![String::from("Warm"), String::from("Airy")]),
+            ..Changes::none()
+        },
+    );
+    let names: Vec<&str> = settings
+        .presets
+        .iter()
+        .map(|preset| preset.name.as_str())
+        .collect();
+    assert_eq!(names, ["Warm", "Airy", "Flat"]);
+}
+
+#[test]
+fn current_recording_stays_put_when_another_is_deleted() {
+    assert_eq!(index_after_removal(3, 1, 4), 2); // Moves up with the list
+    assert_eq!(index_after_removal(1, 3, 4), 1);
+    assert_eq!(index_after_removal(2, 2, 4), 2); // The next one takes its place
+    assert_eq!(index_after_removal(4, 4, 4), 3); // The last one falls back to the one before
+    assert_eq!(index_after_removal(0, 0, 0), 0);
+}
+
+#[test]
+fn fallback_names_can_be_grouped_at_either_end() {
+    let dir = TestDir::new("fallback_grouping");
+    for name in [
+        "Recording 1",
+        "Default taken... 1",
+        "Recording 2",
+        "Default taken... 2",
+    ] {
+        dir.write_wav(name, RECORDING_SAMPLE_RATE, 0.1);
+    }
+
+    let mut settings = Settings::new();
+    settings.fallback_grouping = FallbackGrouping::Last;
+    settings.apply(&Disk, &Changes::none());
+    assert_eq!(
+        settings.recording_names(),
+        [
+            "Recording 1",
+            "Recording 2",
+            "Default taken... 1",
+            "Default taken... 2"
+        ]
+    );
+
+    settings.fallback_grouping = FallbackGrouping::First;
+    settings.apply(&Disk, &Changes::none());
+    assert_eq!(
+        settings.recording_names(),
+        [
+            "Default taken... 1",
+            "Default taken... 2",
+            "Recording 1",
+            "Recording 2"
+        ]
+    );
+}
+
+#[test]
+fn resetting_automation_empties_every_snapshot_but_keeps_settings() {
+    let dir = TestDir::new("reset_automation");
+    dir.write_wav("First", RECORDING_SAMPLE_RATE, 0.5);
+    dir.write_wav("Second", RECORDING_SAMPLE_RATE, 0.5);
+    let mut settings = Settings::new();
+    settings.apply(&Disk, &Changes::none());
+    assert!(save(DataType::Settings(settings.clone()), SETTINGS_FILE).is_none());
+    let captured = SnapShot {
+        frames: vec![([0, 0, 0, 0, 0, 0], 0), ([3, 0, 0, 0, 0, 0], 10)],
+    };
+    assert!(captured.save("First").is_none());
+
+    let (reset, failed) = settings.reset_automation();
+    assert!(reset == 2 && failed.is_none());
+    let Ok(DataType::SnapShot(snapshot)) = load("First", LoadType::Snapshot) else {
+        panic!("Couldn't load the snapshot");
+    };
+    assert!(snapshot.is_empty());
+    assert!(matches!(
+        load(SETTINGS_FILE, LoadType::Settings),
+        Ok(DataType::Settings(_))
+    ));
+}
+
+#[test]
+fn overridden_dials_replace_their_captured_values() {
+    let captured = [1, 2, 3, 4, 5, 6];
+    assert_eq!(with_overrides(captured, [None; 6]), captured);
+    assert_eq!(
+        with_overrides(captured, [None, Some(-3), None, None, None, Some(0)]),
+        [1, -3, 3, 4, 5, 0]
+    );
+}
+
+#[test]
+fn monitor_plays_input_at_its_own_rate() {
+    let buffer = Arc::new(Mutex::new(VecDeque::new()));
+    let mut monitor = MonitorSound::new(buffer.clone(), 48000);
+    let mut input: VecDeque<Frame> = (0..4).map(|value| Frame::from_mono(value as f32)).collect();
+
+    // An output at twice the capture rate plays a blend between every pair of input frames
+    let step = 48000.0 / 96000.0;
+    let played: Vec<f32> = (0..6)
+        .map(|_| monitor.pull(&mut input, step).left)
+        .collect();
+    assert_eq!(played, vec![0.0, 0.5, 1.0, 1.5, 2.0, 2.5]);
+
+    // Nothing is played until there are two frames to blend
+    input.clear();
+    input.push_back(Frame::from_mono(1.0));
+    assert_eq!(monitor.pull(&mut input, step), Frame::ZERO);
+}
+
+#[test]
+fn integer_recordings_are_read_and_written_in_their_own_format() {
+    let dir = TestDir::new("integer_samples");
+    // A 16 bit file like most other programs write, rather than the float ones the recorder makes
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate: RECORDING_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(format!("{}/Imported.wav", dir.path), spec).unwrap();
+    for _ in 0..RECORDING_SAMPLE_RATE * 2 {
+        writer.write_sample(16384_i16).unwrap();
+        writer.write_sample(-32768_i16).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let Ok((read, channels)) = read_channels("Imported") else {
+        panic!("Couldn't read the 16 bit recording");
+    };
+    assert!(read == spec);
+    assert_eq!(channels[0][0], 0.5);
+    assert_eq!(channels[1][0], -1.0);
+
+    let mut recording = Recording::new(&String::from("Imported"));
+    recording.loop_start = Some(1.0);
+    let Ok(name) = recording.crop(&vec![recording.clone()], true) else {
+        panic!("Couldn't crop the 16 bit recording");
+    };
+    let reader = WavReader::open(format!("{}/{}.wav", dir.path, name)).unwrap();
+    assert!(reader.spec() == spec);
+    let samples: Vec<i16> = reader
+        .into_samples()
+        .map(|sample| sample.unwrap())
+        .collect();
+    assert_eq!(samples.len(), RECORDING_SAMPLE_RATE as usize * 2);
+    assert_eq!(&samples[..2], &[16384, -32768]);
+}
+
+#[test]
+fn new_recordings_from_edits_skip_files_that_havent_been_synced() {
+    let dir = TestDir::new("edit_names");
+    dir.write_wav("Long", RECORDING_SAMPLE_RATE, 2.0);
+    dir.write_wav("Long cropped", RECORDING_SAMPLE_RATE, 0.5); // Turned up since the last sync
+    dir.write_wav("Long part 1", RECORDING_SAMPLE_RATE, 0.5);
+
+    let mut recording = Recording::new(&String::from("Long"));
+    let recordings = vec![recording.clone()];
+    recording.loop_start = Some(1.0);
+    let Ok(name) = recording.crop(&recordings, true) else {
+        panic!("Couldn't crop the recording");
+    };
+    assert_eq!(name, "Long cropped 2");
+    let Ok(parts) = recording.split(Duration::from_secs(1), &recordings) else {
+        panic!("Couldn't split the recording");
+    };
+    assert_eq!(parts, ["Long part 1 2", "Long part 2"]);
+
+    // The files that were already there are left as they were
+    for name in ["Long cropped", "Long part 1"] {
+        let Ok((_, channels)) = read_channels(name) else {
+            panic!("Couldn't read a file that was already there");
+        };
+        assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize / 2);
+    }
+}
+
+#[test]
+fn cropping_in_place_keeps_automation_that_cant_be_read() {
+    let dir = TestDir::new("crop_unreadable");
+    dir.write_wav("Long", RECORDING_SAMPLE_RATE, 2.0);
+    fs::write(format!("{}/Long.bin", dir.path), b"not a snapshot").unwrap();
+
+    let mut recording = Recording::new(&String::from("Long"));
+    recording.loop_start = Some(1.0);
+    assert!(recording.crop(&vec![recording.clone()], false).is_err());
+
+    // Neither the audio nor the automation were touched
+    let Ok((_, channels)) = read_channels("Long") else {
+        panic!("Couldn't read the recording");
+    };
+    assert_eq!(channels[0].len(), RECORDING_SAMPLE_RATE as usize * 2);
+    assert_eq!(
+        fs::read(format!("{}/Long.bin", dir.path)).unwrap(),
+        b"not a snapshot"
+    );
+}
+
+#[test]
+fn audio_lasts_as_long_at_any_sample_rate() {
+    let dir = TestDir::new("audio_length");
+    for rate in [44100, 48000, 96000] {
+        let name = format!("At {}", rate);
+        dir.write_wav(&name, rate, 1.5);
+        let Ok(data) = StaticSoundData::from_file(format!("{}/{}.wav", dir.path, name)) else {
+            panic!("Couldn't load the {} Hz recording", rate);
+        };
+        assert_eq!(audio_length(&data), Some(Duration::from_millis(1500)));
+    }
+}
+
+#[test]
+fn inverting_one_channel_cancels_it_out_in_mono() {
+    for sample in [0.25, -0.8, 1.0] {
+        let frame = Frame::from_mono(sample);
+        for flipped in [(true, false), (false, true)] {
+            let inverted = invert(frame, flipped);
+            assert_eq!(inverted.left + inverted.right, 0.0);
+        }
+        // Flipping both keeps them in phase with each other
+        let both = invert(frame, (true, true));
+        assert_eq!(both, Frame::from_mono(-sample));
+        assert_eq!(invert(frame, (false, false)), frame);
+    }
+}
+
+// The saved structs as they were laid out at version 0, before any fields were versioned
+// Kept with the same names so the schema written with them matches what the app wrote back then
+mod v0 {
+    use savefile_derive::Savefile;
+
+    #[derive(Savefile)]
+    pub struct Preset {
+        pub name: String,
+        pub sub_bass: i32,
+        pub bass: i32,
+        pub low_mids: i32,
+        pub high_mids: i32,
+        pub treble: i32,
+        pub pan: i32,
+    }
+
+    #[derive(Savefile)]
+    pub struct Recording {
+        pub name: String,
+        pub sub_bass: i32,
+        pub bass: i32,
+        pub low_mids: i32,
+        pub high_mids: i32,
+        pub treble: i32,
+        pub pan: i32,
+    }
+
+    #[derive(Savefile)]
+    pub struct Settings {
+        pub presets: Vec<Preset>,
+        pub recordings: Vec<Recording>,
+    }
+
+    #[derive(Savefile)]
+    pub struct SnapShot {
+        pub frames: Vec<([i32; 6], i32)>,
+    }
+}
+
+#[test]
+fn version_zero_files_load_with_defaults_for_newer_fields() {
+    let dir = TestDir::new("version_zero");
+    let old = v0::Settings {
+        presets: vec![v0::Preset {
+            name: String::from("Warm"),
+            sub_bass: 3,
+            bass: 2,
+            low_mids: 1,
+            high_mids: -1,
+            treble: -2,
+            pan: 4,
+        }],
+        recordings: vec![v0::Recording {
+            name: String::from("Recording 1"),
+            sub_bass: -3,
+            bass: 0,
+            low_mids: 2,
+            high_mids: 0,
+            treble: 1,
+            pan: -4,
+        }],
+    };
+    assert!(save_file(format!("{}/{}.bin", dir.path, SETTINGS_FILE), 0, &old).is_ok());
+
+    let Ok(DataType::Settings(settings)) = load(SETTINGS_FILE, LoadType::Settings) else {
+        panic!("Version 0 settings didn't load");
+    };
+    // Everything that was saved is kept and every field added since starts at its default
+    let mut expected = Settings::new();
+    expected.presets = vec![Preset {
+        name: String::from("Warm"),
+        sub_bass: 3,
+        bass: 2,
+        low_mids: 1,
+        high_mids: -1,
+        treble: -2,
+        pan: 4,
+        filter_kinds: default_filter_kinds(),
+    }];
+    let mut recording = Recording::new(&String::from("Recording 1"));
+    recording.sub_bass = -3;
+    recording.low_mids = 2;
+    recording.treble = 1;
+    recording.pan = -4;
+    expected.recordings = vec![recording];
+    assert!(settings == expected);
+
+    let old = v0::SnapShot {
+        frames: vec![([0, 0, 0, 0, 0, 0], 0), ([1, 2, 3, 4, 5, 6], 10)],
+    };
+    assert!(save_file(format!("{}/Recording 1.bin", dir.path), 0, &old).is_ok());
+    let Ok(DataType::SnapShot(snapshot)) = load("Recording 1", LoadType::Snapshot) else {
+        panic!("Version 0 snapshot didn't load");
+    };
+    assert!(snapshot.frames == old.frames);
+}
@@ -0,0 +1,1249 @@
+// Threads that record, play back, click the metronome, monitor the input, and watch the output devices
+// Each one is started once and then driven by the messages the UI sends it
+
+// -------- Imports --------
+use crate::*; // Constants, errors, and the other modules through the crate root
+use kira::AudioManager; // Playing recordings, clicks, and the monitored input
+use qruhear::{rucallback, RUBuffers, RUHear}; // Imports for recording audio
+use std::{cmp::min, sync::mpsc::RecvTimeoutError};
+
+// -------- Functions --------
+pub fn start_recorder(
+    tracker: &Tracker,
+    errors: &Arc<RwLock<Option<Error>>>,
+    warnings: &Arc<RwLock<Option<Warning>>>,
+    notifier: &Notifier,
+) -> mpsc::Sender<Message> {
+    // Records the chosen input channels to a new file between the start and stop messages
+    let (record_sender, record_receiver) = mpsc::channel::<Message>(); // Creates recorder message sender and receiver
+
+    // Creates references to the required values in the tracker
+    let record_error_handle = errors.clone();
+    let recording_empty_handle = tracker.empty_recording.clone();
+    let check = tracker.recording_check.clone();
+    let record_warning_handle = warnings.clone();
+    let record_notifier = notifier.clone();
+    let recorder_settings_handle = tracker.settings.clone();
+    let frames_handle = tracker.recorded_frames.clone();
+    let dropouts_handle = tracker.dropouts.clone();
+    let dropped_handle = tracker.dropped_recording.clone();
+    let monitoring_handle = tracker.monitoring.clone();
+    let monitor_buffer_handle = tracker.monitor_buffer.clone();
+    match thread::Builder::new() // Spawns a new thread for recording audio
+        .name(String::from("Recorder"))
+        .spawn(move || {
+            let raise = recorder_settings_handle
+                .read()
+                .unwrap()
+                .raise_thread_priority;
+            if let Some(warning) = raise_priority(raise) {
+                record_notifier.write(record_warning_handle.clone(), Some(warning));
+            }
+
+            let audio_spec = WavSpec {
+                // Decides on the settings of the recording
+                channels: 2,
+                sample_rate: RECORDING_SAMPLE_RATE,
+                bits_per_sample: 32,
+                sample_format: SampleFormat::Float,
+            };
+
+            let path = match File::get_directory() {
+                Ok(value) => value,
+                Err(_) => {
+                    record_notifier.write(record_error_handle.clone(), Some(Error::DirectoryError));
+                    String::new()
+                }
+            };
+
+            let empty = recording_empty_handle.clone(); // New reference for the loop do avoid memory issues
+            loop {
+                match record_receiver.recv() {
+                    // Blocks until message received
+                    Ok(Message::StartRecording) => (),
+                    _ => {
+                        record_notifier
+                            .write(record_error_handle.clone(), Some(Error::MessageError));
+                        continue; // Write an error and start looking for another message
+                    }
+                }
+
+                Tracker::write(empty.clone(), true);
+                Tracker::write(check.clone(), true);
+                Tracker::write(frames_handle.clone(), 0);
+                Tracker::write(dropouts_handle.clone(), 0);
+
+                // Number of frames to record before warning the user
+                let warn_after = recorder_settings_handle
+                    .read()
+                    .unwrap()
+                    .recording_warn_after as u64
+                    * RECORDING_SAMPLE_RATE as u64;
+
+                let (input_channels, trim_silence, needed_sound) = {
+                    let settings = recorder_settings_handle.read().unwrap();
+                    (
+                        settings.input_channels,
+                        settings.trim_leading_silence,
+                        min_sound_frames(settings.min_sound_ms),
+                    )
+                };
+
+                let taken_names = match File::search(&Disk, &path, "wav", false) {
+                    Ok(File::Names(value)) => value,
+                    Err(_) => vec![String::from("Couldn't read files")],
+                };
+
+                let mut fallbacks = 0;
+                for name in &taken_names {
+                    // Checks how many times something has had to been renamed to the fallback name
+                    if (*name).contains(FALLBACK_NAME) {
+                        fallbacks += 1;
+                    }
+                }
+
+                let mut new_name;
+
+                let (scheme_name, collision) = {
+                    let settings = recorder_settings_handle.read().unwrap();
+                    (
+                        settings.naming_scheme.name(
+                            &settings.name_prefix,
+                            &taken_names,
+                            SystemTime::now(),
+                        ),
+                        settings.name_collision,
+                    )
+                };
+
+                if let Some(name) = scheme_name {
+                    // Dated and prefixed names never fall back to 'Default taken...'
+                    new_name = format!("{}.wav", name);
+                } else {
+                    match sequential_name(&taken_names, collision, fallbacks, SystemTime::now()) {
+                        Some(name) => new_name = format!("{}.wav", name),
+                        None => {
+                            // The user chose not to record under another name
+                            record_notifier
+                                .write(record_error_handle.clone(), Some(Error::NameTakenError));
+                            continue;
+                        }
+                    }
+                }
+
+                // Refuses to pile up more fallback names once the limit is reached
+                let fallback_limit =
+                    recorder_settings_handle.read().unwrap().fallback_limit as usize;
+                if new_name.starts_with(FALLBACK_NAME)
+                    && fallback_limit > 0
+                    && fallbacks >= fallback_limit
+                {
+                    record_notifier
+                        .write(record_error_handle.clone(), Some(Error::FallbackLimitError));
+                    continue;
+                }
+
+                // Doesn't start a recording that couldn't be finished, and warns if one might not reach the warning length
+                if let Some(free) = free_space(&path) {
+                    if free < DISK_SPACE_RESERVE {
+                        record_notifier
+                            .write(record_error_handle.clone(), Some(Error::NoSpaceError));
+                        continue;
+                    }
+                    if warn_after > 0
+                        && free - DISK_SPACE_RESERVE
+                            < recording_bytes(warn_after / RECORDING_SAMPLE_RATE as u64)
+                    {
+                        record_notifier
+                            .write(record_warning_handle.clone(), Some(Warning::LowDiskSpace));
+                    }
+                }
+
+                // Checks the folder again right before writing so a file that turned up since can't be overwritten
+                if let Ok(File::Names(current)) = File::search(&Disk, &path, "wav", false) {
+                    if current.contains(&File::truncate(&mut new_name.clone(), ".", 0)) {
+                        record_notifier
+                            .write(record_error_handle.clone(), Some(Error::ExistsError));
+                        continue;
+                    }
+                }
+
+                let mut writer = // Creates a new writer
+                    match WavWriter::create(format!("{}/{}", path, new_name), audio_spec) {
+                        Ok(value) => value,
+                        Err(_) => {
+                            record_notifier
+                                .write(record_error_handle.clone(), Some(Error::WriteError));
+                            continue;
+                        }
+                    };
+
+                let mut initial_silence = true;
+                let mut heard = 0; // Frames above the sound floor so far
+
+                let empty2 = empty.clone(); // New reference to avoid more memory issues
+                let frames = frames_handle.clone();
+                let dropouts = dropouts_handle.clone();
+                let mut pending = [Vec::<f32>::new(), Vec::<f32>::new()]; // Samples each channel has that the other doesn't yet
+                let mut last_callback: Option<(Instant, usize, bool)> = None; // When the last buffer arrived, how long it was, and whether it had sound
+                let monitoring = monitoring_handle.clone();
+                let monitor_buffer = monitor_buffer_handle.clone();
+                let warning = record_warning_handle.clone();
+                let notifier = record_notifier.clone();
+                let mut warned_channels = false;
+                let record_callback = move |data: RUBuffers| {
+                    // Run when callback called
+                    let mut interleaved = vec![];
+
+                    let (left, right, missing) = map_channels(&data, input_channels);
+                    if missing && !warned_channels {
+                        // Only warns once per recording
+                        warned_channels = true;
+                        notifier.write(warning.clone(), Some(Warning::ChannelsMissing));
+                    }
+                    pending[0].extend_from_slice(left);
+                    pending[1].extend_from_slice(right);
+
+                    // Only takes as much as both channels have, the rest waits for the next callback
+                    let length = min(pending[0].len(), pending[1].len());
+                    let left: Vec<f32> = pending[0].drain(..length).collect();
+                    let right: Vec<f32> = pending[1].drain(..length).collect();
+
+                    // Buffers should arrive about as often as the audio they carry lasts, so a late one means the capture fell behind
+                    // Only checked between buffers with sound as some systems stop sending buffers while nothing is playing
+                    let now = Instant::now();
+                    let sound = left
+                        .iter()
+                        .chain(right.iter())
+                        .any(|sample| audible(*sample));
+                    if let Some((time, frames, had_sound)) = last_callback {
+                        let expected =
+                            Duration::from_secs_f64(frames as f64 / RECORDING_SAMPLE_RATE as f64);
+                        if had_sound
+                            && sound
+                            && now.duration_since(time) > expected + DROPOUT_TOLERANCE
+                        {
+                            let total = Tracker::read(dropouts.clone()) + 1;
+                            Tracker::write(dropouts.clone(), total);
+                        }
+                    }
+                    last_callback = Some((now, length, sound));
+
+                    for channel in pending.iter_mut() {
+                        if channel.len() > CHANNEL_DRIFT_LIMIT {
+                            // The other channel has fallen too far behind so its missing samples aren't coming
+                            channel.clear();
+                            let total = Tracker::read(dropouts.clone()) + 1;
+                            Tracker::write(dropouts.clone(), total);
+                        }
+                    }
+
+                    // Keeps track of how long the recording has been going for
+                    let previous = Tracker::read(frames.clone());
+                    let total = previous + length as u64;
+                    Tracker::write(frames.clone(), total);
+                    if warn_after > 0 && previous < warn_after && total >= warn_after {
+                        // Only warns once when the threshold is crossed
+                        notifier.write(warning.clone(), Some(Warning::LongRecording));
+                    }
+
+                    if Tracker::read(monitoring.clone()) {
+                        // Sends the input to the monitor including any leading silence
+                        let mut buffer = monitor_buffer.lock().unwrap();
+                        for sample in 0..length {
+                            buffer.push_back(Frame::new(left[sample], right[sample]));
+                        }
+                        while buffer.len() > MONITOR_BUFFER_LIMIT {
+                            buffer.pop_front(); // Drops the oldest audio so the monitor doesn't fall behind
+                        }
+                    }
+
+                    if heard < needed_sound {
+                        heard += sound_frames(&left, &right);
+                        if heard >= needed_sound {
+                            // Enough sound has been heard that this isn't a glitch
+                            Tracker::write(empty2.clone(), false); // Tells the tracker that this recording should be saved
+                        }
+                    }
+
+                    for sample in 0..length {
+                        if initial_silence && (audible(left[sample]) || audible(right[sample])) {
+                            // If either channel has audio playing, writing starts here even if it turns out to be a glitch
+                            initial_silence = false;
+                        }
+                        if !initial_silence || !trim_silence {
+                            // Pushes the data from each channel to the interleaved list, leading silence is only kept when trimming is off
+                            interleaved.push(left[sample]);
+                            interleaved.push(right[sample]);
+                        }
+                    }
+
+                    if !interleaved.is_empty() {
+                        for sample in &interleaved {
+                            // Writes the data from the interleaved list to file
+                            if writer.write_sample(*sample).is_err() {
+                                // Counts a failed write as lost audio rather than stopping the recorder
+                                let total = Tracker::read(dropouts.clone()) + 1;
+                                Tracker::write(dropouts.clone(), total);
+                                break;
+                            }
+                        }
+                    }
+                };
+
+                let callback = rucallback!(record_callback); // Initiates a callback
+
+                let mut recorder = RUHear::new(callback); // Creates a new recorder
+
+                match recorder.start() {
+                    // Starts a recorder
+                    Ok(_) => {}
+                    Err(_) => {
+                        record_notifier
+                            .write(record_error_handle.clone(), Some(Error::RecordError));
+                        continue;
+                    }
+                };
+
+                let mut out_of_space = false;
+                loop {
+                    match record_receiver.recv_timeout(DISK_CHECK_INTERVAL) {
+                        // Waits for a stop message, checking the disk hasn't filled up in between
+                        Ok(Message::StopRecording) => break,
+                        Err(RecvTimeoutError::Timeout) => {
+                            // The UI stops the recording so it's saved the same way as when stop is pressed
+                            if !out_of_space
+                                && free_space(&path).is_some_and(|free| free < DISK_SPACE_RESERVE)
+                            {
+                                out_of_space = true;
+                                record_notifier.write(
+                                    record_error_handle.clone(),
+                                    Some(Error::SpaceRunningOutError),
+                                );
+                            }
+                        }
+                        _ => {
+                            record_notifier
+                                .write(record_error_handle.clone(), Some(Error::MessageError));
+                            continue;
+                        }
+                    }
+                }
+
+                match recorder.stop() {
+                    // Stops recording
+                    Ok(_) => {}
+                    Err(_) => {
+                        record_notifier
+                            .write(record_error_handle.clone(), Some(Error::RecordError));
+                        continue;
+                    }
+                };
+                drop(recorder); // Drops the writer so the file is finished before anything else opens it
+
+                let delete_empty = recorder_settings_handle
+                    .read()
+                    .unwrap()
+                    .delete_empty_recordings;
+                if Tracker::read(empty.clone()) && !delete_empty {
+                    // Keeps the recording by filling it with the silence that was skipped
+                    if trim_silence {
+                        if let Some(error) = pad_silence(
+                            &File::truncate(&mut new_name, ".", 0),
+                            Tracker::read(frames_handle.clone()),
+                        ) {
+                            record_notifier.write(record_error_handle.clone(), Some(error));
+                        }
+                    }
+                    Tracker::write(empty.clone(), false); // Lets the recording be saved like any other
+                }
+
+                if Tracker::read(empty.clone()) {
+                    // If recording empty
+                    match File::delete(&Disk, File::truncate(&mut new_name, ".", 0)) {
+                        // Delete any recording data that had been saved so far
+                        Some(_) => {
+                            record_notifier.write(
+                                record_error_handle.clone(),
+                                Some(Error::EmptyRecordingError),
+                            );
+                        }
+                        None => (),
+                    }
+                } else {
+                    match SnapShot::create(&File::truncate(&mut new_name, ".", 0)) {
+                        // Creates a new snapshot if there's a file but no snapshots
+                        Some(error) => {
+                            record_notifier.write(record_error_handle.clone(), Some(error));
+                        }
+                        None => (),
+                    }
+
+                    if Tracker::read(dropouts_handle.clone()) > 0 {
+                        // Flags the recording once it's been added to the settings
+                        Tracker::write(
+                            dropped_handle.clone(),
+                            Some(File::truncate(&mut new_name, ".", 0)),
+                        );
+                        record_notifier
+                            .write(record_warning_handle.clone(), Some(Warning::Dropouts));
+                    }
+
+                    let (actions, export_directory, export_format) = {
+                        let settings = recorder_settings_handle.read().unwrap();
+                        (
+                            settings.post_record_actions.clone(),
+                            settings.export_directory.clone(),
+                            settings.export_format,
+                        )
+                    };
+                    let mut failed = false;
+                    for action in &actions {
+                        // Every action runs even if an earlier one failed
+                        match action.run(
+                            &File::truncate(&mut new_name, ".", 0),
+                            &export_directory,
+                            export_format,
+                        ) {
+                            Ok(Some(warning)) => {
+                                record_notifier.write(record_warning_handle.clone(), Some(warning));
+                            }
+                            Ok(None) => (),
+                            Err(error) => {
+                                record_notifier.write(record_error_handle.clone(), Some(error));
+                                failed = true;
+                            }
+                        }
+                    }
+                    if !failed
+                        && actions.len() > 0
+                        && Tracker::read(record_warning_handle.clone()).is_none()
+                    {
+                        record_notifier
+                            .write(record_warning_handle.clone(), Some(Warning::PostRecordDone));
+                    }
+                }
+            }
+        }) {
+        Ok(_) => (),
+        Err(_) => {
+            notifier.write(errors.clone(), Some(Error::RecorderThreadError)); // Error if thread fails to start
+        }
+    };
+
+    record_sender
+}
+
+pub fn start_player(
+    tracker: &Tracker,
+    errors: &Arc<RwLock<Option<Error>>>,
+    warnings: &Arc<RwLock<Option<Warning>>>,
+    notifier: &Notifier,
+) -> mpsc::Sender<Message> {
+    // Plays recordings through the chosen output with the dial values and captured inputs applied
+    let (audio_sender, audio_receiver) = mpsc::channel::<Message>(); // Message sender and reciever for audio playback
+
+    // Creates references for required values in audio thread
+    let player_error_handle = errors.clone();
+    let player_warning_handle = warnings.clone();
+    let player_notifier = notifier.clone();
+    let player_settings_handle = tracker.settings.clone();
+    let player_frame_handle = tracker.snapshot_frame_values.clone();
+    let player_bypass_handle = tracker.bypass.clone();
+    let player_state = tracker.player.clone();
+    let player_position_handle = tracker.position.clone();
+    let player_empty_file_handle = tracker.empty_file.clone();
+    let player_automation_handle = tracker.automation_point.clone();
+    let player_captured_handle = tracker.captured_points.clone();
+    let player_overrides_handle = tracker.dial_overrides.clone();
+    let player_resume_handle = tracker.resume_from.clone();
+    let player_looping_handle = tracker.looping.clone();
+    let player_levels_handle = tracker.levels.clone();
+    match thread::Builder::new() // Creates audio thread
+        .name(String::from("Player"))
+        .spawn(move || {
+            let raise = player_settings_handle.read().unwrap().raise_thread_priority;
+            if let Some(warning) = raise_priority(raise) {
+                player_notifier.write(player_warning_handle.clone(), Some(warning));
+            }
+
+            // Initialises some variables
+            let mut missing_output = String::new(); // Output device that was last found missing
+
+            let mut resample_cache = ResampleCache::new(); // Recordings already brought to the recording rate
+
+            let mut sound_data;
+
+            let mut length;
+
+            let mut file;
+
+            'one: loop {
+                match audio_receiver.recv() {
+                    // Blocks until a load file message is received
+                    Ok(Message::File(name)) => {
+                        file = name;
+                        let resample = player_settings_handle.read().unwrap().resample;
+                        let data = match resample {
+                            ResampleMode::OnPlay => {
+                                resample_cache.load(&file, RECORDING_SAMPLE_RATE)
+                            }
+                            _ => StaticSoundData::from_file(&file),
+                        };
+                        sound_data = match data {
+                            // Loads audio data from file
+                            Ok(value) => {
+                                if value.num_frames() == 0 {
+                                    // Only the header was written, so there's nothing to play
+                                    Tracker::write(
+                                        player_empty_file_handle.clone(),
+                                        Path::new(&file)
+                                            .file_stem()
+                                            .map(|name| name.to_string_lossy().to_string()),
+                                    );
+                                    player_notifier.write(
+                                        player_error_handle.clone(),
+                                        Some(Error::EmptyFileError),
+                                    );
+                                    continue 'one;
+                                }
+                                length = match audio_length(&value) {
+                                    // Gets the length of the audio
+                                    Some(value) => value,
+                                    None => {
+                                        player_notifier.write(
+                                            player_error_handle.clone(),
+                                            Some(Error::ReadError(file.clone())),
+                                        );
+                                        continue 'one;
+                                    }
+                                };
+                                Tracker::write(player_state.clone(), PlayerState::Loaded);
+                                value
+                            }
+                            Err(error) => {
+                                // A file that's there but can't be decoded says why, a missing one says which
+                                player_notifier.write(
+                                    player_error_handle.clone(),
+                                    Some(if Path::new(&file).is_file() {
+                                        Error::PlaybackError(error.to_string())
+                                    } else {
+                                        Error::ReadError(file.clone())
+                                    }),
+                                );
+                                continue 'one;
+                            }
+                        };
+                    }
+                    Ok(Message::StopAndReply(reply)) => {
+                        let _ = reply.send(()); // Nothing is playing so nothing needs saving
+                        continue 'one;
+                    }
+                    _ => {
+                        player_notifier
+                            .write(player_error_handle.clone(), Some(Error::MessageError));
+                        continue 'one;
+                    }
+                }
+
+                'two: loop {
+                    let mut capturing = false;
+                    match audio_receiver.recv() {
+                        // Blocks until message received
+                        Ok(Message::File(_)) => break 'two, // Breaks the second loop to load a file
+                        Ok(Message::PlayAudio(mut playback)) => {
+                            if let Playback::Capture(_) = playback.0 {
+                                capturing = true; // Sets capturing check to true if playback type is Capture
+                            }
+                            // Plays through the chosen output, only warning once while it stays unplugged
+                            let device =
+                                player_settings_handle.read().unwrap().output_device.clone();
+                            let (manager_settings, warning) = output_settings(&device);
+                            match warning {
+                                Some(warning) if device != missing_output => {
+                                    player_notifier
+                                        .write(player_warning_handle.clone(), Some(warning));
+                                    missing_output = device;
+                                }
+                                Some(_) => (),
+                                None => missing_output.clear(),
+                            }
+                            let mut audio_manager = match AudioManager::<DefaultBackend>::new(
+                                // Create a new audio manager
+                                manager_settings,
+                            ) {
+                                Ok(value) => value,
+                                Err(error) => {
+                                    player_notifier.write(
+                                        player_error_handle.clone(),
+                                        Some(Error::PlaybackError(error.to_string())),
+                                    );
+                                    continue 'two;
+                                }
+                            };
+
+                            // Filter handles for real time updating
+                            let mut builder = TrackBuilder::new();
+                            let mut equaliser = Equaliser::add_to(&mut builder);
+                            builder.add_effect(MeterBuilder {
+                                // Added last so the meter shows what's actually heard
+                                levels: player_levels_handle.clone(),
+                            });
+
+                            let mut track = match audio_manager.add_sub_track(builder) {
+                                // Creates a track with the filter handles enabled
+                                Ok(value) => value,
+                                Err(error) => {
+                                    player_notifier.write(
+                                        player_error_handle.clone(),
+                                        Some(Error::PlaybackError(error.to_string())),
+                                    );
+                                    continue 'two;
+                                }
+                            };
+
+                            // Keeps to the recording's loop region while looping, capturing always covers the whole recording
+                            let (region, start_offset) = {
+                                let settings = player_settings_handle.read().unwrap();
+                                let recording = settings.recordings.get(playback.1);
+                                (
+                                    if !capturing && Tracker::read(player_looping_handle.clone()) {
+                                        recording
+                                            .and_then(|recording| recording.loop_region(length))
+                                    } else {
+                                        None
+                                    },
+                                    recording.map_or(Duration::ZERO, |recording| {
+                                        recording.start_position(length)
+                                    }),
+                                )
+                            };
+                            let mut end = region.map_or(length, |region| region.1);
+
+                            // Starts part way through if recovering from an error, capturing always starts from the beginning
+                            // Otherwise skips to the recording's start offset, the loop start takes over while looping
+                            let offset = match player_resume_handle.write().unwrap().take() {
+                                Some(value) if !capturing && value < length => value,
+                                _ if capturing => Duration::ZERO,
+                                _ => region.map_or(start_offset, |region| region.0),
+                            };
+                            Tracker::write(player_position_handle.clone(), offset);
+
+                            let mut sound =
+                                match track.play(sound_data.start_position(offset.as_secs_f64())) {
+                                    // Plays the track
+                                    Ok(value) => value,
+                                    Err(error) => {
+                                        player_notifier.write(
+                                            player_error_handle.clone(),
+                                            Some(Error::PlaybackError(error.to_string())),
+                                        );
+                                        continue 'two;
+                                    }
+                                };
+
+                            let mut start =
+                                Instant::now().checked_sub(offset).unwrap_or(Instant::now()); // Gets the time the track would have started playing from the beginning
+                            let mut frame = (offset.as_millis() / PLAYER_TICK.as_millis()) as usize;
+                            let mut edited_frame: usize = 0;
+                            let mut bypassed = false; // Whether the effects were flattened last loop
+                            let mut overridden = [None; 6]; // Dials the user had taken over from the captured frames last loop
+                            let (glide, tween, autosave) = {
+                                let settings = player_settings_handle.read().unwrap();
+                                (
+                                    Duration::from_millis(settings.pan_glide_ms as u64),
+                                    parameter_tween(settings.parameter_glide_ms),
+                                    Duration::from_secs(settings.capture_autosave_seconds as u64),
+                                )
+                            };
+                            let mut autosaved = Instant::now(); // When captured inputs were last saved
+                            let mut base = SnapShot::none(); // Existing frames that captured inputs are added to
+                            let mut snapshot = if let Playback::Capture(ref data) = playback.0 {
+                                // Captures into a new snapshot so edited frame only counts the new frames
+                                capturing = true;
+                                base = data.clone();
+                                Tracker::write(player_captured_handle.clone(), 0);
+                                SnapShot::new()
+                            } else if let Playback::Input(ref data) = playback.0 {
+                                data.clone()
+                            } else if let Playback::Generic(ref data) = playback.0 {
+                                data.clone()
+                            } else {
+                                SnapShot::new()
+                            };
+                            if frame > 0 {
+                                // Lines the snapshot up with where playback starts and puts back the values that were active there
+                                edited_frame = snapshot.frame_at(frame);
+                                if edited_frame > 0 {
+                                    Tracker::write(
+                                        player_frame_handle.clone(),
+                                        snapshot.frames[edited_frame - 1].0,
+                                    );
+                                    if let Playback::Input(_) = playback.0 {
+                                        equaliser.set(snapshot.frames[edited_frame - 1].0, tween);
+                                    }
+                                }
+                            }
+                            while start.elapsed() < end {
+                                // Loops while the time spent playing is less than the length of the audio
+                                match audio_receiver.try_recv() {
+                                    // Blocks until a file, stop, or playback message is received
+                                    Ok(
+                                        message @ (Message::StopAudio | Message::StopAndReply(_)),
+                                    ) => {
+                                        if capturing {
+                                            snapshot.frames.remove(0);
+                                            match snapshot.merged(&base).save(&File::truncate(&mut file.clone(), ".", 0)) // Saves new snapshot data to file if capturing
+                                            {
+                                                Some(error) => {
+                                                    player_notifier.write(
+                                                        player_error_handle.clone(),
+                                                        Some(error),
+                                                    );
+                                                }
+                                                None => SnapShot::discard_capture(&File::truncate(
+                                                    &mut file.clone(),
+                                                    ".",
+                                                    0,
+                                                )),
+                                            };
+                                        }
+                                        if let Message::StopAndReply(reply) = message {
+                                            let _ = reply.send(()); // The captured inputs are on disk now
+                                        }
+                                        continue 'two; // Stops audio
+                                    }
+                                    Ok(Message::File(_)) => {
+                                        if capturing {
+                                            snapshot.frames.remove(0);
+                                            match snapshot.merged(&base).save(&File::truncate(
+                                                &mut file.clone(),
+                                                ".",
+                                                0,
+                                            )) {
+                                                Some(error) => {
+                                                    player_notifier.write(
+                                                        player_error_handle.clone(),
+                                                        Some(error),
+                                                    );
+                                                }
+                                                None => SnapShot::discard_capture(&File::truncate(
+                                                    &mut file.clone(),
+                                                    ".",
+                                                    0,
+                                                )),
+                                            };
+                                        }
+                                        continue 'one; // Loads new audio data
+                                    }
+                                    Ok(Message::PlayAudio((Playback::Capture(_), _))) => {
+                                        if capturing {
+                                            snapshot.frames.remove(0);
+                                            match snapshot.merged(&base).save(&File::truncate(
+                                                &mut file.clone(),
+                                                ".",
+                                                0,
+                                            )) {
+                                                Some(error) => {
+                                                    player_notifier.write(
+                                                        player_error_handle.clone(),
+                                                        Some(error),
+                                                    );
+                                                }
+                                                None => SnapShot::discard_capture(&File::truncate(
+                                                    &mut file.clone(),
+                                                    ".",
+                                                    0,
+                                                )),
+                                            };
+                                        }
+                                        continue 'two; // Stops playing
+                                    }
+                                    Ok(Message::PlayAudio((value, index))) => {
+                                        // Changes type of playback
+                                        playback.0 = value;
+                                        playback.1 = index; // The index moves if a recording was added while playing
+                                        if let Playback::Input(ref frames) = playback.0 {
+                                            snapshot = frames.clone();
+                                            Tracker::write(
+                                                player_frame_handle.clone(),
+                                                if snapshot.frames.len() < edited_frame {
+                                                    snapshot.frames[edited_frame].0
+                                                } else {
+                                                    snapshot.frames[snapshot.frames.len() - 1].0
+                                                },
+                                            );
+                                        }
+                                    }
+                                    Ok(Message::Seek(position)) => {
+                                        // Captured inputs always cover the recording from the start so capturing can't seek
+                                        if !capturing && position < length {
+                                            sound.seek_to(position.as_secs_f64());
+                                            start = Instant::now()
+                                                .checked_sub(position)
+                                                .unwrap_or(Instant::now());
+                                            frame = (position.as_millis() / PLAYER_TICK.as_millis())
+                                                as usize;
+                                            // Replays the automation from the new position
+                                            edited_frame = snapshot.frame_at(frame);
+                                            if edited_frame > 0 {
+                                                Tracker::write(
+                                                    player_frame_handle.clone(),
+                                                    snapshot.frames[edited_frame - 1].0,
+                                                );
+                                                if let Playback::Input(_) = playback.0 {
+                                                    equaliser.set(
+                                                        snapshot.frames[edited_frame - 1].0,
+                                                        tween,
+                                                    );
+                                                }
+                                            }
+                                        }
+                                    }
+                                    _ => (),
+                                }
+                                let bypass = Tracker::read(player_bypass_handle.clone());
+                                if let Playback::Input(_) = playback.0 {
+                                    // If playback type equals input playback, dials the user has taken over follow them instead
+                                    let overrides = Tracker::read(player_overrides_handle.clone());
+                                    if edited_frame < snapshot.frames.len() {
+                                        if frame == snapshot.frames[edited_frame].1 as usize {
+                                            // If current frame is the same as the one saved in the the snapshot data
+                                            Tracker::write(
+                                                player_frame_handle.clone(),
+                                                snapshot.frames[edited_frame].0,
+                                            ); // Write dial data
+                                               // Set the handle values to edit the audio based on snapshot data
+                                            if !bypass {
+                                                let ticks_to_next = snapshot
+                                                    .frames
+                                                    .get(edited_frame + 1)
+                                                    .map(|next| {
+                                                        (next.1 as usize).saturating_sub(frame)
+                                                    });
+                                                equaliser.set_captured(
+                                                    with_overrides(
+                                                        snapshot.frames[edited_frame].0,
+                                                        overrides,
+                                                    ),
+                                                    tween,
+                                                    pan_glide(glide, ticks_to_next),
+                                                );
+                                            }
+                                        }
+                                    }
+                                    if overrides != overridden && !bypass {
+                                        // A dial was taken over, moved or handed back between captured frames
+                                        equaliser.set(
+                                            with_overrides(
+                                                Tracker::read(player_frame_handle.clone()),
+                                                overrides,
+                                            ),
+                                            tween,
+                                        );
+                                    }
+                                    overridden = overrides;
+                                } else {
+                                    let settings = player_settings_handle.read().unwrap();
+
+                                    if let Playback::Capture(_) = playback.0 {
+                                        // If capturing inputs, adds the dial values if they've changed enough since the last ones kept
+                                        if snapshot.capture(
+                                            Recording::parse(&settings.recordings[playback.1]),
+                                            frame as i32,
+                                            settings.capture_filter(),
+                                        ) {
+                                            edited_frame += 1;
+                                            // The first frame only holds the starting values and is removed when capturing stops
+                                            Tracker::write(
+                                                player_captured_handle.clone(),
+                                                snapshot.frames.len() - 1,
+                                            );
+                                        }
+                                    }
+
+                                    // Set the handle values based on settings
+                                    if !bypass {
+                                        equaliser.set(
+                                            Recording::parse(&settings.recordings[playback.1]),
+                                            tween,
+                                        );
+                                    }
+
+                                    drop(settings); // Drop read access of settings
+                                }
+
+                                // Anything not captured in snapshots always follows the recording
+                                if let Some(recording) = player_settings_handle
+                                    .read()
+                                    .unwrap()
+                                    .recordings
+                                    .get(playback.1)
+                                {
+                                    if !bypass {
+                                        equaliser.follow(recording, tween);
+                                    }
+                                    // Picks up loop points and playback type changes made while playing
+                                    end = match recording.loop_region(length) {
+                                        Some(region)
+                                            if !capturing
+                                                && Tracker::read(player_looping_handle.clone()) =>
+                                        {
+                                            region.1
+                                        }
+                                        _ => length,
+                                    };
+                                }
+
+                                if bypass && !bypassed {
+                                    equaliser.flatten(tween);
+                                } else if !bypass && bypassed {
+                                    if let Playback::Input(_) = playback.0 {
+                                        // Snapshot values are only set when they change so the last one is put back here
+                                        equaliser.set(
+                                            with_overrides(
+                                                Tracker::read(player_frame_handle.clone()),
+                                                Tracker::read(player_overrides_handle.clone()),
+                                            ),
+                                            tween,
+                                        );
+                                    }
+                                }
+                                bypassed = bypass;
+
+                                if !capturing {
+                                    // Increases edited frame if equal to snapshot data so it remains in sync if you swap playback type
+                                    if frame
+                                        == snapshot.frames[if edited_frame < snapshot.frames.len() {
+                                            edited_frame
+                                        } else {
+                                            edited_frame - 1
+                                        }]
+                                        .1 as usize
+                                    {
+                                        edited_frame += 1;
+                                    }
+                                }
+                                if capturing
+                                    && !autosave.is_zero()
+                                    && autosaved.elapsed() >= autosave
+                                {
+                                    // Keeps what's been captured so far in case the app closes before capturing stops
+                                    let mut partial = snapshot.clone();
+                                    partial.frames.remove(0);
+                                    if let Some(error) = partial
+                                        .merged(&base)
+                                        .save_capture(&File::truncate(&mut file.clone(), ".", 0))
+                                    {
+                                        player_notifier
+                                            .write(player_error_handle.clone(), Some(error));
+                                    }
+                                    autosaved = Instant::now();
+                                }
+                                frame += 1;
+                                Tracker::write(player_position_handle.clone(), start.elapsed());
+                                if let Playback::Input(_) = playback.0 {
+                                    // Lets the UI show where in the captured automation playback is
+                                    let total = snapshot.frames.len();
+                                    let upcoming = edited_frame < total
+                                        && (snapshot.frames[edited_frame].1 as usize)
+                                            .saturating_sub(frame)
+                                            <= AUTOMATION_LOOKAHEAD;
+                                    Tracker::write(
+                                        player_automation_handle.clone(),
+                                        (edited_frame.min(total), total, upcoming),
+                                    );
+                                }
+
+                                thread::sleep(PLAYER_TICK); // Sleeps thread for 20 milliseconds
+                            }
+
+                            player_notifier.write(player_state.clone(), PlayerState::Finished); // Tells the tracker that playback is finished
+
+                            if capturing {
+                                // Saves captured inputs to file
+                                match snapshot.merged(&base).save(&File::truncate(
+                                    &mut file.clone(),
+                                    ".",
+                                    0,
+                                )) {
+                                    Some(error) => {
+                                        player_notifier
+                                            .write(player_error_handle.clone(), Some(error));
+                                    }
+                                    None => SnapShot::discard_capture(&File::truncate(
+                                        &mut file.clone(),
+                                        ".",
+                                        0,
+                                    )),
+                                };
+                            }
+                        }
+                        Ok(Message::StopAudio) => continue 'two, // Waits to play again
+                        Ok(Message::StopAndReply(reply)) => {
+                            let _ = reply.send(()); // Anything captured was saved when playback ended
+                            continue 'two;
+                        }
+                        Ok(Message::Seek(_)) => continue 'two, // Nothing is playing to seek
+                        _ => {
+                            player_notifier
+                                .write(player_error_handle.clone(), Some(Error::MessageError)); // Writes error if incorrect message sent to thread
+                            continue 'two;
+                        }
+                    }
+                }
+            }
+        }) {
+        Ok(_) => (),
+        Err(_) => {
+            notifier.write(errors.clone(), Some(Error::PlayerThreadError));
+        }
+    };
+
+    audio_sender
+}
+
+pub fn start_metronome(
+    tracker: &Tracker,
+    errors: &Arc<RwLock<Option<Error>>>,
+    notifier: &Notifier,
+) -> mpsc::Sender<Message> {
+    // Clicks along with a recording when the output it plays through won't be recorded
+    let (metronome_sender, metronome_receiver) = mpsc::channel::<Message>(); // Message sender and reciever for the metronome
+
+    // Creates references for required values in metronome thread
+    let metronome_error_handle = errors.clone();
+    let metronome_settings_handle = tracker.settings.clone();
+    let metronome_notifier = notifier.clone();
+    match thread::Builder::new() // Creates metronome thread
+        .name(String::from("Metronome"))
+        .spawn(move || {
+            loop {
+                match metronome_receiver.recv() {
+                    // Blocks until recording starts
+                    Ok(Message::StartRecording) => (),
+                    _ => {
+                        metronome_notifier
+                            .write(metronome_error_handle.clone(), Some(Error::MessageError));
+                        continue;
+                    }
+                }
+
+                let (bpm, beats_per_bar, device, sound) = {
+                    let settings = metronome_settings_handle.read().unwrap();
+                    (
+                        settings.metronome_bpm.max(1),
+                        settings.beats_per_bar.max(1),
+                        settings.output_device.clone(),
+                        settings.click_sound.clone(),
+                    )
+                };
+                // The accent is played on the first beat of each bar, a custom file that's gone falls back to the beep
+                let (click, accent) = match sound.sounds() {
+                    Ok(value) => value,
+                    Err(error) => {
+                        metronome_notifier.write(metronome_error_handle.clone(), Some(error));
+                        (click_sound(1000.0), click_sound(1500.0))
+                    }
+                };
+
+                // Clicks go to the same output as playback, which the recorder captures unless a different device was chosen
+                // so the metronome is only started by the record button when that output can't be heard by the recorder
+                let mut audio_manager =
+                    match AudioManager::<DefaultBackend>::new(output_settings(&device).0) {
+                        Ok(value) => value,
+                        Err(error) => {
+                            metronome_notifier.write(
+                                metronome_error_handle.clone(),
+                                Some(Error::PlaybackError(error.to_string())),
+                            );
+                            continue;
+                        }
+                    };
+                let mut track = match audio_manager.add_sub_track(TrackBuilder::new()) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        metronome_notifier.write(
+                            metronome_error_handle.clone(),
+                            Some(Error::PlaybackError(error.to_string())),
+                        );
+                        continue;
+                    }
+                };
+
+                let interval = Duration::from_secs_f64(60.0 / bpm as f64);
+                let start = Instant::now();
+                let mut beat: u32 = 0;
+                loop {
+                    let _ = track.play(if beat % beats_per_bar == 0 {
+                        accent.clone()
+                    } else {
+                        click.clone()
+                    });
+                    beat += 1;
+
+                    // Waits for the next beat based on the start time so the clicks don't drift
+                    let next = start + interval * beat;
+                    match metronome_receiver
+                        .recv_timeout(next.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(Message::StopRecording) => break,
+                        Ok(_) => {
+                            metronome_notifier
+                                .write(metronome_error_handle.clone(), Some(Error::MessageError));
+                            break;
+                        }
+                        Err(_) => (), // Time for the next click
+                    }
+                }
+            }
+        }) {
+        Ok(_) => (),
+        Err(_) => {
+            notifier.write(errors.clone(), Some(Error::PlayerThreadError));
+        }
+    };
+
+    metronome_sender
+}
+
+pub fn start_monitor(
+    tracker: &Tracker,
+    errors: &Arc<RwLock<Option<Error>>>,
+    notifier: &Notifier,
+) -> mpsc::Sender<Message> {
+    // Plays the input back while recording, through the same filters as playback
+    let (monitor_sender, monitor_receiver) = mpsc::channel::<Message>(); // Message sender and reciever for the input monitor
+
+    // Creates references for required values in monitor thread
+    let monitor_error_handle = errors.clone();
+    let monitor_notifier = notifier.clone();
+    let monitor_settings_handle = tracker.settings.clone();
+    let monitor_buffer = tracker.monitor_buffer.clone();
+    let monitor_recording = tracker.monitor_recording.clone();
+    let monitor_bypass_handle = tracker.bypass.clone();
+    let monitoring = tracker.monitoring.clone();
+    match thread::Builder::new() // Creates monitor thread
+        .name(String::from("Monitor"))
+        .spawn(move || loop {
+            match monitor_receiver.recv() {
+                // Blocks until recording starts
+                Ok(Message::StartRecording) => (),
+                _ => {
+                    monitor_notifier.write(monitor_error_handle.clone(), Some(Error::MessageError));
+                    continue;
+                }
+            }
+
+            // Monitors through the same output as playback, the player warns if it's missing
+            let device = monitor_settings_handle
+                .read()
+                .unwrap()
+                .output_device
+                .clone();
+            let mut audio_manager =
+                match AudioManager::<DefaultBackend>::new(output_settings(&device).0) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        monitor_notifier.write(
+                            monitor_error_handle.clone(),
+                            Some(Error::PlaybackError(error.to_string())),
+                        );
+                        continue;
+                    }
+                };
+
+            // Uses the same filters as playback so the monitor sounds the same
+            let mut builder = TrackBuilder::new();
+            let mut equaliser = Equaliser::add_to(&mut builder);
+            let mut track = match audio_manager.add_sub_track(builder) {
+                Ok(value) => value,
+                Err(error) => {
+                    monitor_notifier.write(
+                        monitor_error_handle.clone(),
+                        Some(Error::PlaybackError(error.to_string())),
+                    );
+                    continue;
+                }
+            };
+
+            monitor_buffer.lock().unwrap().clear(); // Gets rid of input from an earlier recording
+            match track.play(MonitorSound::new(
+                monitor_buffer.clone(),
+                RECORDING_SAMPLE_RATE,
+            )) {
+                Ok(_) => (),
+                Err(error) => {
+                    monitor_notifier.write(
+                        monitor_error_handle.clone(),
+                        Some(Error::PlaybackError(error.to_string())),
+                    );
+                    continue;
+                }
+            }
+            Tracker::write(monitoring.clone(), true);
+
+            loop {
+                // Keeps the filters in sync with the dials, or flat if monitor EQ is turned off
+                let recording = if monitor_settings_handle.read().unwrap().monitor_eq
+                    && !Tracker::read(monitor_bypass_handle.clone())
+                {
+                    monitor_recording.read().unwrap().clone()
+                } else {
+                    Recording::new(&String::new())
+                };
+                equaliser.set(recording.parse(), Tween::default());
+                equaliser.follow(&recording, Tween::default());
+
+                match monitor_receiver.recv_timeout(Duration::from_millis(20)) {
+                    Ok(Message::StopRecording) => break,
+                    Ok(_) => {
+                        monitor_notifier
+                            .write(monitor_error_handle.clone(), Some(Error::MessageError));
+                        break;
+                    }
+                    Err(_) => (),
+                }
+            }
+
+            Tracker::write(monitoring.clone(), false);
+        }) {
+        Ok(_) => (),
+        Err(_) => {
+            notifier.write(errors.clone(), Some(Error::PlayerThreadError));
+        }
+    };
+
+    monitor_sender
+}
+
+pub fn start_device_watch(
+    tracker: &Tracker,
+    errors: &Arc<RwLock<Option<Error>>>,
+    notifier: &Notifier,
+) {
+    // Lists outputs off the UI thread as asking the system can take long enough to stall it
+    let outputs_handle = tracker.outputs.clone();
+    let watching_handle = tracker.watching_devices.clone();
+    let device_notifier = notifier.clone();
+    match thread::Builder::new() // Creates device thread
+        .name(String::from("Devices"))
+        .spawn(move || loop {
+            if Tracker::read(watching_handle.clone()) {
+                let outputs = Some(Outputs::list());
+                if *outputs_handle.read().unwrap() != outputs {
+                    Tracker::write(outputs_handle.clone(), outputs);
+                    device_notifier.wake(); // The UI checks the new list straight away
+                }
+            } else if outputs_handle.read().unwrap().is_some() {
+                // Forgotten while nothing needs it so the first look after something starts is a fresh one
+                Tracker::write(outputs_handle.clone(), None);
+            }
+            thread::sleep(DEVICE_POLL_INTERVAL);
+        }) {
+        Ok(_) => (),
+        Err(_) => {
+            notifier.write(errors.clone(), Some(Error::PlayerThreadError));
+        }
+    };
+}
@@ -0,0 +1,464 @@
+// Data shared between the UI and the audio threads, along with the output devices they play through
+
+// -------- Imports --------
+use crate::*; // Constants, errors, and the other modules through the crate root
+
+// -------- Enums --------
+// Types of playback
+#[derive(PartialEq)]
+pub enum Playback {
+    Input(SnapShot),
+    Capture(SnapShot), // Frames that the captured inputs are added to, empty when they replace what was there
+    Generic(SnapShot),
+}
+
+// What the player thread has, set by it and read by the UI in place of separate loaded and finished flags
+#[derive(Clone, Copy, PartialEq)]
+pub enum PlayerState {
+    Empty, // Nothing is loaded, or the UI has asked for another recording and it hasn't arrived yet
+    Loaded, // A recording is loaded and ready to play
+    Finished, // Playback reached the end and the UI hasn't moved on from it yet, the recording stays loaded
+}
+
+// Mpsc messages
+pub enum Message {
+    File(String),                 // Path
+    PlayAudio((Playback, usize)), // Type, index of current recording
+    StopAudio,
+    StopAndReply(mpsc::Sender<()>), // Stops like StopAudio and replies once any captured inputs have been saved
+    Seek(Duration),                 // Where to jump to in the playing recording
+    StartRecording,
+    StopRecording,
+}
+
+// -------- Structs --------
+// Wakes the UI when a thread changes something it shows, so errors and finished playback are seen straight away
+#[derive(Clone)]
+pub struct Notifier {
+    wake: Arc<dyn Fn(Arc<AtomicBool>) -> bool + Send + Sync>, // Runs the UI's checks and clears the pending flag, false once the UI has closed
+    enabled: Arc<AtomicBool>, // Whether waking is turned on, the UI still checks on its timers either way
+    pending: Arc<AtomicBool>, // Whether a wake is already waiting to run, so a burst of changes only wakes it once
+}
+
+impl Notifier {
+    pub fn new(
+        enabled: bool,
+        wake: impl Fn(Arc<AtomicBool>) -> bool + Send + Sync + 'static,
+    ) -> Notifier {
+        // The UI passes in how it's woken so the threads don't need to know about the window
+        Notifier {
+            wake: Arc::new(wake),
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            pending: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, AtomicOrdering::Relaxed);
+    }
+
+    pub fn write<T>(&self, handle: Arc<RwLock<T>>, set: T) {
+        // Writes tracked data the same as the tracker does, then wakes the UI to show it
+        Tracker::write(handle, set);
+        self.wake();
+    }
+
+    pub fn edit<'a>(&self, settings: &'a Arc<RwLock<Settings>>) -> RwLockWriteGuard<'a, Settings> {
+        // Locks the settings for changing the same as the tracker does, then wakes the UI to update the unsaved notice
+        // The UI waits on the lock before it compares, so it sees the change once the guard is dropped
+        let guard = Tracker::edit(settings);
+        self.wake();
+        guard
+    }
+
+    pub fn wake(&self) {
+        // Has the UI check for errors, unplugged devices, unsaved changes, and finished playback now instead of on its next timer
+        if !self.enabled.load(AtomicOrdering::Relaxed)
+            || self.pending.swap(true, AtomicOrdering::Relaxed)
+        {
+            return;
+        }
+        if !(self.wake)(self.pending.clone()) {
+            self.pending.store(false, AtomicOrdering::Relaxed); // The event loop has closed so there's nothing to wake
+        }
+    }
+}
+
+// What was stopped when the device it was using was unplugged
+#[derive(Clone, Copy, PartialEq)]
+pub enum Interrupted {
+    Audio(usize, Duration), // Recording index and how far through playback was
+    Input(usize, Duration),
+    Capture, // Captured inputs are saved when capturing stops so it isn't started again
+    Recording,
+}
+
+// How the device an activity is using has changed since the last check
+#[derive(Clone, Copy, PartialEq)]
+pub enum DeviceChange {
+    Unchanged,
+    Lost(Interrupted),     // The device went away while in use
+    Restored(Interrupted), // The device that went away is back
+}
+
+// Follows the device playback or recording is using so it can be paused when the device is unplugged
+#[derive(Clone, PartialEq)]
+pub struct DeviceWatch {
+    pub last: Option<String>, // Device that was in use at the last check
+    pub lost: Option<(String, Interrupted)>, // Device that went away and what it stopped
+}
+
+impl DeviceWatch {
+    pub fn new() -> DeviceWatch {
+        DeviceWatch {
+            last: None,
+            lost: None,
+        }
+    }
+
+    pub fn check(
+        &mut self,
+        active: Option<Interrupted>,
+        current: impl FnOnce() -> Option<String>,
+    ) -> DeviceChange {
+        // Compares the device in use now with the one from the last check
+        // Devices are only looked up while something is running or waiting for its device to come back
+        if active.is_some() {
+            self.lost = None; // Something was started again by hand so there's nothing to pick up
+        } else if self.lost.is_none() {
+            self.last = None; // The device is looked up again when something starts
+            return DeviceChange::Unchanged;
+        }
+
+        let current = current();
+        let change = match self.lost.take() {
+            Some((device, interrupted)) if current.as_ref() == Some(&device) => {
+                DeviceChange::Restored(interrupted)
+            }
+            Some(lost) => {
+                self.lost = Some(lost);
+                DeviceChange::Unchanged
+            }
+            None => match (active, &self.last) {
+                (Some(interrupted), Some(last)) if current.as_ref() != Some(last) => {
+                    self.lost = Some((last.clone(), interrupted));
+                    DeviceChange::Lost(interrupted)
+                }
+                _ => DeviceChange::Unchanged,
+            },
+        };
+        self.last = current;
+        change
+    }
+
+    pub fn needs_devices(&self, active: Option<Interrupted>) -> bool {
+        // Whether the next check will look up devices, so they're only polled while something could be affected
+        active.is_some() || self.lost.is_some()
+    }
+}
+
+// Output devices as the device thread last saw them, so the UI never has to ask the system itself
+#[derive(Clone, PartialEq)]
+pub struct Outputs {
+    pub connected: Vec<String>,  // Names of every output that's plugged in
+    pub default: Option<String>, // Output the system sends audio to when nothing is chosen
+}
+
+impl Outputs {
+    pub fn list() -> Outputs {
+        // Asks the system for its outputs, which can take a while so it's kept off the UI thread where it can be
+        Outputs {
+            connected: output_devices(),
+            default: cpal::default_host()
+                .default_output_device()
+                .and_then(|device| device.name().ok()),
+        }
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.connected.iter().any(|device| device == name)
+    }
+
+    pub fn current(&self, chosen: &str) -> Option<String> {
+        // Name of the device audio is going to, the chosen one if it's connected or else the system default
+        if !chosen.is_empty() && self.has(chosen) {
+            Some(chosen.to_string())
+        } else {
+            self.default.clone()
+        }
+    }
+
+    pub fn chosen(&self, chosen: &str) -> Option<String> {
+        // The chosen device while it's connected, used to tell when playback has lost the device it was sent to
+        // Playback through the system default follows the default so it's never counted as lost
+        self.has(chosen).then(|| chosen.to_string())
+    }
+
+    pub fn captured(&self, watch: &DeviceWatch) -> Option<String> {
+        // Device the recorder is capturing, which stays the one it started on while that's connected
+        // Plugging in another output changes the system default but doesn't take away the one being recorded
+        let in_use = match &watch.lost {
+            Some((device, _)) => Some(device), // Looks out for the lost device coming back first
+            None => watch.last.as_ref(),
+        };
+        match in_use {
+            Some(device) if self.has(device) => Some(device.clone()),
+            _ => self.default.clone(),
+        }
+    }
+}
+
+// Output levels of each channel, measured after every effect so boosts that clip show up
+#[derive(Clone, Copy, PartialEq)]
+pub struct Levels {
+    pub peak: [f32; 2], // Loudest sample since the UI last read the levels
+    pub rms: [f32; 2],  // Average loudness of the last block of audio
+    pub held: [f32; 2], // Loudest sample since the peak hold was last reset
+}
+
+impl Levels {
+    pub fn new() -> Levels {
+        Levels {
+            peak: [0.0, 0.0],
+            rms: [0.0, 0.0],
+            held: [0.0, 0.0],
+        }
+    }
+
+    pub fn measure(&mut self, frames: &[Frame]) {
+        // Adds a block of audio to the levels
+        if frames.is_empty() {
+            return;
+        }
+        let mut squares = [0.0, 0.0];
+        for frame in frames {
+            for (channel, sample) in [frame.left, frame.right].into_iter().enumerate() {
+                self.peak[channel] = self.peak[channel].max(sample.abs());
+                squares[channel] += sample * sample;
+            }
+        }
+        for channel in 0..2 {
+            self.rms[channel] = (squares[channel] / frames.len() as f32).sqrt();
+            self.held[channel] = self.held[channel].max(self.peak[channel]);
+        }
+    }
+
+    pub fn clipped(&self) -> bool {
+        // Whether either channel has gone over full scale since the hold was reset
+        self.held[0] >= 1.0 || self.held[1] >= 1.0
+    }
+}
+
+// Keeps track of the settings, the recording thread, whether recordings are being played, and the values of the dials during a set of audio frames
+pub struct Tracker {
+    pub settings: Arc<RwLock<Settings>>,
+    pub saved: Arc<RwLock<Settings>>, // Settings as they were last written to disk, so unsaved changes can be spotted
+    pub locked: Arc<RwLock<Recording>>, // Values to hold while locked
+    pub player: Arc<RwLock<PlayerState>>, // What the player thread has loaded and whether it has finished playing
+    pub snapshot_frame_values: Arc<RwLock<[i32; 6]>>, // Values of the currently active snapshot frame group
+    pub empty_recording: Arc<RwLock<bool>>,           // Whether the newest reecording is empty
+    pub recording_check: Arc<RwLock<bool>>, // Whether a recording is in progress or just happened
+    pub save_requested: Arc<RwLock<Option<Instant>>>, // When the last unsaved dial edit happened
+    pub recorded_frames: Arc<RwLock<u64>>, // How many frames the current recording has been going for
+    pub dropouts: Arc<RwLock<u64>>,        // How many times the current recording has lost samples
+    pub dropped_recording: Arc<RwLock<Option<String>>>, // Name of a finished recording that lost samples and hasn't been flagged yet
+    pub metronome_running: Arc<RwLock<bool>>, // Whether the metronome was started with the current recording
+    pub monitor_running: Arc<RwLock<bool>>, // Whether the monitor was started with the current recording
+    pub monitoring: Arc<RwLock<bool>>,      // Whether the recorder should send input to the monitor
+    pub monitor_buffer: Arc<Mutex<VecDeque<Frame>>>, // Input waiting to be monitored
+    pub monitor_recording: Arc<RwLock<Recording>>, // Current recording whose effects are applied to the monitor
+    pub bypass: Arc<RwLock<bool>>, // Whether every effect is flattened so the unprocessed audio can be heard
+    pub automation_point: Arc<RwLock<(usize, usize, bool)>>, // Snapshot frames reached during input playback, how many there are, and whether the next one is coming up
+    pub dial_overrides: Arc<RwLock<[Option<i32>; 6]>>, // Values the user has taken over dials with during input playback, used instead of the captured ones
+    pub captured_points: Arc<RwLock<usize>>, // How many automation points have been captured so far while capturing inputs
+    pub empty_file: Arc<RwLock<Option<String>>>, // Name of a recording that couldn't be played because it has no audio
+    pub position: Arc<RwLock<Duration>>, // How far through the current recording playback is
+    pub resume_from: Arc<RwLock<Option<Duration>>>, // Where the next playback should start if it isn't the beginning
+    pub levels: Arc<RwLock<Levels>>,                // Output levels of playback for the meter
+    pub looping: Arc<RwLock<bool>>, // Whether the current recording is set to loop, so playback keeps to its loop region
+    pub library_stats: Arc<RwLock<Option<LibraryStats>>>, // Totals shown in the options panel, worked out when it's first opened
+    pub playback_device: Arc<RwLock<DeviceWatch>>, // Output playback is going to, so it can be paused if it's unplugged
+    pub recording_device: Arc<RwLock<DeviceWatch>>, // Output the recorder is capturing, so it can be stopped if it's unplugged
+    pub outputs: Arc<RwLock<Option<Outputs>>>, // Outputs from the device thread's last look, nothing while it isn't looking
+    pub watching_devices: Arc<RwLock<bool>>, // Whether anything needs the device thread to keep looking
+}
+
+impl Tracker {
+    pub fn new(settings: Settings) -> Tracker {
+        // Creates a new tracker
+        Tracker {
+            saved: Arc::new(RwLock::new(settings.clone())), // Settings are saved before the tracker is made if they had to be created
+            settings: Arc::new(RwLock::new(settings)),
+            locked: Arc::new(RwLock::new(Recording::new(&String::new()))),
+            player: Arc::new(RwLock::new(PlayerState::Empty)),
+            snapshot_frame_values: Arc::new(RwLock::new([0, 0, 0, 0, 0, 0])),
+            empty_recording: Arc::new(RwLock::new(true)),
+            recording_check: Arc::new(RwLock::new(false)),
+            save_requested: Arc::new(RwLock::new(None)),
+            recorded_frames: Arc::new(RwLock::new(0)),
+            dropouts: Arc::new(RwLock::new(0)),
+            dropped_recording: Arc::new(RwLock::new(None)),
+            metronome_running: Arc::new(RwLock::new(false)),
+            monitor_running: Arc::new(RwLock::new(false)),
+            monitoring: Arc::new(RwLock::new(false)),
+            monitor_buffer: Arc::new(Mutex::new(VecDeque::new())),
+            monitor_recording: Arc::new(RwLock::new(Recording::new(&String::new()))),
+            bypass: Arc::new(RwLock::new(false)),
+            automation_point: Arc::new(RwLock::new((0, 0, false))),
+            captured_points: Arc::new(RwLock::new(0)),
+            dial_overrides: Arc::new(RwLock::new([None; 6])),
+            empty_file: Arc::new(RwLock::new(None)),
+            position: Arc::new(RwLock::new(Duration::ZERO)),
+            resume_from: Arc::new(RwLock::new(None)),
+            levels: Arc::new(RwLock::new(Levels::new())),
+            looping: Arc::new(RwLock::new(false)),
+            library_stats: Arc::new(RwLock::new(None)),
+            playback_device: Arc::new(RwLock::new(DeviceWatch::new())),
+            recording_device: Arc::new(RwLock::new(DeviceWatch::new())),
+            outputs: Arc::new(RwLock::new(None)),
+            watching_devices: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    pub fn write<T>(handle: Arc<RwLock<T>>, set: T) {
+        // Wrtes data to tracked data
+        let mut writer = handle.write().unwrap();
+        *writer = set;
+    }
+
+    pub fn read<T: Copy>(handle: Arc<RwLock<T>>) -> T {
+        // Reads and returns tracked data
+        let reader = handle.read().unwrap();
+        *reader
+    }
+
+    pub fn edit(settings: &Arc<RwLock<Settings>>) -> RwLockWriteGuard<'_, Settings> {
+        // Locks the settings for changing and marks them as possibly unsaved
+        // The lock is taken first so the unsaved check can't compare them before the change is made
+        let guard = settings.write().unwrap();
+        SETTINGS_CHANGED.store(true, AtomicOrdering::Relaxed);
+        guard
+    }
+
+    pub fn save_settings(settings: &Settings, saved: Arc<RwLock<Settings>>) -> Option<Error> {
+        // Writes settings to disk and only counts them as saved if the write worked
+        let error = save(DataType::Settings(settings.clone()), SETTINGS_FILE);
+        if error.is_none() {
+            Tracker::write(saved, settings.clone());
+            SETTINGS_CHANGED.store(true, AtomicOrdering::Relaxed);
+        }
+        error
+    }
+
+    pub fn unsaved(settings: &Arc<RwLock<Settings>>, saved: &Arc<RwLock<Settings>>) -> bool {
+        // Whether the settings in memory differ from the ones on disk
+        *settings.read().unwrap() != *saved.read().unwrap()
+    }
+}
+
+// -------- Functions --------
+pub fn raise_priority(raise: bool) -> Option<Warning> {
+    // Asks the system to run the current thread ahead of others
+    // This often needs permissions the app doesn't have, in which case the thread keeps running as normal
+    if !raise {
+        return None;
+    }
+    match set_current_thread_priority(ThreadPriority::Max) {
+        Ok(_) => None,
+        Err(_) => Some(Warning::PriorityNotRaised),
+    }
+}
+
+pub fn output_devices() -> Vec<String> {
+    // Names of the output devices that can be played through right now
+    match cpal::default_host().output_devices() {
+        Ok(devices) => devices.filter_map(|device| device.name().ok()).collect(),
+        Err(_) => vec![],
+    }
+}
+
+pub fn output_choices(saved: &str, available: &[String]) -> Vec<String> {
+    // Devices shown in the options panel, the system default comes first and is blank
+    // The saved device stays on the list while it's unplugged so picking it isn't lost
+    let mut choices = vec![default_output_device()];
+    choices.extend(available.iter().filter(|name| !name.is_empty()).cloned());
+    if !saved.is_empty() && !available.iter().any(|name| name == saved) {
+        choices.push(saved.to_string());
+    }
+    choices.dedup();
+    choices
+}
+
+pub fn output_label(choice: &str, available: &[String]) -> SharedString {
+    // How a device is shown in the options panel
+    if choice.is_empty() {
+        SharedString::from("System default")
+    } else if !available.iter().any(|name| name == choice) {
+        SharedString::from(format!("{} (missing)", choice))
+    } else {
+        SharedString::from(choice)
+    }
+}
+
+pub fn captured_output(chosen: &str) -> bool {
+    // Whether audio sent to an output ends up in recordings
+    // The recorder captures whatever the system's default output plays, and a chosen device that's missing falls back to it
+    let outputs = Outputs::list();
+    outputs.default.is_none() || outputs.current(chosen) == outputs.default
+}
+
+pub fn output_settings(name: &str) -> (AudioManagerSettings<DefaultBackend>, Option<Warning>) {
+    // Settings for an audio manager that plays through the chosen device
+    // Falls back to the system default if the device has been unplugged
+    if name.is_empty() {
+        return (AudioManagerSettings::default(), None);
+    }
+    let device = match cpal::default_host().output_devices() {
+        Ok(mut devices) => {
+            devices.find(|device| device.name().map_or(false, |found| found == name))
+        }
+        Err(_) => None,
+    };
+    match device {
+        Some(device) => (
+            AudioManagerSettings {
+                backend_settings: CpalBackendSettings {
+                    device: Some(device),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        ),
+        None => (
+            AudioManagerSettings::default(),
+            Some(Warning::OutputMissing),
+        ),
+    }
+}
+
+pub fn follow_recording(
+    sender: &mpsc::Sender<Message>,
+    running: Arc<RwLock<bool>>,
+    stopping: bool,
+    enabled: bool,
+) -> Option<Error> {
+    // Starts or stops a thread that runs alongside a recording
+    let message = if stopping {
+        if !Tracker::read(running.clone()) {
+            return None; // Wasn't started with this recording
+        }
+        Tracker::write(running.clone(), false);
+        Message::StopRecording
+    } else if enabled {
+        Tracker::write(running.clone(), true);
+        Message::StartRecording
+    } else {
+        return None;
+    };
+
+    match sender.send(message) {
+        Ok(_) => None,
+        Err(_) => Some(Error::MessageError),
+    }
+}
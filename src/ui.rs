@@ -0,0 +1,469 @@
+// Sends the library's data to the window and reads the user's edits back from it
+// The library doesn't know about the window, so these are added to its types here
+
+// -------- Imports --------
+use crate::*; // The library and the generated UI types through the crate root
+use slint::{Model, ModelRc, SharedString, VecModel, Weak}; // Imports for UI
+use std::sync::atomic::Ordering as AtomicOrdering; // Clearing the notifier's pending flag
+
+// -------- Traits --------
+// Values that are shown in the UI on their own
+pub trait SendToUi {
+    fn send(&self, ui: &AppWindow);
+}
+
+// Band shapes picked in the UI
+pub trait FilterKindUi {
+    fn read(ui: &AppWindow) -> [FilterKind; 5];
+}
+
+// What happens when a recording finishes, which the UI has its own type for
+pub trait PlaybackModeUi {
+    fn from_ui(playback: PlaybackType) -> PlaybackMode;
+    fn to_ui(self) -> PlaybackType;
+}
+
+// Edits made in the UI that syncing applies to the settings
+pub trait ChangesUi {
+    fn read(ui: &AppWindow) -> Changes;
+}
+
+// Values of the chosen recording that aren't shown on the dials
+pub trait RecordingUi {
+    fn send_current(&self, ui: &AppWindow);
+}
+
+// The options panel and everything else the settings keep in step with the UI
+pub trait SettingsUi {
+    fn send_default_preset(&self, ui: &AppWindow);
+    fn send_options(&self, ui: &AppWindow);
+    fn read_options(&mut self, ui: &AppWindow);
+    fn sync(&mut self, storage: &dyn Storage, ui: &AppWindow);
+}
+
+// -------- Impls --------
+impl From<audio::Severity> for Severity {
+    fn from(severity: audio::Severity) -> Severity {
+        // Converts to the severity the UI styles notifications with
+        match severity {
+            audio::Severity::Info => Severity::Info,
+            audio::Severity::Warning => Severity::Warning,
+            audio::Severity::Error => Severity::Error,
+        }
+    }
+}
+
+impl SendToUi for Error {
+    fn send(&self, ui: &AppWindow) {
+        // Takes an error value and updates the ui
+        ui.set_error_notification(self.get_text());
+        ui.set_notification_severity(self.severity().into());
+        ui.set_error_recieved(true);
+    }
+}
+
+impl SendToUi for Warning {
+    fn send(&self, ui: &AppWindow) {
+        // Takes a warning value and updates the ui
+        if ui.get_error_recieved() && ui.get_notification_severity() == Severity::Error {
+            return; // Don't hide an error that the user hasn't seen yet
+        }
+        ui.set_error_notification(self.get_text());
+        ui.set_notification_severity(Severity::Warning);
+        ui.set_error_recieved(true);
+    }
+}
+
+impl SendToUi for LibraryStats {
+    fn send(&self, ui: &AppWindow) {
+        // Sends the totals to the options panel
+        let seconds = self.duration.as_secs();
+        ui.set_library_recordings(self.names.len() as i32);
+        ui.set_library_duration(SharedString::from(format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )));
+        ui.set_library_size(SharedString::from(format!(
+            "{:.1} MB",
+            self.size as f64 / 1_000_000.0
+        )));
+    }
+}
+
+impl FilterKindUi for FilterKind {
+    fn read(ui: &AppWindow) -> [FilterKind; 5] {
+        // Gets the filter kinds from the UI
+        let mut kinds = default_filter_kinds();
+        for index in 0..5 {
+            match ui.get_current_filter_kinds().row_data(index) {
+                Some(value) => kinds[index] = FilterKind::from_index(value),
+                None => return default_filter_kinds(),
+            }
+        }
+
+        kinds
+    }
+}
+
+impl PlaybackModeUi for PlaybackMode {
+    fn from_ui(playback: PlaybackType) -> PlaybackMode {
+        // Converts from the playback type the UI uses
+        match playback {
+            PlaybackType::AutoNext => PlaybackMode::AutoNext,
+            PlaybackType::Loop => PlaybackMode::Loop,
+            PlaybackType::None => PlaybackMode::None,
+        }
+    }
+
+    fn to_ui(self) -> PlaybackType {
+        // Converts to the playback type the UI uses
+        match self {
+            PlaybackMode::AutoNext => PlaybackType::AutoNext,
+            PlaybackMode::Loop => PlaybackType::Loop,
+            PlaybackMode::None => PlaybackType::None,
+        }
+    }
+}
+
+impl ChangesUi for Changes {
+    fn read(ui: &AppWindow) -> Changes {
+        // Gets the edits from the UI
+        let mut dials = [0, 0, 0, 0, 0, 0];
+        for index in 0..6 {
+            // Gets dial values from UI
+            match ui.get_current_dial_values().row_data(index) {
+                Some(value) => dials[index] = value,
+                None => {
+                    dials = [0, 0, 0, 0, 0, 0];
+                    break;
+                }
+            };
+        }
+
+        Changes {
+            dials,
+            filter_kinds: FilterKind::read(ui),
+            high_pass: ui.get_current_high_pass(),
+            low_pass: ui.get_current_low_pass(),
+            invert_left: ui.get_current_invert_left(),
+            invert_right: ui.get_current_invert_right(),
+            auto_makeup: ui.get_current_auto_makeup(),
+            current_recording: ui.get_current_recording().max(0) as usize,
+            dials_edited: ui.get_dials_edited(),
+            new_preset: ui.get_new_preset_created(),
+            deleted_preset: if ui.get_preset_deleted() {
+                Some(ui.get_deleted_preset_index().max(0) as usize)
+            } else {
+                None
+            },
+            preset_names: if ui.get_preset_renamed() {
+                Some(
+                    ui.get_preset_names()
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect(),
+                )
+            } else {
+                None
+            },
+            deleted_recording: if ui.get_recording_deleted() {
+                Some(ui.get_deleted_recording_index().max(0) as usize)
+            } else {
+                None
+            },
+            recording_names: if ui.get_recording_renamed() {
+                Some(
+                    ui.get_recording_names()
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect(),
+                )
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl RecordingUi for Recording {
+    fn send_current(&self, ui: &AppWindow) {
+        // Sends the values that aren't shown on the dials to the UI
+        ui.set_current_filter_kinds(FilterKind::send(self.filter_kinds));
+        ui.set_current_high_pass(self.high_pass);
+        ui.set_current_low_pass(self.low_pass);
+        ui.set_current_invert_left(self.invert_left);
+        ui.set_current_invert_right(self.invert_right);
+        ui.set_current_auto_makeup(self.auto_makeup);
+        ui.set_current_loop(self.loop_summary());
+        ui.set_current_start(self.start_summary());
+        ui.set_current_format(self.format_summary());
+    }
+}
+
+impl SettingsUi for Settings {
+    fn send_default_preset(&self, ui: &AppWindow) {
+        // Sends the choices for the preset new recordings start with, keeping a deleted one on the list while it's chosen
+        let mut labels = vec![SharedString::from("None, use the dials below")];
+        labels.extend(
+            self.presets
+                .iter()
+                .map(|preset| SharedString::from(preset.name.clone())),
+        );
+        let index = match &self.default_preset {
+            Some(name) => match self.presets.iter().position(|preset| preset.name == *name) {
+                Some(index) => index + 1,
+                None => {
+                    labels.push(SharedString::from(format!("{} (deleted)", name)));
+                    labels.len() - 1
+                }
+            },
+            None => 0,
+        };
+        ui.set_default_preset_labels(ModelRc::new(VecModel::from(labels)));
+        ui.set_default_preset(index as i32);
+    }
+
+    fn send_options(&self, ui: &AppWindow) {
+        // Sends the values shown in the options panel to the UI
+        ui.set_recording_warn_minutes((self.recording_warn_after / 60) as i32);
+        ui.set_metronome_enabled(self.metronome);
+        ui.set_metronome_bpm(self.metronome_bpm as i32);
+        ui.set_beats_per_bar(self.beats_per_bar as i32);
+        ui.set_monitor_input(self.monitor_input);
+        ui.set_monitor_eq(self.monitor_eq);
+        ui.set_normalise_new_recordings(
+            self.post_record_actions
+                .contains(&PostRecordAction::Normalise),
+        );
+        ui.set_export_new_recordings(self.post_record_actions.contains(&PostRecordAction::Export));
+        ui.set_playback(self.playback.to_ui());
+        ui.set_shuffle(self.shuffle);
+        ui.set_fallback_limit(self.fallback_limit as i32);
+        ui.set_auto_save_minutes(self.auto_save_minutes as i32);
+        ui.set_follow_queue(self.follow_queue);
+        ui.set_auto_next_gap_seconds(self.auto_next_gap_seconds as i32);
+        ui.set_snapshot_folder(self.snapshot_folder);
+        ui.set_pan_glide_ms(self.pan_glide_ms as i32);
+        ui.set_parameter_glide_ms(self.parameter_glide_ms as i32);
+        ui.set_capture_autosave_seconds(self.capture_autosave_seconds as i32);
+        ui.set_raise_thread_priority(self.raise_thread_priority);
+        ui.set_push_updates(self.push_updates);
+        let available = output_devices();
+        let choices = output_choices(&self.output_device, &available);
+        ui.set_output_device(
+            choices
+                .iter()
+                .position(|choice| *choice == self.output_device)
+                .unwrap_or(0) as i32,
+        );
+        ui.set_output_device_labels(ModelRc::new(VecModel::from(
+            choices
+                .iter()
+                .map(|choice| output_label(choice, &available))
+                .collect::<Vec<SharedString>>(),
+        )));
+        ui.set_output_device_names(ModelRc::new(VecModel::from(
+            choices
+                .iter()
+                .map(|choice| SharedString::from(choice.clone()))
+                .collect::<Vec<SharedString>>(),
+        )));
+        ui.set_delete_empty_recordings(self.delete_empty_recordings);
+        ui.set_min_sound_ms(self.min_sound_ms as i32);
+        ui.set_trim_leading_silence(self.trim_leading_silence);
+        ui.set_left_input_channel(self.input_channels[0] as i32 + 1);
+        ui.set_right_input_channel(self.input_channels[1] as i32 + 1);
+        ui.set_naming_scheme(self.naming_scheme.to_ui());
+        ui.set_name_collision(self.name_collision.to_ui());
+        ui.set_fallback_grouping(self.fallback_grouping.to_ui());
+        ui.set_library_channels(self.library_channels as i32 - 1);
+        ui.set_resample(self.resample.to_ui());
+        ui.set_theme(self.theme.to_ui());
+        ui.set_skip_missing(self.skip_missing);
+        ui.set_click_kind(self.click_sound.to_ui());
+        ui.set_capture_everything(self.capture_everything);
+        ui.set_new_preset_start(self.new_preset_start.to_ui());
+        ui.set_capture_band_step(self.capture_band_step as i32);
+        ui.set_capture_pan_step(self.capture_pan_step as i32);
+        ui.set_capture_window_ms(self.capture_window_ms as i32);
+        if let ClickSound::Custom(path) = &self.click_sound {
+            ui.set_custom_click(SharedString::from(path.clone()));
+        }
+        ui.set_export_format(self.export_format.to_ui());
+        ui.set_export_directory(SharedString::from(
+            self.export_directory.clone().unwrap_or_default(),
+        ));
+        self.send_default_preset(ui);
+        ui.set_name_prefix(SharedString::from(self.name_prefix.clone()));
+        ui.set_queue_summary(self.queue_summary());
+        ui.set_band_labels(ModelRc::new(VecModel::from(
+            (0..5)
+                .map(|band| SharedString::from(self.band_label(band)))
+                .collect::<Vec<SharedString>>(),
+        )));
+        ui.set_default_dials_summary(SharedString::from(
+            self.default_dials
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<String>>()
+                .join(" / "),
+        ));
+    }
+
+    fn read_options(&mut self, ui: &AppWindow) {
+        // Updates settings data with the values from the options panel
+        self.recording_warn_after = ui.get_recording_warn_minutes().max(0) as u32 * 60;
+        self.metronome = ui.get_metronome_enabled();
+        self.metronome_bpm = ui.get_metronome_bpm().max(1) as u32;
+        self.beats_per_bar = ui.get_beats_per_bar().max(1) as u32;
+        self.monitor_input = ui.get_monitor_input();
+        self.monitor_eq = ui.get_monitor_eq();
+        self.playback = PlaybackMode::from_ui(ui.get_playback());
+        self.shuffle = ui.get_shuffle();
+        self.fallback_limit = ui.get_fallback_limit().max(0) as u32;
+        self.auto_save_minutes = ui.get_auto_save_minutes().max(0) as u32;
+        self.follow_queue = ui.get_follow_queue();
+        self.auto_next_gap_seconds = ui.get_auto_next_gap_seconds().max(0) as u32;
+        self.snapshot_folder = ui.get_snapshot_folder();
+        self.pan_glide_ms = ui.get_pan_glide_ms().max(0) as u32;
+        self.parameter_glide_ms = ui.get_parameter_glide_ms().max(0) as u32;
+        self.capture_autosave_seconds = ui.get_capture_autosave_seconds().max(0) as u32;
+        self.raise_thread_priority = ui.get_raise_thread_priority();
+        self.push_updates = ui.get_push_updates();
+        self.output_device = ui
+            .get_output_device_names()
+            .row_data(ui.get_output_device().max(0) as usize)
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        self.delete_empty_recordings = ui.get_delete_empty_recordings();
+        self.min_sound_ms = ui.get_min_sound_ms().max(0) as u32;
+        self.trim_leading_silence = ui.get_trim_leading_silence();
+        self.input_channels = [
+            (ui.get_left_input_channel() - 1).max(0) as u32,
+            (ui.get_right_input_channel() - 1).max(0) as u32,
+        ];
+        self.naming_scheme = NamingScheme::from_ui(ui.get_naming_scheme());
+        self.name_collision = NameCollision::from_ui(ui.get_name_collision());
+        self.fallback_grouping = FallbackGrouping::from_ui(ui.get_fallback_grouping());
+        self.library_channels = (ui.get_library_channels() + 1).clamp(1, 2) as u16;
+        self.resample = ResampleMode::from_ui(ui.get_resample());
+        self.theme = Theme::from_ui(ui.get_theme());
+        self.skip_missing = ui.get_skip_missing();
+        self.capture_everything = ui.get_capture_everything();
+        self.new_preset_start = NewPresetStart::from_ui(ui.get_new_preset_start());
+        self.capture_band_step = ui.get_capture_band_step().max(1) as u32;
+        self.capture_pan_step = ui.get_capture_pan_step().max(1) as u32;
+        self.capture_window_ms = ui.get_capture_window_ms().max(0) as u32;
+        if ui.get_click_kind() != 3 {
+            self.click_sound = ClickSound::from_ui(ui.get_click_kind()); // A custom file is only kept once it's loaded
+        }
+        self.export_format = ExportFormat::from_ui(ui.get_export_format());
+        let export_directory = ui.get_export_directory().trim().to_string();
+        self.export_directory = if export_directory.is_empty() {
+            None
+        } else {
+            Some(export_directory)
+        };
+        // The entry after the presets is a deleted preset that's still chosen, so it's left as it is
+        match ui.get_default_preset() as usize {
+            0 => self.default_preset = None,
+            index if index <= self.presets.len() => {
+                self.default_preset = Some(self.presets[index - 1].name.clone())
+            }
+            _ => (),
+        }
+        self.name_prefix = clean_prefix(&ui.get_name_prefix());
+        self.band_labels = ui
+            .get_band_labels()
+            .iter()
+            .map(|label| label.trim().to_string())
+            .collect();
+        // Actions that were already on keep their place in the order
+        for (action, on) in [
+            (
+                PostRecordAction::Normalise,
+                ui.get_normalise_new_recordings(),
+            ),
+            (PostRecordAction::Export, ui.get_export_new_recordings()),
+        ] {
+            if !on {
+                self.post_record_actions.retain(|other| *other != action);
+            } else if !self.post_record_actions.contains(&action) {
+                self.post_record_actions.push(action);
+            }
+        }
+    }
+
+    fn sync(&mut self, storage: &dyn Storage, ui: &AppWindow) {
+        // Sync settings data with files and UI
+        let (errors, deleted) = self.apply(storage, &Changes::read(ui));
+
+        if deleted {
+            ui.set_can_delete(true); // Tells the UI that the item has finished being deleted to enable more things to be deleted
+        }
+        for error in errors {
+            error.send(ui); // Sends error values to UI
+        }
+    }
+}
+
+impl AppState {
+    pub fn from_parts(playback: PlaybackState, recording: bool) -> AppState {
+        // The state for a kind of playback with or without a recording going
+        match (playback, recording) {
+            (PlaybackState::Stopped, false) => AppState::Stopped,
+            (PlaybackState::Audio, false) => AppState::Audio,
+            (PlaybackState::Input, false) => AppState::Input,
+            (PlaybackState::Capture, false) => AppState::Capture,
+            (PlaybackState::Stopped, true) => AppState::Recording,
+            (PlaybackState::Audio, true) => AppState::RecordingAudio,
+            (PlaybackState::Input, true) => AppState::RecordingInput,
+            (PlaybackState::Capture, true) => AppState::RecordingCapture,
+        }
+    }
+
+    pub fn playback(self) -> PlaybackState {
+        match self {
+            AppState::Stopped | AppState::Recording => PlaybackState::Stopped,
+            AppState::Audio | AppState::RecordingAudio => PlaybackState::Audio,
+            AppState::Input | AppState::RecordingInput => PlaybackState::Input,
+            AppState::Capture | AppState::RecordingCapture => PlaybackState::Capture,
+        }
+    }
+
+    pub fn recording(self) -> bool {
+        self != AppState::from_parts(self.playback(), false)
+    }
+
+    pub fn playing(self, playback: PlaybackState) -> AppState {
+        // Changes what's playing and leaves a recording going if there is one
+        AppState::from_parts(playback, self.recording())
+    }
+
+    pub fn with_recording(self, recording: bool) -> AppState {
+        // Starts or stops recording and leaves playback as it is
+        AppState::from_parts(self.playback(), recording)
+    }
+}
+
+// -------- Functions --------
+pub fn notifier(ui: Weak<AppWindow>, enabled: bool) -> Notifier {
+    // Wakes the UI by running the checks its timers would, straight away
+    Notifier::new(enabled, move |pending| {
+        ui.upgrade_in_event_loop(move |ui| {
+            pending.store(false, AtomicOrdering::Relaxed);
+            ui.invoke_check_for_errors();
+            ui.invoke_watch_devices();
+            ui.invoke_sync_unsaved();
+            if ui.get_audio_or_input_playback() {
+                ui.invoke_sync_playing_with_backend();
+            }
+        })
+        .is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,23 @@
+// Tests for the app state the UI switches between
+use super::*;
+
+#[test]
+fn playback_and_recording_change_without_touching_each_other() {
+    let playbacks = [
+        PlaybackState::Stopped,
+        PlaybackState::Audio,
+        PlaybackState::Input,
+        PlaybackState::Capture,
+    ];
+    for playback in playbacks {
+        for recording in [false, true] {
+            let state = AppState::from_parts(playback, recording);
+            assert!(state.playback() == playback);
+            assert!(state.recording() == recording);
+            assert!(state.with_recording(!recording).playback() == playback);
+            assert!(state.playing(PlaybackState::Stopped).recording() == recording);
+        }
+    }
+    assert!(AppState::Stopped.with_recording(true) == AppState::Recording);
+    assert!(AppState::Recording.playing(PlaybackState::Capture) == AppState::RecordingCapture);
+}